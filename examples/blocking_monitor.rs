@@ -0,0 +1,40 @@
+//! Monitor an existing pod from plain synchronous code with `flexserv_deployer::blocking`
+//! (no `#[tokio::main]`). Needs the `blocking` feature.
+//!
+//! ```text
+//! export TAPIS_TENANT_URL=https://tacc.tapis.io
+//! export TAPIS_TOKEN=...
+//! export POD_ID=p...
+//! export VOLUME_ID=v...
+//! cargo run --features blocking --example blocking_monitor
+//! ```
+
+use flexserv_deployer::{blocking, Backend, FlexServInstance, FlexServPodDeployment};
+
+fn main() -> Result<(), flexserv_deployer::DeploymentError> {
+    env_logger::init();
+
+    let tenant_url = std::env::var("TAPIS_TENANT_URL").expect("TAPIS_TENANT_URL is required");
+    let tapis_token = std::env::var("TAPIS_TOKEN").expect("TAPIS_TOKEN is required");
+    let tapis_user = std::env::var("TAPIS_USER").unwrap_or_else(|_| "testuser".to_string());
+    let pod_id = std::env::var("POD_ID").expect("POD_ID is required");
+    let volume_id = std::env::var("VOLUME_ID").expect("VOLUME_ID is required");
+
+    let server = FlexServInstance::new(
+        tenant_url,
+        tapis_user,
+        "no-model-yet".to_string(),
+        None,
+        None,
+        None,
+        Backend::Transformers { command: vec![] },
+    );
+    let deployment = FlexServPodDeployment::from_existing(server, tapis_token, pod_id, volume_id);
+
+    let result = blocking::monitor(&deployment)?;
+    let pod = result.as_pod().expect("pod deployment returns PodResult");
+    println!("pod_id:  {}", pod.pod_id);
+    println!("status:  {}", pod.status.unwrap_or("unknown"));
+    println!("pod_url: {}", pod.pod_url.unwrap_or("(not yet available)"));
+    Ok(())
+}
@@ -40,7 +40,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         } else {
             eprintln!("Usage: POD_URL=... FLEXSERV_TOKEN=... cargo run --example call_pod");
             eprintln!("   or: cargo run --example call_pod -- <POD_URL> <FLEXSERV_TOKEN>");
-            eprintln!("Example token for GPT-2 (no FLEXSERV_SECRET): openai-community_gpt2");
+            eprintln!("Token: flexserv_deployer::flexserv_token(FLEXSERV_SECRET, model_id), e.g. openai-community_gpt2");
             std::process::exit(1);
         };
 
@@ -88,7 +88,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // 3. Completions (GPT-2 has no chat template; use /v1/completions with "prompt")
-    let model_path = format!("/app/models/{}", token);
+    let model_path = format!("{}/{}", flexserv_deployer::FLEXSERV_MODEL_REPO, token);
     println!("--- POST /v1/completions ---");
     let url = format!("{}/v1/completions", base_url);
     let body_json = serde_json::json!({
@@ -63,6 +63,7 @@ async fn main() -> Result<(), DeploymentError> {
         Backend::Transformers { command: vec![] },
     );
 
+    println!("Deploying {}", server.summary());
     let mut deployment = FlexServPodDeployment::new(server, tapis_token);
 
     let result = deployment.create().await?;
@@ -77,6 +78,7 @@ async fn main() -> Result<(), DeploymentError> {
             tapis_user,
             tapis_tenant,
             model_id,
+            ..
         } => {
             println!("Create succeeded:");
             println!(
@@ -98,8 +100,9 @@ async fn main() -> Result<(), DeploymentError> {
                 "  model_id:   {}  (use this in request JSON: \"model\": \"...\" )",
                 model_id
             );
-            let auth_token = model_id.replace('/', "_");
-            println!("  auth_token: {} (use as Authorization: Bearer or X-FlexServ-Secret; add FLEXSERV_SECRET prefix if you set it)", auth_token);
+            let secret = std::env::var("FLEXSERV_SECRET").unwrap_or_default();
+            let auth_token = flexserv_deployer::flexserv_token(&secret, &model_id);
+            println!("  auth_token: {} (use as Authorization: Bearer or X-FlexServ-Secret)", auth_token);
             println!("  pod_info: {}", pod_info.chars().collect::<String>());
         }
         DeploymentResult::HPCResult { .. } => unreachable!("pod deployment returns PodResult"),
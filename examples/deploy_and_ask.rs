@@ -0,0 +1,149 @@
+//! Example: deploy a model, ask it one question, and tear everything down.
+//!
+//! Creates the volume and pod with `CreateMode::WaitReady` (so `create()` returns once the pod
+//! is AVAILABLE/RUNNING) and `warmup: true`, sends one `/v1/completions` request, prints the
+//! answer, then terminates the pod and volume. Terminate runs even when a step fails.
+//!
+//! Set env vars then run:
+//!
+//!   export TAPIS_TENANT_URL=https://tacc.tapis.io
+//!   export TAPIS_TOKEN=<your-jwt>
+//!   cargo run --example deploy_and_ask -- openai-community/gpt2 "The capital of France is"
+//!
+//! Optional:
+//!
+//!   export TAPIS_USER=<your-user>          # default testuser
+//!   export FLEXSERV_SECRET=<secret>        # prefix of the FlexServ token
+//!   export HF_TOKEN=<your-hf-token>        # gated models
+//!   export MAX_TOKENS=32                   # completion length (default 32)
+//!   export READY_TIMEOUT_SECS=1200         # how long to wait for the pod (default 20 minutes)
+
+use flexserv_deployer::{
+    model_path, Backend, BackoffConfig, CreateMode, DeploymentResult, FlexServDeployment,
+    FlexServInstance, FlexServPodDeployment, PodDeploymentOptions,
+};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    let (tenant_url, tapis_token) = match (
+        std::env::var("TAPIS_TENANT_URL"),
+        std::env::var("TAPIS_TOKEN"),
+    ) {
+        (Ok(t), Ok(token)) => (t, token),
+        _ => {
+            eprintln!("Usage: TAPIS_TENANT_URL=... TAPIS_TOKEN=... cargo run --example deploy_and_ask -- <MODEL_ID> <PROMPT>");
+            std::process::exit(1);
+        }
+    };
+    let (model_id, prompt) = match std::env::args().collect::<Vec<_>>().as_slice() {
+        [_, model, prompt, ..] => (model.clone(), prompt.clone()),
+        _ => {
+            eprintln!("Usage: cargo run --example deploy_and_ask -- <MODEL_ID> <PROMPT>");
+            std::process::exit(1);
+        }
+    };
+    let tapis_user = std::env::var("TAPIS_USER").unwrap_or_else(|_| "testuser".to_string());
+    let secret = std::env::var("FLEXSERV_SECRET").unwrap_or_default();
+    let max_tokens: u32 = std::env::var("MAX_TOKENS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(32);
+    let ready_timeout = std::env::var("READY_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1200);
+
+    let server = FlexServInstance::new(
+        tenant_url,
+        tapis_user,
+        model_id.clone(),
+        None,
+        std::env::var("HF_TOKEN").ok(),
+        None,
+        Backend::Transformers { command: vec![] },
+    );
+    let options = PodDeploymentOptions {
+        flexserv_secret: Some(secret.clone()),
+        create_mode: CreateMode::WaitReady {
+            backoff: BackoffConfig {
+                max_delay: Duration::from_secs(15),
+                timeout: Some(Duration::from_secs(ready_timeout)),
+                ..Default::default()
+            },
+        },
+        warmup: true,
+        ..Default::default()
+    };
+
+    println!("Deploying {}", server.summary());
+    let mut deployment = FlexServPodDeployment::with_options(server, tapis_token, options);
+
+    let outcome = deploy_and_ask(&mut deployment, &model_id, &secret, &prompt, max_tokens).await;
+
+    println!(
+        "Terminating pod {} and volume {}",
+        deployment.pod_id, deployment.volume_id
+    );
+    if let Err(e) = deployment.terminate().await {
+        eprintln!("Terminate failed (clean up manually): {}", e);
+    }
+
+    match outcome {
+        Ok(answer) => {
+            println!("Prompt: {}", prompt);
+            println!("Answer: {}", answer);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Failed: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Create the pod (waiting until ready and warmed up), then send one completion and return its text.
+async fn deploy_and_ask(
+    deployment: &mut FlexServPodDeployment,
+    model_id: &str,
+    secret: &str,
+    prompt: &str,
+    max_tokens: u32,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let result = deployment.create().await?;
+    let pod_url = match result {
+        DeploymentResult::PodResult {
+            pod_url: Some(url), ..
+        } => url,
+        _ => return Err("pod is ready but has no URL".into()),
+    };
+    println!("Pod ready at {}", pod_url);
+
+    let token = flexserv_deployer::flexserv_token(secret, model_id);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(120))
+        .build()?;
+    let resp = client
+        .post(format!("{}/v1/completions", pod_url.trim_end_matches('/')))
+        .header("X-FlexServ-Secret", &token)
+        .bearer_auth(&token)
+        .json(&serde_json::json!({
+            "model": model_path(model_id),
+            "prompt": prompt,
+            "max_tokens": max_tokens,
+        }))
+        .send()
+        .await?;
+    let status = resp.status();
+    let text = resp.text().await?;
+    if !status.is_success() {
+        return Err(format!("completion returned {}: {}", status, text).into());
+    }
+    let body: serde_json::Value = serde_json::from_str(&text)?;
+    Ok(body["choices"][0]["text"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string())
+}
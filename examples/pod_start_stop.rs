@@ -11,9 +11,7 @@
 //! cargo run --example pod_start_stop
 //! ```
 
-use flexserv_deployer::{
-    Backend, DeploymentResult, FlexServDeployment, FlexServInstance, FlexServPodDeployment,
-};
+use flexserv_deployer::{Backend, FlexServDeployment, FlexServInstance, FlexServPodDeployment};
 
 #[tokio::main]
 async fn main() -> Result<(), flexserv_deployer::DeploymentError> {
@@ -52,12 +50,7 @@ async fn main() -> Result<(), flexserv_deployer::DeploymentError> {
     let stopped = deployment.stop().await?;
     println!("stop => {:#?}", stopped);
 
-    match stopped {
-        DeploymentResult::PodResult { pod_url, .. } => {
-            println!("After stop, pod_url hint: {:?}", pod_url);
-        }
-        DeploymentResult::HPCResult { .. } => unreachable!(),
-    }
+    println!("After stop, pod_url hint: {:?}", stopped.pod_url());
 
     Ok(())
 }
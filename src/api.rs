@@ -0,0 +1,573 @@
+//! HTTP API request types, kept separate from the domain types so the wire contract can stay
+//! stable while [FlexServInstance] and [PodDeploymentOptions] evolve.
+//!
+//! With the `server` feature, [create_pod] serves `POST /pods` on top of [CreatePodRequest] and
+//! [list_models] serves `GET /models` ([ModelsQuery]). Given a
+//! [DeploymentRegistry](crate::registry::DeploymentRegistry) as app data, created pods are
+//! recorded there, [list_pods] serves `GET /pods` ([PodsQuery]), and [terminate_pod] serves
+//! `DELETE /pods/{pod_id}`.
+
+use crate::backend::Backend;
+use crate::deployment::PodDeploymentOptions;
+use crate::server::{FlexServInstance, ValidationError};
+use serde::{Deserialize, Serialize};
+
+/// Body of `POST /pods`. Only `tenant_url`, `tapis_user`, `model_id`, and `backend` are required;
+/// everything else falls back to the [PodDeploymentOptions] defaults. Unknown fields are rejected.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePodRequest {
+    pub tenant_url: String,
+    pub tapis_user: String,
+    pub model_id: String,
+    pub backend: Backend,
+    #[serde(default)]
+    pub model_revision: Option<String>,
+    #[serde(default)]
+    pub hf_token: Option<String>,
+    #[serde(default)]
+    pub default_embedding_model: Option<String>,
+    #[serde(default)]
+    pub deployment_id: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub volume_size_mb: Option<i32>,
+    #[serde(default)]
+    pub cpu_request: Option<i32>,
+    #[serde(default)]
+    pub cpu_limit: Option<i32>,
+    #[serde(default)]
+    pub mem_request_mb: Option<i32>,
+    #[serde(default)]
+    pub mem_limit_mb: Option<i32>,
+    #[serde(default)]
+    pub gpus: Option<i32>,
+}
+
+impl TryFrom<CreatePodRequest> for (FlexServInstance, PodDeploymentOptions) {
+    type Error = ValidationError;
+
+    /// Validate the request (via [FlexServInstance::builder]) and split it into the server
+    /// config and pod options.
+    fn try_from(req: CreatePodRequest) -> Result<Self, Self::Error> {
+        let mut builder = FlexServInstance::builder()
+            .tenant_url(req.tenant_url)
+            .tapis_user(req.tapis_user)
+            .model(req.model_id)
+            .hf_token(req.hf_token)
+            .default_embedding_model(req.default_embedding_model)
+            .backend(req.backend);
+        if let Some(revision) = req.model_revision {
+            builder = builder.model_revision(revision);
+        }
+        let server = builder.build()?;
+        let options = PodDeploymentOptions {
+            deployment_id: req.deployment_id,
+            image: req.image,
+            volume_size_mb: req.volume_size_mb,
+            cpu_request: req.cpu_request,
+            cpu_limit: req.cpu_limit,
+            mem_request_mb: req.mem_request_mb,
+            mem_limit_mb: req.mem_limit_mb,
+            gpus: req.gpus,
+            ..Default::default()
+        };
+        Ok((server, options))
+    }
+}
+
+/// Query for `GET /models`. Without `pod_id` the endpoint lists the available backends; with
+/// `tenant_url` and `pod_id` it lists what that running pod serves (`X-Tapis-Token` and
+/// `X-FlexServ-Token` headers required).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ModelsQuery {
+    #[serde(default)]
+    pub tenant_url: Option<String>,
+    #[serde(default)]
+    pub pod_id: Option<String>,
+}
+
+/// Query for `GET /pods`: the tenant whose registered pods the caller (`X-Tapis-Token`) wants.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PodsQuery {
+    #[serde(default)]
+    pub tenant_url: Option<String>,
+}
+
+/// JSON error body returned by the HTTP API: `{"error": "..."}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiError {
+    pub error: String,
+}
+
+#[cfg(feature = "server")]
+pub use handlers::{create_pod, json_config, list_models, list_pods, terminate_pod};
+
+#[cfg(feature = "server")]
+mod handlers {
+    use super::{ApiError, CreatePodRequest, ModelsQuery, PodsQuery};
+    use crate::backend::{registered_custom_backends, Backend, BUILTIN_BACKENDS};
+    use crate::deployment::{
+        DeploymentError, FlexServDeployment, FlexServPodDeployment, PodDeploymentOptions,
+    };
+    use crate::registry::{DeploymentRecord, DeploymentRegistry};
+    use crate::server::FlexServInstance;
+    use actix_web::{error, web, HttpRequest, HttpResponse};
+
+    /// Non-empty value of header `name`.
+    fn header<'a>(req: &'a HttpRequest, name: &str) -> Option<&'a str> {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .filter(|t| !t.is_empty())
+    }
+
+    /// HTTP status for a deployment error, by its root cause.
+    fn error_response(e: &DeploymentError) -> HttpResponse {
+        let mut resp = match e.root_cause() {
+            DeploymentError::InvalidConfiguration(_)
+            | DeploymentError::TapisBadRequest(_)
+            | DeploymentError::IncompatibleImage { .. } => HttpResponse::BadRequest(),
+            DeploymentError::TapisAuthFailed(_) => HttpResponse::Unauthorized(),
+            _ => HttpResponse::BadGateway(),
+        };
+        resp.json(ApiError {
+            error: e.to_string(),
+        })
+    }
+
+    /// JSON extractor config that turns malformed or incomplete bodies into a 400 [ApiError]
+    /// (e.g. "missing field `model_id`") instead of actix's plain-text default.
+    pub fn json_config() -> web::JsonConfig {
+        web::JsonConfig::default().error_handler(|err, _req| {
+            let body = ApiError {
+                error: err.to_string(),
+            };
+            error::InternalError::from_response(err, HttpResponse::BadRequest().json(body)).into()
+        })
+    }
+
+    /// `POST /pods`: validate a [CreatePodRequest] and create the pod with the caller's
+    /// `X-Tapis-Token`. 400 on invalid input, 401 without a token. TAPIS is checked with
+    /// [FlexServPodDeployment::check_tapis_reachable] first, so a bad token or unreachable tenant
+    /// is a clean 401/502 before anything is created; later failures are mapped by their root
+    /// cause (the message names the failing create stage). The new pod is recorded in the
+    /// [DeploymentRegistry] app data, when there is one.
+    pub async fn create_pod(
+        req: HttpRequest,
+        body: web::Json<CreatePodRequest>,
+        registry: Option<web::Data<DeploymentRegistry>>,
+    ) -> HttpResponse {
+        let Some(token) = header(&req, "X-Tapis-Token") else {
+            return HttpResponse::Unauthorized().json(ApiError {
+                error: "X-Tapis-Token header is required".to_string(),
+            });
+        };
+        let parts: Result<(FlexServInstance, PodDeploymentOptions), _> =
+            body.into_inner().try_into();
+        let (server, options) = match parts {
+            Ok(parts) => parts,
+            Err(e) => {
+                return HttpResponse::BadRequest().json(ApiError {
+                    error: e.to_string(),
+                })
+            }
+        };
+        let mut deployment =
+            FlexServPodDeployment::with_options(server, token.to_string(), options);
+        if let Err(e) = deployment.check_tapis_reachable().await {
+            return error_response(&e);
+        }
+        match deployment.create().await {
+            Ok(result) => {
+                if let Some(registry) = registry {
+                    registry.insert(DeploymentRecord::new(&deployment, &result));
+                }
+                HttpResponse::Created().json(result.to_api_json())
+            }
+            Err(e) => error_response(&e),
+        }
+    }
+
+    /// `GET /pods?tenant_url=...`: the caller's pods in the [DeploymentRegistry], as
+    /// `{"pods": [DeploymentRecord]}`. 401 without `X-Tapis-Token`, 400 without `tenant_url`.
+    /// Only records for that tenant whose pod the token can see in TAPIS
+    /// ([FlexServPodDeployment::visible_pod_ids]) are listed, and the token is sent only to the
+    /// tenant the caller names.
+    pub async fn list_pods(
+        req: HttpRequest,
+        query: web::Query<PodsQuery>,
+        registry: web::Data<DeploymentRegistry>,
+    ) -> HttpResponse {
+        let Some(token) = header(&req, "X-Tapis-Token") else {
+            return HttpResponse::Unauthorized().json(ApiError {
+                error: "X-Tapis-Token header is required".to_string(),
+            });
+        };
+        let Some(tenant_url) = query
+            .into_inner()
+            .tenant_url
+            .filter(|t| !t.trim().is_empty())
+        else {
+            return HttpResponse::BadRequest().json(ApiError {
+                error: "tenant_url is required".to_string(),
+            });
+        };
+        // Only the pod listing is needed, so the user, model, and backend are placeholders.
+        let server = FlexServInstance::new(
+            tenant_url.clone(),
+            String::new(),
+            String::new(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let deployment = FlexServPodDeployment::from_existing(
+            server,
+            token.to_string(),
+            String::new(),
+            String::new(),
+        );
+        let visible = match deployment.visible_pod_ids().await {
+            Ok(ids) => ids,
+            Err(e) => return error_response(&e),
+        };
+        let tenant = crate::utils::canonical_tenant_url(&tenant_url);
+        let pods: Vec<DeploymentRecord> = registry
+            .list()
+            .into_iter()
+            .filter(|r| crate::utils::canonical_tenant_url(&r.tenant_url) == tenant)
+            .filter(|r| visible.contains(&r.pod_id))
+            .collect();
+        HttpResponse::Ok().json(serde_json::json!({ "pods": pods }))
+    }
+
+    /// `DELETE /pods/{pod_id}`: terminate a pod recorded in the [DeploymentRegistry] with the
+    /// caller's `X-Tapis-Token`, and drop it from the registry once terminated. 404 for a pod
+    /// the registry does not know, 401 without a token.
+    pub async fn terminate_pod(
+        req: HttpRequest,
+        path: web::Path<String>,
+        registry: web::Data<DeploymentRegistry>,
+    ) -> HttpResponse {
+        let pod_id = path.into_inner();
+        let Some(token) = header(&req, "X-Tapis-Token") else {
+            return HttpResponse::Unauthorized().json(ApiError {
+                error: "X-Tapis-Token header is required".to_string(),
+            });
+        };
+        let Some(record) = registry.get(&pod_id) else {
+            return HttpResponse::NotFound().json(ApiError {
+                error: format!("pod {} is not registered", pod_id),
+            });
+        };
+        // Only the ids and tenant are needed to terminate, so the backend is a placeholder.
+        let server = FlexServInstance::new(
+            record.tenant_url.clone(),
+            record.tapis_user.clone(),
+            record.model_id.clone(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        // Delete exactly the recorded pods, and keep a shared volume as create() left it.
+        let options = PodDeploymentOptions {
+            volume_id_override: record.shared_volume.then(|| record.volume_id.clone()),
+            ..Default::default()
+        };
+        let deployment = FlexServPodDeployment::from_existing_with_options(
+            server,
+            token.to_string(),
+            record.pod_id.clone(),
+            record.volume_id.clone(),
+            options,
+        )
+        .with_replica_ids(record.pod_ids.clone());
+        match deployment.terminate().await {
+            Ok(result) => {
+                registry.remove(&pod_id);
+                HttpResponse::Ok().json(result.to_api_json())
+            }
+            Err(e) => error_response(&e),
+        }
+    }
+
+    /// `GET /models`: the built-in and registered custom backends, or with `pod_id` (plus
+    /// `tenant_url`, `X-Tapis-Token`, and `X-FlexServ-Token`) the models that pod serves,
+    /// proxied from its `/v1/models`.
+    pub async fn list_models(req: HttpRequest, query: web::Query<ModelsQuery>) -> HttpResponse {
+        let ModelsQuery { tenant_url, pod_id } = query.into_inner();
+        let Some(pod_id) = pod_id else {
+            let mut models: Vec<String> = BUILTIN_BACKENDS.iter().map(|s| s.to_string()).collect();
+            models.extend(registered_custom_backends());
+            return HttpResponse::Ok().json(serde_json::json!({ "models": models }));
+        };
+        let Some(tenant_url) = tenant_url.filter(|t| !t.trim().is_empty()) else {
+            return HttpResponse::BadRequest().json(ApiError {
+                error: "tenant_url is required with pod_id".to_string(),
+            });
+        };
+        let (Some(tapis_token), Some(flexserv_token)) = (
+            header(&req, "X-Tapis-Token"),
+            header(&req, "X-FlexServ-Token"),
+        ) else {
+            return HttpResponse::Unauthorized().json(ApiError {
+                error: "X-Tapis-Token and X-FlexServ-Token headers are required".to_string(),
+            });
+        };
+        // Only the pod lookup is needed, so the model and backend are placeholders.
+        let server = FlexServInstance::new(
+            tenant_url,
+            String::new(),
+            String::new(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let deployment = FlexServPodDeployment::from_existing(
+            server,
+            tapis_token.to_string(),
+            pod_id.clone(),
+            String::new(),
+        );
+        let snapshot = match deployment.monitor().await {
+            Ok(snapshot) => snapshot,
+            Err(e) => return error_response(&e),
+        };
+        let Some(pod_url) = snapshot.pod_url() else {
+            return HttpResponse::ServiceUnavailable().json(ApiError {
+                error: format!("pod {} has no URL yet", pod_id),
+            });
+        };
+        match crate::deployment::served_models(pod_url, flexserv_token).await {
+            Ok(models) => {
+                HttpResponse::Ok().json(serde_json::json!({ "pod_id": pod_id, "models": models }))
+            }
+            Err(e) => error_response(&e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_json() -> serde_json::Value {
+        serde_json::json!({
+            "tenant_url": "tacc.tapis.io",
+            "tapis_user": "alice",
+            "model_id": "openai-community/gpt2",
+            "backend": {"transformers": {}},
+            "gpus": 1,
+            "deployment_id": "550e8400-e29b-41d4-a716-446655440000"
+        })
+    }
+
+    #[test]
+    fn test_create_pod_request_valid() {
+        let req: CreatePodRequest = serde_json::from_value(valid_json()).unwrap();
+        let (server, options): (FlexServInstance, PodDeploymentOptions) = req.try_into().unwrap();
+        assert_eq!(server.tenant_url, "https://tacc.tapis.io");
+        assert_eq!(server.tapis_user, "alice");
+        assert_eq!(server.default_model, "openai-community/gpt2");
+        assert_eq!(server.backend, Backend::Transformers { command: vec![] });
+        assert_eq!(options.gpus, Some(1));
+        assert_eq!(
+            options.deployment_id.as_deref(),
+            Some("550e8400-e29b-41d4-a716-446655440000")
+        );
+        assert_eq!(options.image, None);
+    }
+
+    #[test]
+    fn test_create_pod_request_rejects_missing_and_unknown_fields() {
+        let mut body = valid_json();
+        body.as_object_mut().unwrap().remove("model_id");
+        let err = serde_json::from_value::<CreatePodRequest>(body).unwrap_err();
+        assert!(err.to_string().contains("missing field `model_id`"));
+
+        let mut body = valid_json();
+        body["pod_id"] = serde_json::json!("pwhatever");
+        assert!(serde_json::from_value::<CreatePodRequest>(body).is_err());
+    }
+
+    #[test]
+    fn test_create_pod_request_validation_error() {
+        let mut body = valid_json();
+        body["tapis_user"] = serde_json::json!("  ");
+        let req: CreatePodRequest = serde_json::from_value(body).unwrap();
+        let result: Result<(FlexServInstance, PodDeploymentOptions), _> = req.try_into();
+        assert!(matches!(result, Err(ValidationError::EmptyTapisUser)));
+    }
+
+    #[cfg(feature = "server")]
+    #[actix_web::test]
+    async fn test_create_pod_handler_returns_400() {
+        use actix_web::{test, web, App};
+
+        let app = test::init_service(
+            App::new()
+                .app_data(json_config())
+                .route("/pods", web::post().to(create_pod)),
+        )
+        .await;
+
+        let mut body = valid_json();
+        body.as_object_mut().unwrap().remove("model_id");
+        let req = test::TestRequest::post()
+            .uri("/pods")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .set_json(&body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let err: ApiError = test::read_body_json(resp).await;
+        assert!(err.error.contains("missing field `model_id`"));
+
+        let mut body = valid_json();
+        body["tenant_url"] = serde_json::json!("");
+        let req = test::TestRequest::post()
+            .uri("/pods")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .set_json(&body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let err: ApiError = test::read_body_json(resp).await;
+        assert!(err.error.starts_with("invalid tenant URL"));
+    }
+
+    #[cfg(feature = "server")]
+    #[actix_web::test]
+    async fn test_list_models_handler() {
+        use actix_web::{test, web, App};
+
+        let app = test::init_service(App::new().route("/models", web::get().to(list_models))).await;
+
+        let req = test::TestRequest::get().uri("/models").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["models"][0], "transformers");
+        assert!(body.get("pod_id").is_none());
+
+        let req = test::TestRequest::get()
+            .uri("/models?pod_id=pabc")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .insert_header(("X-FlexServ-Token", "tok"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+
+        let req = test::TestRequest::get()
+            .uri("/models?pod_id=pabc&tenant_url=tacc.tapis.io")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[cfg(feature = "server")]
+    #[actix_web::test]
+    async fn test_registry_lists_and_terminates_pods() {
+        use crate::registry::{DeploymentRecord, DeploymentRegistry};
+        use crate::test_support::MockServer;
+        use actix_web::{test, web, App};
+
+        let mock = MockServer::start(|req| {
+            if req.method == "GET" {
+                // The token sees pabc but not another user's pod.
+                return (
+                    200,
+                    r#"{"message":"ok","metadata":{},"result":[{"pod_id":"pabc","image":"tapis/flexserv:1.0"}],"status":"success","version":"dev"}"#
+                        .to_string(),
+                );
+            }
+            (
+                200,
+                r#"{"message":"deleted","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            )
+        });
+        let registry = DeploymentRegistry::new();
+        let record = DeploymentRecord {
+            pod_id: "pabc".to_string(),
+            pod_ids: vec!["pabc".to_string(), "pold7".to_string()],
+            volume_id: "vabc".to_string(),
+            shared_volume: true,
+            tenant_url: mock.base_url.clone(),
+            tapis_user: "u".to_string(),
+            model_id: "m".to_string(),
+            pod_url: None,
+            created_at: 0,
+        };
+        registry.insert(DeploymentRecord {
+            pod_id: "pother".to_string(),
+            pod_ids: vec!["pother".to_string()],
+            tapis_user: "other".to_string(),
+            ..record.clone()
+        });
+        registry.insert(record);
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(registry.clone()))
+                .route("/pods", web::get().to(list_pods))
+                .route("/pods/{pod_id}", web::delete().to(terminate_pod)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/pods").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+        let req = test::TestRequest::get()
+            .uri("/pods")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 400);
+        assert!(mock.requests().is_empty());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/pods?tenant_url={}", mock.base_url))
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let ids: Vec<&str> = body["pods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["pod_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["pabc"]);
+        let listed = mock.requests();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].path, "/v3/pods");
+        assert_eq!(listed[0].header("x-tapis-token"), Some("jwt"));
+        registry.remove("pother");
+
+        let req = test::TestRequest::delete()
+            .uri("/pods/pother")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+        let req = test::TestRequest::delete().uri("/pods/pabc").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401);
+        assert_eq!(mock.requests().len(), 1);
+
+        let req = test::TestRequest::delete()
+            .uri("/pods/pabc")
+            .insert_header(("X-Tapis-Token", "jwt"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+        assert!(registry.is_empty());
+        // Exactly the recorded pods are deleted, and the shared volume is kept.
+        let paths: Vec<String> = mock
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "DELETE")
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(paths, ["/v3/pods/pabc", "/v3/pods/pold7"]);
+    }
+}
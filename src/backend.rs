@@ -9,6 +9,8 @@ use crate::server::FlexServInstance;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
 use tapis_sdk::jobs::models::{JobArgSpec, JobParameterSet, KeyValuePair};
 
 /// Supported ML inference backends.
@@ -36,17 +38,60 @@ pub enum Backend {
         #[serde(default)]
         command: Vec<String>,
     },
+    /// Any other inference server. `command` starts the server; it receives the model path as
+    /// its first argument followed by any builder options, with no backend-specific flags.
+    #[serde(rename = "custom")]
+    Custom {
+        name: String,
+        #[serde(default)]
+        command: Vec<String>,
+    },
+}
+
+/// Names of the built-in backends, as returned by [Backend::as_str].
+pub const BUILTIN_BACKENDS: &[&str] = &["transformers", "vllm", "sglang", "trtllm"];
+
+static CUSTOM_BACKENDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Register a custom backend name so it is advertised next to the built-ins (e.g. by the
+/// server's `/models` endpoint). Duplicates and empty names are ignored.
+pub fn register_custom_backend(name: impl Into<String>) {
+    let name = name.into().trim().to_string();
+    if name.is_empty() {
+        return;
+    }
+    let mut registered = CUSTOM_BACKENDS.lock().unwrap_or_else(|e| e.into_inner());
+    if !registered.contains(&name) {
+        registered.push(name);
+    }
 }
 
+/// Custom backend names registered with [register_custom_backend], in registration order.
+pub fn registered_custom_backends() -> Vec<String> {
+    CUSTOM_BACKENDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Python interpreter of the Transformers venv in the FlexServ pod image.
+pub const TRANSFORMERS_PYTHON_BIN: &str = "/app/venvs/transformers/bin/python";
+
+/// Transformers backend server script in the FlexServ pod image.
+pub const TRANSFORMERS_SERVER_PATH: &str =
+    "/app/flexserv/python/backend/transformers/backend_server.py";
+
 /// Default command to start each backend in the FlexServ pod image. Only Transformers has a
 /// defined startup script; VLlm, SGLang, and TrtLlm return None until their startup paths exist.
+/// Custom backends use their own command (None when empty, i.e. the image entrypoint).
 fn default_pod_command(backend: &Backend) -> Option<Vec<String>> {
     match backend {
         Backend::Transformers { .. } => Some(vec![
-            "/app/venvs/transformers/bin/python".to_string(),
-            "/app/flexserv/python/backend/transformers/backend_server.py".to_string(),
+            TRANSFORMERS_PYTHON_BIN.to_string(),
+            TRANSFORMERS_SERVER_PATH.to_string(),
         ]),
         Backend::VLlm { .. } | Backend::SGLang { .. } | Backend::TrtLlm { .. } => None,
+        Backend::Custom { command, .. } => Some(command.clone()).filter(|c| !c.is_empty()),
     }
 }
 
@@ -57,6 +102,7 @@ impl Backend {
             Backend::VLlm { .. } => "vllm",
             Backend::SGLang { .. } => "sglang",
             Backend::TrtLlm { .. } => "trtllm",
+            Backend::Custom { name, .. } => name,
         }
     }
 
@@ -68,6 +114,52 @@ impl Backend {
             Backend::VLlm { command, .. } => command,
             Backend::SGLang { command, .. } => command,
             Backend::TrtLlm { command, .. } => command,
+            Backend::Custom { command, .. } => command,
+        }
+    }
+
+    /// Whether the server can use GPUs if the pod or job gets them. All built-in backends can;
+    /// custom backends are assumed to.
+    pub fn supports_gpu(&self) -> bool {
+        match self {
+            Backend::Transformers { .. }
+            | Backend::VLlm { .. }
+            | Backend::SGLang { .. }
+            | Backend::TrtLlm { .. }
+            | Backend::Custom { .. } => true,
+        }
+    }
+
+    /// Whether the server is built for GPUs and is impractical (or fails) without one:
+    /// vLLM, SGLang, and TRT-LLM. Transformers also runs on CPU.
+    pub fn requires_gpu(&self) -> bool {
+        matches!(
+            self,
+            Backend::VLlm { .. } | Backend::SGLang { .. } | Backend::TrtLlm { .. }
+        )
+    }
+
+    /// Resources a pod for this backend gets when the caller sets none (see
+    /// [crate::PodDeploymentOptions::merge_resource_hints]). vLLM, SGLang, and TRT-LLM get
+    /// one GPU and room for CUDA graphs and the KV cache; Transformers and custom backends
+    /// keep the general pod defaults (1 CPU, 4 GB, no GPU).
+    pub fn default_resource_hints(&self) -> ResourceHints {
+        match self {
+            Backend::VLlm { .. } | Backend::SGLang { .. } => ResourceHints {
+                cpu: 4000,
+                mem_mb: 32768,
+                gpus: 1,
+            },
+            Backend::TrtLlm { .. } => ResourceHints {
+                cpu: 4000,
+                mem_mb: 49152,
+                gpus: 1,
+            },
+            Backend::Transformers { .. } | Backend::Custom { .. } => ResourceHints {
+                cpu: 1000,
+                mem_mb: 4096,
+                gpus: 0,
+            },
         }
     }
 
@@ -79,10 +171,218 @@ impl Backend {
             Backend::VLlm { .. } => Box::new(VLlmParameterSetBuilder::new(command)),
             Backend::SGLang { .. } => Box::new(SGLangParameterSetBuilder::new(command)),
             Backend::TrtLlm { .. } => Box::new(TrtLlmParameterSetBuilder::new(command)),
+            Backend::Custom { .. } => Box::new(CustomParameterSetBuilder::new(command)),
+        }
+    }
+
+    /// Typed Transformers builder, or [BackendMismatch] if this is another backend.
+    pub fn try_transformers(&self) -> Result<TransformersParameterSetBuilder, BackendMismatch> {
+        match self {
+            Backend::Transformers { .. } => Ok(TransformersParameterSetBuilder::new(
+                default_pod_command(self),
+            )),
+            _ => Err(BackendMismatch::new("transformers", self)),
+        }
+    }
+
+    /// Typed vLLM builder, or [BackendMismatch] if this is another backend.
+    pub fn try_vllm(&self) -> Result<VLlmParameterSetBuilder, BackendMismatch> {
+        match self {
+            Backend::VLlm { .. } => Ok(VLlmParameterSetBuilder::new(default_pod_command(self))),
+            _ => Err(BackendMismatch::new("vllm", self)),
+        }
+    }
+
+    /// Typed SGLang builder, or [BackendMismatch] if this is another backend.
+    pub fn try_sglang(&self) -> Result<SGLangParameterSetBuilder, BackendMismatch> {
+        match self {
+            Backend::SGLang { .. } => Ok(SGLangParameterSetBuilder::new(default_pod_command(self))),
+            _ => Err(BackendMismatch::new("sglang", self)),
+        }
+    }
+
+    /// Typed TensorRT-LLM builder, or [BackendMismatch] if this is another backend.
+    pub fn try_trtllm(&self) -> Result<TrtLlmParameterSetBuilder, BackendMismatch> {
+        match self {
+            Backend::TrtLlm { .. } => Ok(TrtLlmParameterSetBuilder::new(default_pod_command(self))),
+            _ => Err(BackendMismatch::new("trtllm", self)),
+        }
+    }
+
+    /// Typed builder for a custom backend, or [BackendMismatch] if this is a built-in backend.
+    pub fn try_custom(&self) -> Result<CustomParameterSetBuilder, BackendMismatch> {
+        match self {
+            Backend::Custom { .. } => Ok(CustomParameterSetBuilder::new(default_pod_command(self))),
+            _ => Err(BackendMismatch::new("custom", self)),
+        }
+    }
+
+    /// Convenience form of [Backend::try_transformers].
+    ///
+    /// # Panics
+    /// If this is not [Backend::Transformers]. Prefer the `try_` form when the backend comes
+    /// from user input or config.
+    pub fn transformers(&self) -> TransformersParameterSetBuilder {
+        self.try_transformers().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Convenience form of [Backend::try_vllm].
+    ///
+    /// # Panics
+    /// If this is not [Backend::VLlm].
+    pub fn vllm(&self) -> VLlmParameterSetBuilder {
+        self.try_vllm().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Convenience form of [Backend::try_sglang].
+    ///
+    /// # Panics
+    /// If this is not [Backend::SGLang].
+    pub fn sglang(&self) -> SGLangParameterSetBuilder {
+        self.try_sglang().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Convenience form of [Backend::try_trtllm].
+    ///
+    /// # Panics
+    /// If this is not [Backend::TrtLlm].
+    pub fn trtllm(&self) -> TrtLlmParameterSetBuilder {
+        self.try_trtllm().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Convenience form of [Backend::try_custom].
+    ///
+    /// # Panics
+    /// If this is not [Backend::Custom].
+    pub fn custom(&self) -> CustomParameterSetBuilder {
+        self.try_custom().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// Default pod resources for a backend, from [Backend::default_resource_hints]. Limits are
+/// derived as twice the requests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceHints {
+    /// CPU request in millicpus.
+    pub cpu: i32,
+    /// Memory request in MB.
+    pub mem_mb: i32,
+    pub gpus: i32,
+}
+
+/// Assumptions behind [estimate_create_duration]. Defaults describe a typical GPU node pulling
+/// the FlexServ image from a registry and the model from Hugging Face; override fields to match
+/// a site.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateEstimate {
+    /// Hugging Face download bandwidth onto the volume, in MB/s. Default 50.
+    pub download_mb_per_sec: f64,
+    /// Rate at which the server loads weights from the volume, in MB/s. Default 500.
+    pub load_mb_per_sec: f64,
+    /// Image pull and pod scheduling. Default 90s (the FlexServ image is large).
+    pub image_pull: Duration,
+    /// Server startup before weights load, per backend. Defaults: Transformers 30s, vLLM 90s
+    /// (CUDA graph capture), SGLang 60s, TRT-LLM 180s (engine build), custom 30s.
+    pub warmup_transformers: Duration,
+    pub warmup_vllm: Duration,
+    pub warmup_sglang: Duration,
+    pub warmup_trtllm: Duration,
+    pub warmup_custom: Duration,
+    /// Extra startup per GPU beyond the first (tensor-parallel workers). Default 10s.
+    pub per_extra_gpu: Duration,
+    /// Multiplier on weight loading when no GPU is assigned (CPU inference). Default 2.0.
+    pub cpu_load_factor: f64,
+}
+
+impl Default for CreateEstimate {
+    fn default() -> Self {
+        Self {
+            download_mb_per_sec: 50.0,
+            load_mb_per_sec: 500.0,
+            image_pull: Duration::from_secs(90),
+            warmup_transformers: Duration::from_secs(30),
+            warmup_vllm: Duration::from_secs(90),
+            warmup_sglang: Duration::from_secs(60),
+            warmup_trtllm: Duration::from_secs(180),
+            warmup_custom: Duration::from_secs(30),
+            per_extra_gpu: Duration::from_secs(10),
+            cpu_load_factor: 2.0,
+        }
+    }
+}
+
+impl CreateEstimate {
+    fn warmup(&self, backend: &Backend) -> Duration {
+        match backend {
+            Backend::Transformers { .. } => self.warmup_transformers,
+            Backend::VLlm { .. } => self.warmup_vllm,
+            Backend::SGLang { .. } => self.warmup_sglang,
+            Backend::TrtLlm { .. } => self.warmup_trtllm,
+            Backend::Custom { .. } => self.warmup_custom,
+        }
+    }
+
+    /// Rough time from submit to a ready server: model download (`model_size_mb` /
+    /// `download_mb_per_sec`), image pull, backend warmup, and weight loading. Assumes a cold
+    /// volume; a model already downloaded makes the real time shorter. Non-positive bandwidths
+    /// drop that term.
+    pub fn estimate(&self, model_size_mb: u64, backend: &Backend, gpus: u32) -> Duration {
+        let secs_at = |rate: f64| {
+            if rate > 0.0 {
+                Duration::from_secs_f64(model_size_mb as f64 / rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+        let mut load = secs_at(self.load_mb_per_sec);
+        if gpus == 0 {
+            load = load.mul_f64(self.cpu_load_factor.max(0.0));
+        }
+        secs_at(self.download_mb_per_sec)
+            + self.image_pull
+            + self.warmup(backend)
+            + load
+            + self.per_extra_gpu * gpus.saturating_sub(1)
+    }
+}
+
+/// Heuristic ETA for creating a deployment of a `model_size_mb` model on `backend` with `gpus`
+/// GPUs, using the [CreateEstimate] defaults. Intended to set user expectations, not as a
+/// timeout; use [CreateEstimate::estimate] to override the assumptions.
+pub fn estimate_create_duration(model_size_mb: u64, backend: &Backend, gpus: u32) -> Duration {
+    CreateEstimate::default().estimate(model_size_mb, backend, gpus)
+}
+
+/// A typed builder was requested for a different backend than the one configured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackendMismatch {
+    /// Backend the caller asked for (e.g. "vllm").
+    pub expected: &'static str,
+    /// Backend actually configured, as returned by [Backend::as_str].
+    pub actual: String,
+}
+
+impl BackendMismatch {
+    fn new(expected: &'static str, actual: &Backend) -> Self {
+        Self {
+            expected,
+            actual: actual.as_str().to_string(),
         }
     }
 }
 
+impl std::fmt::Display for BackendMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "backend mismatch: expected {}, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for BackendMismatch {}
+
 /// Parameters for a pod: command, arguments, and environment variables.
 /// Use `${pods:secrets:KEY}` in env values to reference secret_map entries.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -96,6 +396,63 @@ pub struct PodParameterSet {
     pub environment_variables: Option<HashMap<String, Value>>,
 }
 
+impl PodParameterSet {
+    /// True when both sets would run the same thing regardless of ordering: equal commands,
+    /// the same leading positional arguments, the same `--flag [values]` groups in any order
+    /// (`--flag=value` is the same as `--flag value`), and the same env vars in any order.
+    /// A missing field equals an empty one, and a null env value equals a missing key.
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        let command = |p: &Self| p.command.clone().unwrap_or_default();
+        let env = |p: &Self| -> BTreeMap<String, Value> {
+            p.environment_variables
+                .iter()
+                .flatten()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        };
+        command(self) == command(other)
+            && normalized_pod_args(self.arguments.as_deref().unwrap_or_default())
+                == normalized_pod_args(other.arguments.as_deref().unwrap_or_default())
+            && env(self) == env(other)
+    }
+
+    /// The full argv this set runs for the model at `model_path`: the command, the model path
+    /// as the first positional argument, then the arguments. This is what a pod executes, in a
+    /// form usable as a Dockerfile `CMD` or Kubernetes `command` elsewhere. With no command
+    /// (image entrypoint), the array starts with the model path.
+    pub fn to_command_array(&self, model_path: &str) -> Vec<String> {
+        self.command
+            .iter()
+            .flatten()
+            .cloned()
+            .chain(std::iter::once(model_path.to_string()))
+            .chain(self.arguments.iter().flatten().cloned())
+            .collect()
+    }
+}
+
+/// Split CLI arguments into the leading positional arguments (in order) and the sorted
+/// `--flag [values]` groups that follow.
+fn normalized_pod_args(args: &[String]) -> (Vec<String>, Vec<(String, Vec<String>)>) {
+    let mut positional = Vec::new();
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for arg in args {
+        if let Some(flag) = arg.strip_prefix("--") {
+            match flag.split_once('=') {
+                Some((name, value)) => groups.push((name.to_string(), vec![value.to_string()])),
+                None => groups.push((flag.to_string(), Vec::new())),
+            }
+        } else if let Some((_, values)) = groups.last_mut() {
+            values.push(arg.clone());
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    groups.sort();
+    (positional, groups)
+}
+
 /// HPC job parameter set (tapis-sdk [JobParameterSet]).
 pub type HPCParameterSet = JobParameterSet;
 
@@ -336,10 +693,17 @@ pub struct TransformersParameterSetBuilder {
 }
 
 impl BackendParameterSetBuilder for TransformersParameterSetBuilder {
-    fn build_params_for_pod(&self, _server: &FlexServInstance) -> PodParameterSet {
+    fn build_params_for_pod(&self, server: &FlexServInstance) -> PodParameterSet {
         let mut merged = BTreeMap::new();
         merged.insert("host".to_string(), Value::String("0.0.0.0".to_string()));
         merged.insert("port".to_string(), Value::String("8000".to_string()));
+        if let Some(model) = &server.default_embedding_model {
+            merged.insert(
+                "default-embedding-model".to_string(),
+                Value::String(model.clone()),
+            );
+            merged.insert("force-default-embedding-model".to_string(), Value::Bool(true));
+        }
         for (k, v) in &self.options {
             merged.insert(k.clone(), v.clone());
         }
@@ -683,6 +1047,56 @@ impl TrtLlmParameterSetBuilder {
     }
 }
 
+/// Builder for a custom backend: no default flags, only caller-supplied options and env vars.
+pub struct CustomParameterSetBuilder {
+    command: Option<Vec<String>>,
+    options: BTreeMap<String, Value>,
+    environment_variables: HashMap<String, Value>,
+}
+
+impl BackendParameterSetBuilder for CustomParameterSetBuilder {
+    fn build_params_for_pod(&self, _server: &FlexServInstance) -> PodParameterSet {
+        let mut arguments = Vec::new();
+        for (flag, value) in &self.options {
+            arguments.extend(value_to_pod_args(flag, value));
+        }
+        PodParameterSet {
+            command: self.command.clone(),
+            arguments: Some(arguments),
+            environment_variables: Some(self.environment_variables.clone()),
+        }
+    }
+
+    fn build_params_for_hpc(&self, server: &FlexServInstance) -> HPCParameterSet {
+        let mut params =
+            build_hpc_from_options(&filter_hpc_options(&self.options), &self.environment_variables);
+        apply_flexserv_hpc_contract(&mut params, server);
+        params
+    }
+}
+
+impl CustomParameterSetBuilder {
+    pub fn new(command: Option<Vec<String>>) -> Self {
+        Self {
+            command,
+            options: BTreeMap::new(),
+            environment_variables: HashMap::new(),
+        }
+    }
+
+    /// Insert an arbitrary option key/value pair.
+    pub fn insert_option(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn insert_env_var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.environment_variables
+            .insert(key.into(), Value::String(value.into()));
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -694,6 +1108,213 @@ mod tests {
         assert_eq!(backend.as_str(), "transformers");
     }
 
+    #[test]
+    fn test_estimate_create_duration() {
+        let transformers = Backend::Transformers { command: vec![] };
+        let trtllm = Backend::TrtLlm { command: vec![] };
+        // 5000 MB: 100s download + 90s pull + 30s warmup + 10s load.
+        assert_eq!(
+            estimate_create_duration(5000, &transformers, 1),
+            Duration::from_secs(230)
+        );
+        // CPU doubles load; extra GPUs add 10s each.
+        assert_eq!(
+            estimate_create_duration(5000, &transformers, 0),
+            Duration::from_secs(240)
+        );
+        assert_eq!(
+            estimate_create_duration(5000, &transformers, 4),
+            Duration::from_secs(260)
+        );
+        assert!(
+            estimate_create_duration(5000, &trtllm, 1)
+                > estimate_create_duration(5000, &transformers, 1)
+        );
+        assert!(
+            estimate_create_duration(50_000, &transformers, 1)
+                > estimate_create_duration(5000, &transformers, 1)
+        );
+
+        let fast = CreateEstimate {
+            download_mb_per_sec: 0.0,
+            image_pull: Duration::ZERO,
+            ..CreateEstimate::default()
+        };
+        assert_eq!(
+            fast.estimate(5000, &transformers, 1),
+            Duration::from_secs(40)
+        );
+    }
+
+    #[test]
+    fn test_pod_parameter_set_to_command_array() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "u".to_string(),
+            "org/model".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let params = server.backend.transformers().build_params_for_pod(&server);
+        let argv = params.to_command_array("/app/models/org_model");
+        assert_eq!(
+            argv[..2],
+            [TRANSFORMERS_PYTHON_BIN, TRANSFORMERS_SERVER_PATH]
+        );
+        assert_eq!(argv[2], "/app/models/org_model");
+        assert_eq!(argv[3..], params.arguments.clone().unwrap()[..]);
+
+        let entrypoint = PodParameterSet {
+            arguments: Some(vec!["--port".to_string(), "8000".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            entrypoint.to_command_array("/m"),
+            vec!["/m", "--port", "8000"]
+        );
+    }
+
+    #[test]
+    fn test_pod_parameter_set_semantically_eq() {
+        let set = |args: &[&str], env: &[(&str, Value)]| PodParameterSet {
+            command: Some(vec!["python".to_string()]),
+            arguments: Some(args.iter().map(|a| a.to_string()).collect()),
+            environment_variables: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.clone()))
+                    .collect(),
+            ),
+        };
+        let a = set(
+            &[
+                "/app/models/m",
+                "--host",
+                "0.0.0.0",
+                "--port",
+                "8000",
+                "--enable-cors",
+            ],
+            &[("A", Value::from("1")), ("B", Value::from("2"))],
+        );
+        let b = set(
+            &[
+                "/app/models/m",
+                "--enable-cors",
+                "--port=8000",
+                "--host",
+                "0.0.0.0",
+            ],
+            &[
+                ("B", Value::from("2")),
+                ("A", Value::from("1")),
+                ("C", Value::Null),
+            ],
+        );
+        assert!(a.semantically_eq(&b));
+        assert!(b.semantically_eq(&a));
+
+        let changed = set(
+            &[
+                "/app/models/m",
+                "--host",
+                "0.0.0.0",
+                "--port",
+                "8080",
+                "--enable-cors",
+            ],
+            &[("A", Value::from("1")), ("B", Value::from("2"))],
+        );
+        assert!(!a.semantically_eq(&changed));
+        let other_env = set(
+            &[
+                "/app/models/m",
+                "--host",
+                "0.0.0.0",
+                "--port",
+                "8000",
+                "--enable-cors",
+            ],
+            &[("A", Value::from("1")), ("B", Value::from("3"))],
+        );
+        assert!(!a.semantically_eq(&other_env));
+        // Positional arguments keep their order.
+        let swapped = set(&["x", "y"], &[]);
+        assert!(!set(&["y", "x"], &[]).semantically_eq(&swapped));
+
+        // Missing fields equal empty ones.
+        let empty = PodParameterSet::default();
+        let explicit = PodParameterSet {
+            command: Some(vec![]),
+            arguments: Some(vec![]),
+            environment_variables: Some(HashMap::new()),
+        };
+        assert!(empty.semantically_eq(&explicit));
+    }
+
+    #[test]
+    fn test_backend_gpu_support() {
+        let cases = [
+            (Backend::Transformers { command: vec![] }, true, false),
+            (Backend::VLlm { command: vec![] }, true, true),
+            (Backend::SGLang { command: vec![] }, true, true),
+            (Backend::TrtLlm { command: vec![] }, true, true),
+            (
+                Backend::Custom {
+                    name: "mine".to_string(),
+                    command: vec![],
+                },
+                true,
+                false,
+            ),
+        ];
+        for (backend, supports, requires) in cases {
+            assert_eq!(backend.supports_gpu(), supports, "{}", backend.as_str());
+            assert_eq!(backend.requires_gpu(), requires, "{}", backend.as_str());
+        }
+    }
+
+    #[test]
+    fn test_default_resource_hints() {
+        let hints = |backend: Backend| backend.default_resource_hints();
+        let general = ResourceHints {
+            cpu: 1000,
+            mem_mb: 4096,
+            gpus: 0,
+        };
+        assert_eq!(hints(Backend::Transformers { command: vec![] }), general);
+        assert_eq!(
+            hints(Backend::Custom {
+                name: "mine".to_string(),
+                command: vec![],
+            }),
+            general
+        );
+        let gpu = ResourceHints {
+            cpu: 4000,
+            mem_mb: 32768,
+            gpus: 1,
+        };
+        assert_eq!(hints(Backend::VLlm { command: vec![] }), gpu);
+        assert_eq!(hints(Backend::SGLang { command: vec![] }), gpu);
+        assert_eq!(
+            hints(Backend::TrtLlm { command: vec![] }),
+            ResourceHints {
+                mem_mb: 49152,
+                ..gpu
+            }
+        );
+        // Every backend that needs a GPU gets one by default.
+        for backend in [
+            Backend::VLlm { command: vec![] },
+            Backend::SGLang { command: vec![] },
+            Backend::TrtLlm { command: vec![] },
+        ] {
+            assert!(backend.default_resource_hints().gpus >= 1);
+        }
+    }
+
     #[test]
     fn test_backend_parameter_set() {
         let server = FlexServInstance::new(
@@ -1070,4 +1691,136 @@ mod tests {
                 && env.value.as_deref() == Some("0,1,2,3")
         }));
     }
+
+    #[test]
+    fn test_transformers_pod_params_include_embedding_model() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "user".to_string(),
+            "gpt2".to_string(),
+            None,
+            None,
+            Some("sentence-transformers/all-MiniLM-L6-v2".to_string()),
+            Backend::Transformers { command: vec![] },
+        );
+        let pod_params = TransformersParameterSetBuilder::new(None).build_params_for_pod(&server);
+        let args = pod_params.arguments.as_ref().unwrap();
+        let idx = args
+            .iter()
+            .position(|a| a == "--default-embedding-model")
+            .expect("embedding model flag");
+        assert_eq!(args[idx + 1], "sentence-transformers/all-MiniLM-L6-v2");
+        assert!(args.contains(&"--force-default-embedding-model".to_string()));
+
+        // Builder options still override the instance-derived defaults.
+        let pod_params = TransformersParameterSetBuilder::new(None)
+            .force_default_embedding_model(false)
+            .build_params_for_pod(&server);
+        let args = pod_params.arguments.as_ref().unwrap();
+        assert!(!args.contains(&"--force-default-embedding-model".to_string()));
+    }
+
+    #[test]
+    fn test_transformers_pod_params_without_embedding_model() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "user".to_string(),
+            "gpt2".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let pod_params = TransformersParameterSetBuilder::new(None).build_params_for_pod(&server);
+        let args = pod_params.arguments.as_ref().unwrap();
+        assert!(!args.iter().any(|a| a.contains("embedding")));
+    }
+
+    #[test]
+    fn test_custom_backend() {
+        let backend = Backend::Custom {
+            name: "llamacpp".to_string(),
+            command: vec!["/opt/llama/server".to_string()],
+        };
+        assert_eq!(backend.as_str(), "llamacpp");
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "user".to_string(),
+            "gpt2".to_string(),
+            None,
+            None,
+            None,
+            backend.clone(),
+        );
+        let pod_params = backend.parameter_set_builder().build_params_for_pod(&server);
+        assert_eq!(pod_params.command, Some(vec!["/opt/llama/server".to_string()]));
+        assert_eq!(pod_params.arguments, Some(vec![]));
+
+        let json = serde_json::to_value(&backend).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"custom": {"name": "llamacpp", "command": ["/opt/llama/server"]}})
+        );
+        let back: Backend = serde_json::from_value(json).unwrap();
+        assert_eq!(back, backend);
+    }
+
+    #[test]
+    fn test_register_custom_backend() {
+        register_custom_backend("my-server");
+        register_custom_backend("my-server");
+        register_custom_backend("  ");
+        let names = registered_custom_backends();
+        assert_eq!(names.iter().filter(|n| *n == "my-server").count(), 1);
+        assert!(!names.iter().any(|n| n.trim().is_empty()));
+    }
+
+    #[test]
+    fn test_try_builders_match() {
+        assert!(Backend::Transformers { command: vec![] }
+            .try_transformers()
+            .is_ok());
+        assert!(Backend::VLlm { command: vec![] }.try_vllm().is_ok());
+        assert!(Backend::SGLang { command: vec![] }.try_sglang().is_ok());
+        assert!(Backend::TrtLlm { command: vec![] }.try_trtllm().is_ok());
+        let custom = Backend::Custom {
+            name: "llamacpp".to_string(),
+            command: vec![],
+        };
+        assert!(custom.try_custom().is_ok());
+    }
+
+    #[test]
+    fn test_try_builders_mismatch() {
+        let backend = Backend::Transformers { command: vec![] };
+        let err = backend.try_vllm().err().unwrap();
+        assert_eq!(
+            err,
+            BackendMismatch {
+                expected: "vllm",
+                actual: "transformers".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "backend mismatch: expected vllm, got transformers"
+        );
+
+        let custom = Backend::Custom {
+            name: "llamacpp".to_string(),
+            command: vec![],
+        };
+        let err = custom.try_transformers().err().unwrap();
+        assert_eq!(err.expected, "transformers");
+        assert_eq!(err.actual, "llamacpp");
+        assert!(custom.try_sglang().is_err());
+        assert!(custom.try_trtllm().is_err());
+        assert!(backend.try_custom().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "backend mismatch: expected sglang, got vllm")]
+    fn test_panicking_builder_mismatch() {
+        let _ = Backend::VLlm { command: vec![] }.sglang();
+    }
 }
@@ -20,7 +20,7 @@ ALPHABET_VERT: [u8; 256] = [0xff: 256];
 for (i, &v) in ALPHABET.iter().enumerate() {
     ALPHABET_VERT[v as usize] = i as u8;
 }
- */
+*/
 const ALPHABET_VERT: [u8; 256] = [
     255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
     255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
@@ -37,6 +37,31 @@ const ALPHABET_VERT: [u8; 256] = [
     255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
 ];
 
+/// True when `ALPHABET_VERT` is exactly the inverse of `ALPHABET`: each alphabet char maps back
+/// to its index and every other byte is 255.
+const fn alphabet_vert_is_consistent() -> bool {
+    let mut expected = [255u8; 256];
+    let mut i = 0;
+    while i < ALPHABET_SIZE {
+        expected[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    let mut b = 0;
+    while b < 256 {
+        if ALPHABET_VERT[b] != expected[b] {
+            return false;
+        }
+        b += 1;
+    }
+    true
+}
+
+// Fail the build if a hand edit desyncs the two tables.
+const _: () = assert!(
+    alphabet_vert_is_consistent(),
+    "ALPHABET_VERT is not the inverse of ALPHABET"
+);
+
 fn encode_len(n: usize) -> usize {
     if n == BASE256BLOCK_LEN {
         return BASE62BLOCK_LEN;
@@ -146,6 +171,21 @@ mod tests {
         check_bytes(plaintext.as_bytes(), ciphertext.as_bytes());
     }
 
+    #[test]
+    fn test_alphabet_vert_is_inverse() {
+        let mut vert = [255u8; 256];
+        for (i, &c) in ALPHABET.iter().enumerate() {
+            vert[c as usize] = i as u8;
+        }
+        assert_eq!(vert, ALPHABET_VERT);
+        for (i, &c) in ALPHABET.iter().enumerate() {
+            assert_eq!(ALPHABET_VERT[c as usize] as usize, i, "{c}");
+        }
+        let mapped = ALPHABET_VERT.iter().filter(|&&v| v != 255).count();
+        assert_eq!(mapped, ALPHABET_SIZE);
+        assert!(alphabet_vert_is_consistent());
+    }
+
     #[test]
     fn test_str() {
         check_str("", "");
@@ -0,0 +1,119 @@
+//! Synchronous wrappers around [FlexServDeployment] for scripts and CLIs without an async
+//! runtime (feature `blocking`). Outside any runtime, calls run on one shared current-thread
+//! Tokio runtime, created on first use, so HTTP clients and connection pools are not split
+//! across runtimes.
+//!
+//! Calling these from async code is supported on a multi-thread runtime: the call runs on the
+//! caller's runtime, moved off the async worker with `block_in_place`. Inside a current-thread runtime (e.g. plain
+//! `#[tokio::main(flavor = "current_thread")]` or `#[tokio::test]`) blocking would stall the
+//! runtime, so the call fails with [DeploymentError::InvalidConfiguration] instead; use the
+//! async methods there.
+
+use crate::deployment::{DeploymentError, DeploymentResult, FlexServDeployment};
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// The shared runtime, built on first use.
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build the flexserv-deployer blocking runtime")
+    })
+}
+
+/// Run `fut` to completion from synchronous code: on the shared runtime, or with
+/// `block_in_place` on the caller's multi-thread runtime. Errors inside a current-thread
+/// runtime instead of panicking.
+pub fn block_on<F: Future>(fut: F) -> Result<F::Output, DeploymentError> {
+    match Handle::try_current() {
+        Err(_) => Ok(runtime().block_on(fut)),
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            Ok(tokio::task::block_in_place(|| handle.block_on(fut)))
+        }
+        Ok(_) => Err(DeploymentError::InvalidConfiguration(
+            "blocking call made inside a current-thread Tokio runtime; use the async API there"
+                .to_string(),
+        )),
+    }
+}
+
+/// [FlexServDeployment::create], blocking.
+pub fn create<D: FlexServDeployment>(
+    deployment: &mut D,
+) -> Result<DeploymentResult, DeploymentError> {
+    block_on(deployment.create())?
+}
+
+/// [FlexServDeployment::start], blocking.
+pub fn start<D: FlexServDeployment>(deployment: &D) -> Result<DeploymentResult, DeploymentError> {
+    block_on(deployment.start())?
+}
+
+/// [FlexServDeployment::stop], blocking.
+pub fn stop<D: FlexServDeployment>(deployment: &D) -> Result<DeploymentResult, DeploymentError> {
+    block_on(deployment.stop())?
+}
+
+/// [FlexServDeployment::terminate], blocking.
+pub fn terminate<D: FlexServDeployment>(
+    deployment: &D,
+) -> Result<DeploymentResult, DeploymentError> {
+    block_on(deployment.terminate())?
+}
+
+/// [FlexServDeployment::monitor], blocking.
+pub fn monitor<D: FlexServDeployment>(deployment: &D) -> Result<DeploymentResult, DeploymentError> {
+    block_on(deployment.monitor())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deployment::FlexServPodDeployment;
+    use crate::test_support::{existing_deployment, MockServer, POD_RESPONSE_JSON};
+
+    fn make_deployment(base_url: &str) -> FlexServPodDeployment {
+        let mut d = existing_deployment(base_url, "pabc", "vabc");
+        // No volume lookup, so each monitor() is one GET.
+        d.volume_id = String::new();
+        d
+    }
+
+    #[test]
+    fn test_blocking_monitor_without_runtime() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let d = make_deployment(&mock.base_url);
+        let result = monitor(&d).unwrap();
+        assert_eq!(result.as_pod().unwrap().status, Some("AVAILABLE"));
+
+        // The shared runtime serves later calls and other threads too.
+        let base_url = mock.base_url.clone();
+        let other = std::thread::spawn(move || monitor(&make_deployment(&base_url)).is_ok());
+        assert!(other.join().unwrap());
+        assert!(monitor(&d).is_ok());
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_blocking_monitor_inside_multi_thread_runtime() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let d = make_deployment(&mock.base_url);
+        assert!(monitor(&d).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_blocking_inside_current_thread_runtime_errors() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let d = make_deployment(&mock.base_url);
+        assert!(matches!(
+            monitor(&d),
+            Err(DeploymentError::InvalidConfiguration(_))
+        ));
+        assert!(mock.requests().is_empty());
+    }
+}
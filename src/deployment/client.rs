@@ -0,0 +1,328 @@
+//! TAPIS HTTP clients: one `reqwest::Client` per token, optionally shared process-wide so
+//! repeated operations reuse pooled connections instead of paying a TLS handshake each time.
+
+use super::DeploymentError;
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Upper bound on cached clients; the cache is cleared when it is reached (tokens expire, so
+/// old entries are rarely useful).
+const MAX_SHARED_CLIENTS: usize = 64;
+
+static SHARED_CLIENTS: Mutex<Option<HashMap<String, reqwest::Client>>> = Mutex::new(None);
+
+/// Source of a fresh TAPIS token for services that outlive a token's lifetime. Asked once per
+/// operation, so a rotated file or refreshed token is picked up by the next call.
+#[derive(Clone)]
+pub enum TokenProvider {
+    /// Re-read this file (trimmed) on every operation, e.g. a token kept fresh by a sidecar.
+    File(PathBuf),
+    /// Await this function on every operation, e.g. to refresh against an OAuth endpoint.
+    Fn(Arc<dyn Fn() -> BoxFuture<'static, Result<String, DeploymentError>> + Send + Sync>),
+}
+
+impl TokenProvider {
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        TokenProvider::File(path.into())
+    }
+
+    /// Provider from an async function, e.g. `TokenProvider::from_fn(|| refresh())` for an
+    /// `async fn refresh() -> Result<String, DeploymentError>`.
+    pub fn from_fn<F, Fut>(f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<String, DeploymentError>> + Send + 'static,
+    {
+        TokenProvider::Fn(Arc::new(move || Box::pin(f())))
+    }
+
+    /// The current token. Unreadable files and empty tokens are TapisAuthFailed.
+    pub async fn token(&self) -> Result<String, DeploymentError> {
+        let token = match self {
+            TokenProvider::File(path) => tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| {
+                    DeploymentError::TapisAuthFailed(format!(
+                        "reading token file {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?
+                .trim()
+                .to_string(),
+            TokenProvider::Fn(f) => f().await?,
+        };
+        if token.is_empty() {
+            return Err(DeploymentError::TapisAuthFailed(
+                "token provider returned an empty token".to_string(),
+            ));
+        }
+        Ok(token)
+    }
+}
+
+impl fmt::Debug for TokenProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenProvider::File(path) => f.debug_tuple("File").field(path).finish(),
+            TokenProvider::Fn(_) => f.write_str("Fn(..)"),
+        }
+    }
+}
+
+/// Build a client that sends `X-Tapis-Token` on every request. `ca_cert_pem` adds extra trusted
+/// roots; `danger_accept_invalid_certs` disables verification (development only). `http_proxy`
+/// (an `http://` or `https://` URL) routes all calls through that proxy, still honoring
+/// `NO_PROXY`; when None, the `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` env vars apply as usual.
+/// `extra_headers` (e.g. an API gateway key) are sent on every request too; `X-Tapis-Token`
+/// cannot be overridden. Invalid header names or values are TapisAuthFailed, like the token.
+pub fn tapis_client(
+    tapis_token: &str,
+    ca_cert_pem: Option<&str>,
+    danger_accept_invalid_certs: bool,
+    http_proxy: Option<&str>,
+    extra_headers: &HashMap<String, String>,
+) -> Result<reqwest::Client, DeploymentError> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in extra_headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+            DeploymentError::TapisAuthFailed(format!("invalid header name {:?}: {}", name, e))
+        })?;
+        let value = HeaderValue::from_str(value).map_err(|e| {
+            DeploymentError::TapisAuthFailed(format!("invalid value for header {}: {}", name, e))
+        })?;
+        headers.insert(name, value);
+    }
+    headers.insert(
+        "X-Tapis-Token",
+        HeaderValue::from_str(tapis_token)
+            .map_err(|e| DeploymentError::TapisAuthFailed(e.to_string()))?,
+    );
+    let builder = reqwest::Client::builder().default_headers(headers);
+    let builder = with_transport_options(
+        builder,
+        ca_cert_pem,
+        danger_accept_invalid_certs,
+        http_proxy,
+    )?;
+    builder
+        .build()
+        .map_err(|e| DeploymentError::TapisAuthFailed(e.to_string()))
+}
+
+/// Client for calls to a FlexServ server itself (health checks, warmup, model listing,
+/// [super::PodInferenceClient]): the TLS and proxy options of [tapis_client], but no TAPIS
+/// token or extra headers. It has no overall timeout, so streams are not cut off; set one per
+/// request instead.
+pub(crate) fn flexserv_client(
+    ca_cert_pem: Option<&str>,
+    danger_accept_invalid_certs: bool,
+    http_proxy: Option<&str>,
+) -> Result<reqwest::Client, DeploymentError> {
+    with_transport_options(
+        reqwest::Client::builder(),
+        ca_cert_pem,
+        danger_accept_invalid_certs,
+        http_proxy,
+    )?
+    .build()
+    .map_err(|e| DeploymentError::InferenceFailed(e.to_string()))
+}
+
+/// Extra trusted roots, disabled verification, and proxy, as documented on [tapis_client].
+fn with_transport_options(
+    mut builder: reqwest::ClientBuilder,
+    ca_cert_pem: Option<&str>,
+    danger_accept_invalid_certs: bool,
+    http_proxy: Option<&str>,
+) -> Result<reqwest::ClientBuilder, DeploymentError> {
+    if let Some(pem) = ca_cert_pem {
+        let cert = reqwest::Certificate::from_pem(pem.as_bytes()).map_err(|e| {
+            DeploymentError::InvalidConfiguration(format!("invalid ca_cert_pem: {}", e))
+        })?;
+        builder = builder.add_root_certificate(cert);
+    }
+    if danger_accept_invalid_certs {
+        log::warn!("TLS certificate verification is disabled");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    if let Some(url) = http_proxy {
+        builder = builder.proxy(proxy_from_url(url)?);
+    }
+    Ok(builder)
+}
+
+/// Process-wide variant of [tapis_client]: returns the cached client for the same token and TLS
+/// settings, building it on first use. Intended for servers handling many requests per token.
+pub fn shared_tapis_client(
+    tapis_token: &str,
+    ca_cert_pem: Option<&str>,
+    danger_accept_invalid_certs: bool,
+    http_proxy: Option<&str>,
+    extra_headers: &HashMap<String, String>,
+) -> Result<reqwest::Client, DeploymentError> {
+    let key = cache_key(
+        tapis_token,
+        ca_cert_pem,
+        danger_accept_invalid_certs,
+        http_proxy,
+        extra_headers,
+    );
+    let mut guard = SHARED_CLIENTS.lock().unwrap_or_else(|e| e.into_inner());
+    let clients = guard.get_or_insert_with(HashMap::new);
+    if let Some(client) = clients.get(&key) {
+        return Ok(client.clone());
+    }
+    let client = tapis_client(
+        tapis_token,
+        ca_cert_pem,
+        danger_accept_invalid_certs,
+        http_proxy,
+        extra_headers,
+    )?;
+    if clients.len() >= MAX_SHARED_CLIENTS {
+        clients.clear();
+    }
+    clients.insert(key, client.clone());
+    Ok(client)
+}
+
+/// Proxy for every scheme from an explicit `http://` / `https://` URL, with `NO_PROXY` applied.
+fn proxy_from_url(url: &str) -> Result<reqwest::Proxy, DeploymentError> {
+    let invalid =
+        |msg: String| DeploymentError::InvalidConfiguration(format!("invalid http_proxy: {}", msg));
+    let url = url.trim();
+    if !crate::utils::is_absolute_http_url(url) {
+        return Err(invalid(format!(
+            "{:?} must be an http:// or https:// URL",
+            url
+        )));
+    }
+    let proxy = reqwest::Proxy::all(url).map_err(|e| invalid(e.to_string()))?;
+    Ok(proxy.no_proxy(reqwest::NoProxy::from_env()))
+}
+
+/// Hash of the token and client settings, so raw tokens are not kept as map keys.
+fn cache_key(
+    tapis_token: &str,
+    ca_cert_pem: Option<&str>,
+    danger: bool,
+    http_proxy: Option<&str>,
+    extra_headers: &HashMap<String, String>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(tapis_token.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(ca_cert_pem.unwrap_or_default().as_bytes());
+    hasher.update([danger as u8]);
+    hasher.update(http_proxy.unwrap_or_default().as_bytes());
+    let mut headers: Vec<_> = extra_headers.iter().collect();
+    headers.sort();
+    for (name, value) in headers {
+        hasher.update([0u8]);
+        hasher.update(name.to_ascii_lowercase().as_bytes());
+        hasher.update([0u8]);
+        hasher.update(value.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_headers() -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_token_and_tls() {
+        let a = cache_key("t1", None, false, None, &no_headers());
+        assert_eq!(a, cache_key("t1", None, false, None, &no_headers()));
+        assert_ne!(a, cache_key("t2", None, false, None, &no_headers()));
+        assert_ne!(a, cache_key("t1", None, true, None, &no_headers()));
+        assert_ne!(a, cache_key("t1", Some("pem"), false, None, &no_headers()));
+        assert_ne!(
+            a,
+            cache_key("t1", None, false, Some("http://proxy:3128"), &no_headers())
+        );
+        let gateway = HashMap::from([("X-Api-Key".to_string(), "k".to_string())]);
+        assert_ne!(a, cache_key("t1", None, false, None, &gateway));
+        assert!(!a.contains("t1"));
+    }
+
+    #[test]
+    fn test_shared_tapis_client_rejects_invalid_token() {
+        let err = shared_tapis_client("bad\ntoken", None, false, None, &no_headers()).unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisAuthFailed(_)));
+    }
+
+    #[test]
+    fn test_shared_tapis_client_reuses_entry() {
+        shared_tapis_client("shared-token", None, false, None, &no_headers()).unwrap();
+        shared_tapis_client("shared-token", None, false, None, &no_headers()).unwrap();
+        let guard = SHARED_CLIENTS.lock().unwrap();
+        let key = cache_key("shared-token", None, false, None, &no_headers());
+        assert!(guard.as_ref().unwrap().contains_key(&key));
+    }
+
+    #[tokio::test]
+    async fn test_token_provider_file_and_fn() {
+        let path = std::env::temp_dir().join(format!("flexserv-token-{}", std::process::id()));
+        let provider = TokenProvider::file(&path);
+        assert!(matches!(
+            provider.token().await,
+            Err(DeploymentError::TapisAuthFailed(ref m)) if m.contains("reading token file")
+        ));
+        std::fs::write(&path, "jwt-1\n").unwrap();
+        assert_eq!(provider.token().await.unwrap(), "jwt-1");
+        // Re-read on every call, so a rotated token is picked up.
+        std::fs::write(&path, "jwt-2").unwrap();
+        assert_eq!(provider.token().await.unwrap(), "jwt-2");
+        std::fs::write(&path, "  ").unwrap();
+        assert!(provider.token().await.is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let provider = TokenProvider::from_fn(|| async { Ok("fresh".to_string()) });
+        assert_eq!(provider.token().await.unwrap(), "fresh");
+        assert_eq!(format!("{:?}", provider), "Fn(..)");
+    }
+
+    #[test]
+    fn test_extra_headers_validation() {
+        let bad_name = HashMap::from([("bad header".to_string(), "v".to_string())]);
+        let err = tapis_client("t", None, false, None, &bad_name).unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisAuthFailed(ref m) if m.contains("bad header")));
+        let bad_value = HashMap::from([("X-Api-Key".to_string(), "a\nb".to_string())]);
+        assert!(matches!(
+            tapis_client("t", None, false, None, &bad_value),
+            Err(DeploymentError::TapisAuthFailed(_))
+        ));
+        let ok = HashMap::from([("X-Api-Key".to_string(), "k".to_string())]);
+        assert!(tapis_client("t", None, false, None, &ok).is_ok());
+    }
+
+    #[test]
+    fn test_http_proxy_validation() {
+        for bad in ["proxy.internal:3128", "ftp://proxy.internal", ""] {
+            let err = tapis_client("t", None, false, Some(bad), &no_headers()).unwrap_err();
+            assert!(
+                matches!(err, DeploymentError::InvalidConfiguration(ref m) if m.contains("http_proxy")),
+                "{bad:?} should be rejected"
+            );
+        }
+        assert!(tapis_client(
+            "t",
+            None,
+            false,
+            Some("http://proxy.internal:3128"),
+            &no_headers()
+        )
+        .is_ok());
+    }
+}
@@ -1,6 +1,7 @@
-use super::{DeploymentError, DeploymentResult, FlexServDeployment};
+use super::{DeploymentError, DeploymentResult, FlexServDeployment, TapisErrorBody};
+use crate::polling::{poll_with_backoff, BackoffConfig};
 use crate::server::FlexServInstance;
-use tokio::time::{sleep, Duration};
+use tokio::time::Duration;
 use tapis_sdk::jobs::apis;
 use tapis_sdk::jobs::apis::configuration;
 use tapis_sdk::jobs::apis::jobs_api;
@@ -75,11 +76,9 @@ impl FlexServHPCDeployment {
     fn jobs_config(&self) -> Result<configuration::Configuration, DeploymentError> {
         let mut config = configuration::Configuration::default();
         if let Some(server) = self.server.as_ref() {
-            let base = server.tenant_url.trim_end_matches('/');
-            config.base_path = format!("{}/v3", base);
+            config.base_path = crate::utils::tapis_api_base(&server.tenant_url);
         } else if let Some(tenant_url) = self.tenant_url.as_ref() {
-            let base = tenant_url.trim_end_matches('/');
-            config.base_path = format!("{}/v3", base);
+            config.base_path = crate::utils::tapis_api_base(tenant_url);
         } else {
             return Err(DeploymentError::InvalidConfiguration(
                 "missing tenant URL; pass server in new() or set tenant_url for from_existing()"
@@ -117,14 +116,15 @@ impl FlexServHPCDeployment {
             apis::Error::Io(e) => DeploymentError::UnknownError(e.to_string()),
             apis::Error::ResponseError(resp) => {
                 let code = resp.status.as_u16();
+                let message = TapisErrorBody::message_or_raw(resp.content);
                 if code == 401 || code == 403 {
-                    DeploymentError::TapisAuthFailed(resp.content)
+                    DeploymentError::TapisAuthFailed(message)
                 } else if code == 400 {
-                    DeploymentError::TapisBadRequest(resp.content)
+                    DeploymentError::TapisBadRequest(message)
                 } else if (500..600).contains(&code) {
-                    DeploymentError::TapisInternalServerError(resp.content)
+                    DeploymentError::TapisInternalServerError(message)
                 } else {
-                    DeploymentError::UnknownError(resp.content)
+                    DeploymentError::UnknownError(message)
                 }
             }
         }
@@ -233,10 +233,14 @@ impl FlexServHPCDeployment {
         None
     }
 
+    /// Scan `tapisjob.out` for the FlexServ access line. With `poll` the log is re-read up to
+    /// five times with backoff (starting at 5 s) while the server starts; without it a single
+    /// read is made.
     async fn fetch_running_access_from_logs(
         &self,
         config: &configuration::Configuration,
         job: &models::Job,
+        poll: bool,
     ) -> Option<(String, String)> {
         let exec_system_id = job.exec_system_id.as_deref()?;
         let exec_output_dir = job.exec_system_output_dir.as_deref()?;
@@ -246,34 +250,50 @@ impl FlexServHPCDeployment {
         let endpoint = format!("{}/files/content/{}/{}", base, exec_system_id, normalized_path);
         let server_ready_marker = "Server ready to accept requests";
 
-        for page in 1..=5 {
-            let mut req_builder = config.client.request(reqwest::Method::GET, endpoint.as_str());
-            req_builder = req_builder.header("more", page.to_string());
-            if let Some(ref api_key) = config.api_key {
-                let token = match api_key.prefix {
-                    Some(ref prefix) => format!("{} {}", prefix, api_key.key),
-                    None => api_key.key.clone(),
-                };
-                req_builder = req_builder.header("X-Tapis-Token", token);
-            }
-
-            let request = req_builder.build().ok()?;
-            let response = config.client.execute(request).await.ok()?;
-            if !response.status().is_success() {
-                return None;
-            }
-            let log_text = response.text().await.ok()?;
+        let backoff = BackoffConfig {
+            initial_delay: Duration::from_secs(5),
+            max_delay: Duration::from_secs(10),
+            multiplier: 1.5,
+            max_attempts: Some(if poll { 5 } else { 1 }),
+            timeout: None,
+            ..Default::default()
+        };
+        let endpoint = endpoint.as_str();
+        let outcome = poll_with_backoff(
+            |page| async move {
+                let log_text = Self::fetch_log_page(config, endpoint, page).await.ok_or(())?;
+                let access = Self::parse_access_information(&log_text);
+                Ok::<_, ()>((access, log_text.contains(server_ready_marker)))
+            },
+            |(access, ready)| access.is_some() || *ready,
+            &backoff,
+        )
+        .await;
+        outcome.ok().and_then(|(access, _)| access)
+    }
 
-            if let Some(access) = Self::parse_access_information(&log_text) {
-                return Some(access);
-            }
-            if log_text.contains(server_ready_marker) {
-                return None;
-            }
-            sleep(Duration::from_secs(5)).await;
+    /// Read one page of a job log via the Files API; None on any transport or HTTP error.
+    async fn fetch_log_page(
+        config: &configuration::Configuration,
+        endpoint: &str,
+        page: u32,
+    ) -> Option<String> {
+        let mut req_builder = config.client.request(reqwest::Method::GET, endpoint);
+        req_builder = req_builder.header("more", page.to_string());
+        if let Some(ref api_key) = config.api_key {
+            let token = match api_key.prefix {
+                Some(ref prefix) => format!("{} {}", prefix, api_key.key),
+                None => api_key.key.clone(),
+            };
+            req_builder = req_builder.header("X-Tapis-Token", token);
         }
 
-        None
+        let request = req_builder.build().ok()?;
+        let response = config.client.execute(request).await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.text().await.ok()
     }
 
     async fn running_connection_fields(
@@ -282,6 +302,7 @@ impl FlexServHPCDeployment {
         status: Option<&str>,
         job_uuid: &str,
         job: Option<&models::Job>,
+        poll: bool,
     ) -> (Option<String>, Option<String>) {
         if status != Some("RUNNING") {
             return (None, None);
@@ -301,7 +322,7 @@ impl FlexServHPCDeployment {
 
         if let Some(job_for_logs) = full_job.as_ref() {
             if let Some((hpc_url, flexserv_token)) =
-                self.fetch_running_access_from_logs(config, job_for_logs).await
+                self.fetch_running_access_from_logs(config, job_for_logs, poll).await
             {
                 return (Some(hpc_url), Some(flexserv_token));
             }
@@ -326,7 +347,7 @@ impl FlexServDeployment for FlexServHPCDeployment {
         self.job_uuid = Some(job_uuid.clone());
         let status = job.as_ref().and_then(|j| Self::job_status_from_record(j));
         let (hpc_url, flexserv_token) = self
-            .running_connection_fields(&config, status.as_deref(), &job_uuid, job.as_ref())
+            .running_connection_fields(&config, status.as_deref(), &job_uuid, job.as_ref(), true)
             .await;
 
         Ok(DeploymentResult::HPCResult {
@@ -352,7 +373,7 @@ impl FlexServDeployment for FlexServHPCDeployment {
             .unwrap_or_else(|| prior_uuid.to_string());
         let status = job.as_ref().and_then(|j| Self::job_status_from_record(j));
         let (hpc_url, flexserv_token) = self
-            .running_connection_fields(&config, status.as_deref(), &job_uuid, job.as_ref())
+            .running_connection_fields(&config, status.as_deref(), &job_uuid, job.as_ref(), true)
             .await;
         Ok(DeploymentResult::HPCResult {
             job_uuid: job_uuid.clone(),
@@ -399,6 +420,7 @@ impl FlexServDeployment for FlexServHPCDeployment {
                 status.as_deref(),
                 job_uuid,
                 full_resp.result.as_deref(),
+                false,
             )
             .await;
         Ok(DeploymentResult::HPCResult {
@@ -415,6 +437,7 @@ impl FlexServDeployment for FlexServHPCDeployment {
 mod tests {
     use super::*;
     use crate::backend::Backend;
+    use crate::test_support::MockServer;
 
     #[test]
     fn test_hpc_deployment_creation() {
@@ -498,4 +521,31 @@ mod tests {
                 && env.value.as_deref() == Some("hf_test_token")
         }));
     }
+
+    #[tokio::test]
+    async fn test_monitor_issues_only_get_requests() {
+        let mock = MockServer::start(|req| {
+            if req.path.ends_with("/status") {
+                (200, r#"{"result":{"status":"RUNNING"}}"#.to_string())
+            } else {
+                (200, r#"{"result":{"uuid":"job-1","status":"RUNNING"}}"#.to_string())
+            }
+        });
+        let mut deployment =
+            FlexServHPCDeployment::from_existing("token".to_string(), "job-1".to_string());
+        deployment.tenant_url = Some(mock.base_url.clone());
+
+        let started = std::time::Instant::now();
+        let _ = deployment.monitor().await;
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "monitor() must not sleep while polling logs"
+        );
+        let methods = mock.methods();
+        assert!(!methods.is_empty(), "monitor() should hit the Jobs API");
+        assert!(
+            methods.iter().all(|m| m == "GET"),
+            "monitor() must only issue GET requests: {methods:?}"
+        );
+    }
 }
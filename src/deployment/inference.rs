@@ -0,0 +1,312 @@
+//! Client for the OpenAI-compatible API of a running FlexServ pod. For now it covers streaming
+//! chat completions ([PodInferenceClient::chat_completions_stream]), parsed from the server's
+//! SSE `data:` lines.
+
+use super::DeploymentError;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+/// Talks to one FlexServ server at `pod_url`, authenticating with its FlexServ token (see
+/// [crate::flexserv_token] and [super::FlexServPodDeployment::inference_client]).
+#[derive(Clone, Debug)]
+pub struct PodInferenceClient {
+    pod_url: String,
+    flexserv_token: String,
+    /// Filled into requests that have no `model`.
+    model: Option<String>,
+    client: reqwest::Client,
+}
+
+/// One streamed `chat.completion.chunk`. Fields the server leaves out are empty.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChatChunk {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub created: i64,
+    #[serde(default)]
+    pub choices: Vec<ChatChunkChoice>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChatChunkChoice {
+    #[serde(default)]
+    pub index: u32,
+    #[serde(default)]
+    pub delta: ChatDelta,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// New text (and, on the first chunk, the role) for one choice.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChatDelta {
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+impl ChatChunk {
+    /// Text added to the first choice by this chunk ("" when none).
+    pub fn content(&self) -> &str {
+        self.choices
+            .first()
+            .and_then(|c| c.delta.content.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+impl PodInferenceClient {
+    pub fn new(pod_url: impl Into<String>, flexserv_token: impl Into<String>) -> Self {
+        Self {
+            pod_url: pod_url.into(),
+            flexserv_token: flexserv_token.into(),
+            model: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use `model` for requests that do not name one (FlexServ serves the model by its path,
+    /// see [crate::model_path]).
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Use `client` for requests (e.g. with custom TLS or proxies). It should have no overall
+    /// timeout, or streams are cut off after it.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// `method {pod_url}{path}`, authenticated with the FlexServ token as both
+    /// `X-FlexServ-Secret` and `Authorization: Bearer`.
+    pub(crate) fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, self.endpoint(path))
+            .header("X-FlexServ-Secret", &self.flexserv_token)
+            .bearer_auth(&self.flexserv_token)
+    }
+
+    /// `{pod_url}{path}`, e.g. for error messages.
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        crate::utils::join_url_path(&self.pod_url, path)
+    }
+
+    /// `POST {pod_url}/v1/chat/completions` with `request` (an OpenAI chat request body) and
+    /// `"stream": true`, yielding each chunk as it arrives until the server sends `[DONE]`.
+    ///
+    /// A failed request or non-2xx response is a single InferenceFailed item. Mid-stream, an
+    /// `{"error": ...}` event, an unparsable chunk, a dropped connection, or the stream ending
+    /// without `[DONE]` is yielded as InferenceFailed and ends the stream.
+    pub fn chat_completions_stream(
+        &self,
+        request: serde_json::Value,
+    ) -> impl Stream<Item = Result<ChatChunk, DeploymentError>> {
+        let state = match request {
+            serde_json::Value::Object(mut body) => {
+                body.insert("stream".to_string(), serde_json::json!(true));
+                if let Some(ref model) = self.model {
+                    body.entry("model")
+                        .or_insert_with(|| serde_json::json!(model));
+                }
+                StreamState::Request(
+                    self.request(reqwest::Method::POST, "/v1/chat/completions")
+                        .header("Accept", "text/event-stream")
+                        .json(&body),
+                )
+            }
+            other => StreamState::Failed(DeploymentError::InvalidConfiguration(format!(
+                "chat request must be a JSON object, got {}",
+                other
+            ))),
+        };
+        futures::stream::unfold(state, next_chunk)
+    }
+}
+
+enum StreamState {
+    Request(reqwest::RequestBuilder),
+    Reading {
+        resp: reqwest::Response,
+        buf: Vec<u8>,
+    },
+    Failed(DeploymentError),
+    Done,
+}
+
+fn stream_error(msg: impl Into<String>) -> DeploymentError {
+    DeploymentError::InferenceFailed(msg.into())
+}
+
+/// Advance the stream: send the request on first call, then read until the next complete
+/// `data:` line.
+async fn next_chunk(
+    state: StreamState,
+) -> Option<(Result<ChatChunk, DeploymentError>, StreamState)> {
+    let (mut resp, mut buf) = match state {
+        StreamState::Done => return None,
+        StreamState::Failed(e) => return Some((Err(e), StreamState::Done)),
+        StreamState::Request(request) => match request.send().await {
+            Err(e) => return Some((Err(stream_error(e.to_string())), StreamState::Done)),
+            Ok(resp) if !resp.status().is_success() => {
+                let status = resp.status();
+                let text = resp.text().await.unwrap_or_default();
+                let e = stream_error(format!("chat completions returned {}: {}", status, text));
+                return Some((Err(e), StreamState::Done));
+            }
+            Ok(resp) => (resp, Vec::new()),
+        },
+        StreamState::Reading { resp, buf } => (resp, buf),
+    };
+    loop {
+        while let Some(end) = buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            // Comments, `event:`/`id:` fields, and blank separators carry no chunk.
+            let Some(data) = line.trim_end().strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if data == "[DONE]" {
+                return None;
+            }
+            if data.is_empty() {
+                continue;
+            }
+            return match parse_chunk(data) {
+                Ok(chunk) => Some((Ok(chunk), StreamState::Reading { resp, buf })),
+                Err(e) => Some((Err(e), StreamState::Done)),
+            };
+        }
+        match resp.chunk().await {
+            Ok(Some(bytes)) => buf.extend_from_slice(&bytes),
+            // A last line without a trailing newline still counts.
+            Ok(None) if !buf.is_empty() => buf.push(b'\n'),
+            Ok(None) => {
+                let e = stream_error("chat completions stream ended without [DONE]");
+                return Some((Err(e), StreamState::Done));
+            }
+            Err(e) => return Some((Err(stream_error(e.to_string())), StreamState::Done)),
+        }
+    }
+}
+
+/// One `data:` payload: a chunk, or the error the server reported in place of one.
+fn parse_chunk(data: &str) -> Result<ChatChunk, DeploymentError> {
+    let value: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| stream_error(format!("bad chunk {:?}: {}", data, e)))?;
+    if let Some(error) = value.get("error") {
+        let msg = error["message"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        return Err(stream_error(format!("server error mid-stream: {}", msg)));
+    }
+    serde_json::from_value(value).map_err(|e| stream_error(format!("bad chunk {:?}: {}", data, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockServer;
+    use futures::StreamExt;
+
+    fn sse(events: &[&str]) -> String {
+        events.iter().map(|e| format!("data: {}\n\n", e)).collect()
+    }
+
+    fn chunk_json(content: &str) -> String {
+        serde_json::json!({
+            "id": "c1", "object": "chat.completion.chunk", "created": 1, "model": "m",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}]
+        })
+        .to_string()
+    }
+
+    async fn collect(
+        client: &PodInferenceClient,
+        request: serde_json::Value,
+    ) -> Vec<Result<ChatChunk, DeploymentError>> {
+        client.chat_completions_stream(request).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_stream_yields_chunks_until_done() {
+        let body = format!(
+            ": keep-alive\n\n{}",
+            sse(&[
+                &chunk_json("Hel"),
+                &chunk_json("lo"),
+                &chunk_json("!"),
+                "[DONE]",
+                &chunk_json("ignored"),
+            ])
+        );
+        let mock = MockServer::start(move |_| (200, body.clone()));
+        let client =
+            PodInferenceClient::new(mock.base_url.clone(), "tok").with_model("/app/models/m");
+        let messages = serde_json::json!({"messages": [{"role": "user", "content": "hi"}]});
+        let chunks = collect(&client, messages).await;
+        let text: String = chunks
+            .iter()
+            .map(|c| c.as_ref().unwrap().content())
+            .collect();
+        assert_eq!(text, "Hello!");
+        assert_eq!(chunks.len(), 3);
+
+        let req = &mock.requests()[0];
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/v1/chat/completions");
+        assert_eq!(req.header("Authorization"), Some("Bearer tok"));
+        let sent: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+        assert_eq!(sent["stream"], true);
+        assert_eq!(sent["model"], "/app/models/m");
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_stream_errors() {
+        // Non-2xx before the stream starts: one error, nothing else.
+        let mock = MockServer::start(|_| (503, r#"{"error":"loading"}"#.to_string()));
+        let client = PodInferenceClient::new(mock.base_url.clone(), "tok");
+        let items = collect(&client, serde_json::json!({"messages": []})).await;
+        assert_eq!(items.len(), 1);
+        assert!(matches!(&items[0], Err(DeploymentError::InferenceFailed(m)) if m.contains("503")));
+
+        // An error event mid-stream ends it after the chunks before it.
+        let body = sse(&[
+            &chunk_json("a"),
+            r#"{"error":{"message":"CUDA out of memory"}}"#,
+            &chunk_json("b"),
+        ]);
+        let mock = MockServer::start(move |_| (200, body.clone()));
+        let client = PodInferenceClient::new(mock.base_url.clone(), "tok");
+        let items = collect(&client, serde_json::json!({"messages": []})).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap().content(), "a");
+        assert!(
+            matches!(&items[1], Err(DeploymentError::InferenceFailed(m)) if m.contains("CUDA out of memory"))
+        );
+
+        // A stream cut off before [DONE].
+        let body = sse(&[&chunk_json("a")]);
+        let mock = MockServer::start(move |_| (200, body.clone()));
+        let client = PodInferenceClient::new(mock.base_url.clone(), "tok");
+        let items = collect(&client, serde_json::json!({"messages": []})).await;
+        assert_eq!(items.len(), 2);
+        assert!(
+            matches!(&items[1], Err(DeploymentError::InferenceFailed(m)) if m.contains("[DONE]"))
+        );
+
+        // Not an object: rejected without a request.
+        let items = collect(&client, serde_json::json!(["hi"])).await;
+        assert!(matches!(
+            &items[..],
+            [Err(DeploymentError::InvalidConfiguration(_))]
+        ));
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
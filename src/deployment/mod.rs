@@ -1,14 +1,29 @@
 //! Deployment module: common types and traits, plus Pod and HPC implementations.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use tapis_sdk::jobs::models::Job;
 
+mod client;
 mod hpc;
+mod inference;
 mod pod;
+mod trace;
 
+pub use client::{shared_tapis_client, tapis_client, TokenProvider};
 pub use hpc::{FlexServHPCDeployment, HpcDeploymentOptions};
-pub use pod::{FlexServPodDeployment, PodDeploymentOptions};
+pub use inference::{ChatChunk, ChatChunkChoice, ChatDelta, PodInferenceClient};
+pub use pod::{
+    check_gpu_availability, estimate_cost, flexserv_token, health_check, model_path, monitor_all,
+    served_models, AutoTerminatePolicy, CostInputs, CostRates, CreateMode, DeploymentEvent,
+    DeploymentPlan, DownloadRetries, DownloadTool, FitResult, FlexServPodDeployment,
+    HealthCheckConfig, HealthSummary, IdStrategy, ModelSource, OperationTimeouts,
+    PodDeploymentOptions, SpecDiff, FLEXSERV_MODEL_REPO, MAX_TAPIS_ID_LEN, MONITOR_ALL_CONCURRENCY,
+    RESTART_POLICIES,
+};
+/// The Pods API model returned by [FlexServPodDeployment::monitor_raw].
+pub use tapis_sdk::pods::models::PodResponseModel;
+pub use trace::{OperationTrace, TraceEntry};
 
 /// Deployment result enum.
 /// Implements Serialize so HTTP handlers can return it as JSON (e.g. `HttpResponse::Ok().json(result)`).
@@ -21,13 +36,27 @@ pub enum DeploymentResult {
         volume_id: String,
         /// URL to reach the pod (e.g. from networking.default.url). Use for inference or health checks.
         pod_url: Option<String>,
+        /// URLs of every replica (one entry for a single-pod deployment; empty after terminate).
+        pod_urls: Vec<String>,
         /// Raw TAPIS pod status (e.g. `AVAILABLE`, `RUNNING`, `STOPPED`).
         status: Option<String>,
+        /// Current utilization when TAPIS reports it (monitor() only).
+        resource_usage: Option<ResourceUsage>,
         pod_info: String,
+        /// Debug dump of the TAPIS volume (monitor() only; empty with
+        /// `PodDeploymentOptions::skip_volume_info_dump`).
         volume_info: String,
+        /// Parsed volume details, including disk usage (monitor() only).
+        volume: Option<VolumeInfo>,
+        /// Conditions and events of the primary pod, oldest first (monitor() only), e.g. why
+        /// it is stuck in Pending. Empty when TAPIS reports none.
+        conditions: Vec<PodCondition>,
         tapis_user: String,
         tapis_tenant: String,
         model_id: String,
+        /// Model revision the pod downloads: the resolved commit sha when
+        /// `PodDeploymentOptions::pin_revision` is set, otherwise the configured revision.
+        model_revision: Option<String>,
     },
     HPCResult {
         /// TAPIS Jobs API job UUID (empty if the submit response did not include one).
@@ -39,11 +68,267 @@ pub enum DeploymentResult {
         /// Optional endpoint metadata returned only when job is RUNNING.
         hpc_url: Option<String>,
         /// Optional token returned only when job is RUNNING.
-        flexserv_token: Option<String>
-
+        flexserv_token: Option<String>,
     },
 }
 
+/// Borrowed view of a [DeploymentResult::PodResult], from [DeploymentResult::as_pod].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PodResultView<'a> {
+    pub pod_id: &'a str,
+    pub volume_id: &'a str,
+    pub pod_url: Option<&'a str>,
+    pub pod_urls: &'a [String],
+    pub status: Option<&'a str>,
+    pub resource_usage: Option<&'a ResourceUsage>,
+    pub pod_info: &'a str,
+    pub volume_info: &'a str,
+    pub volume: Option<&'a VolumeInfo>,
+    pub conditions: &'a [PodCondition],
+    pub tapis_user: &'a str,
+    pub tapis_tenant: &'a str,
+    pub model_id: &'a str,
+    pub model_revision: Option<&'a str>,
+}
+
+/// Borrowed view of a [DeploymentResult::HPCResult], from [DeploymentResult::as_hpc].
+#[derive(Debug, Clone, Copy)]
+pub struct HpcResultView<'a> {
+    pub job_uuid: &'a str,
+    pub status: Option<&'a str>,
+    pub job: Option<&'a Job>,
+    pub hpc_url: Option<&'a str>,
+    pub flexserv_token: Option<&'a str>,
+}
+
+impl DeploymentResult {
+    /// Fields of a pod result, or None for an HPC result.
+    pub fn as_pod(&self) -> Option<PodResultView<'_>> {
+        match self {
+            DeploymentResult::PodResult {
+                pod_id,
+                volume_id,
+                pod_url,
+                pod_urls,
+                status,
+                resource_usage,
+                pod_info,
+                volume_info,
+                volume,
+                conditions,
+                tapis_user,
+                tapis_tenant,
+                model_id,
+                model_revision,
+            } => Some(PodResultView {
+                pod_id,
+                volume_id,
+                pod_url: pod_url.as_deref(),
+                pod_urls,
+                status: status.as_deref(),
+                resource_usage: resource_usage.as_ref(),
+                pod_info,
+                volume_info,
+                volume: volume.as_ref(),
+                conditions,
+                tapis_user,
+                tapis_tenant,
+                model_id,
+                model_revision: model_revision.as_deref(),
+            }),
+            DeploymentResult::HPCResult { .. } => None,
+        }
+    }
+
+    /// Fields of an HPC result, or None for a pod result.
+    pub fn as_hpc(&self) -> Option<HpcResultView<'_>> {
+        match self {
+            DeploymentResult::HPCResult {
+                job_uuid,
+                status,
+                job,
+                hpc_url,
+                flexserv_token,
+            } => Some(HpcResultView {
+                job_uuid,
+                status: status.as_deref(),
+                job: job.as_ref(),
+                hpc_url: hpc_url.as_deref(),
+                flexserv_token: flexserv_token.as_deref(),
+            }),
+            DeploymentResult::PodResult { .. } => None,
+        }
+    }
+
+    /// Lean JSON for API responses: the structured fields only, without the raw `pod_info` /
+    /// `volume_info` Debug dumps or the full TAPIS `job` object. Serialize `self` for those.
+    /// A `type` field (`pod` or `hpc`) tells the variants apart.
+    pub fn to_api_json(&self) -> serde_json::Value {
+        match self {
+            DeploymentResult::PodResult {
+                pod_id,
+                volume_id,
+                pod_url,
+                pod_urls,
+                status,
+                resource_usage,
+                volume,
+                conditions,
+                tapis_user,
+                tapis_tenant,
+                model_id,
+                model_revision,
+                ..
+            } => serde_json::json!({
+                "type": "pod",
+                "pod_id": pod_id,
+                "volume_id": volume_id,
+                "pod_url": pod_url,
+                "pod_urls": pod_urls,
+                "status": status,
+                "resource_usage": resource_usage,
+                "volume": volume,
+                "conditions": conditions,
+                "tapis_user": tapis_user,
+                "tapis_tenant": tapis_tenant,
+                "model_id": model_id,
+                "model_revision": model_revision,
+            }),
+            DeploymentResult::HPCResult {
+                job_uuid,
+                status,
+                hpc_url,
+                flexserv_token,
+                ..
+            } => serde_json::json!({
+                "type": "hpc",
+                "job_uuid": job_uuid,
+                "status": status,
+                "hpc_url": hpc_url,
+                "flexserv_token": flexserv_token,
+            }),
+        }
+    }
+
+    /// TAPIS pod id, or None for an HPC result.
+    pub fn pod_id(&self) -> Option<&str> {
+        self.as_pod().map(|p| p.pod_id)
+    }
+
+    /// Pod URL when TAPIS reported one, or None (also for HPC results).
+    pub fn pod_url(&self) -> Option<&str> {
+        self.as_pod().and_then(|p| p.pod_url)
+    }
+}
+
+/// What a pod terminate actually cleaned up. `errors` holds one entry per failed deletion
+/// (pods first, then the volume), so callers can tell e.g. that the pod is gone but the volume leaked.
+#[derive(Debug, Default, Serialize)]
+pub struct TerminateOutcome {
+    /// Every replica pod was deleted.
+    pub pod_deleted: bool,
+    /// The volume was deleted (true when there was no volume to delete, false when a shared
+    /// `volume_id_override` volume was kept).
+    pub volume_deleted: bool,
+    pub errors: Vec<DeploymentError>,
+}
+
+impl TerminateOutcome {
+    /// Both the pod(s) and the volume are gone.
+    pub fn is_complete(&self) -> bool {
+        self.pod_deleted && self.volume_deleted
+    }
+}
+
+/// Pod resource utilization as reported by TAPIS. Fields the API does not expose are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ResourceUsage {
+    /// CPU in use, in millicores (1000 = 1 CPU).
+    pub cpu_millicores: Option<i64>,
+    /// Memory in use, in MB.
+    pub mem_mb: Option<i64>,
+    /// GPU utilization, 0-100.
+    pub gpu_percent: Option<f64>,
+}
+
+/// Model volume details as reported by TAPIS. Fields the API does not expose are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VolumeInfo {
+    pub id: String,
+    /// Size cap, in MB.
+    pub size_limit_mb: Option<i64>,
+    /// Space in use, in MB. Near `size_limit_mb` means a download may not fit.
+    pub size_used_mb: Option<i64>,
+    /// Raw TAPIS volume status (e.g. `AVAILABLE`).
+    pub status: Option<String>,
+}
+
+/// One pod condition or event (e.g. reason `ImagePullBackOff` or `Unschedulable`), from
+/// [DeploymentResult::PodResult]'s `conditions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PodCondition {
+    pub reason: String,
+    /// Human-readable detail, e.g. `0/4 nodes are available: 4 Insufficient nvidia.com/gpu.`
+    pub message: String,
+    /// When it was reported, as given by TAPIS (usually RFC 3339).
+    pub timestamp: Option<String>,
+}
+
+/// Pod status as reported by the TAPIS Pods API. Unrecognized values are kept in
+/// [PodStatus::Unknown] so a new server-side status doesn't break parsing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum PodStatus {
+    Requested,
+    Spawning,
+    Creating,
+    Running,
+    Available,
+    Deleting,
+    Stopping,
+    Stopped,
+    Complete,
+    Error,
+    Failed,
+    Unknown(String),
+}
+
+impl PodStatus {
+    /// Parse a Pods API status string (case-insensitive).
+    pub fn parse(status: &str) -> Self {
+        match status.trim().to_uppercase().as_str() {
+            "REQUESTED" => PodStatus::Requested,
+            "SPAWNING" => PodStatus::Spawning,
+            "CREATING" => PodStatus::Creating,
+            "RUNNING" => PodStatus::Running,
+            "AVAILABLE" => PodStatus::Available,
+            "DELETING" => PodStatus::Deleting,
+            "STOPPING" => PodStatus::Stopping,
+            "STOPPED" => PodStatus::Stopped,
+            "COMPLETE" => PodStatus::Complete,
+            "ERROR" => PodStatus::Error,
+            "FAILED" => PodStatus::Failed,
+            _ => PodStatus::Unknown(status.to_string()),
+        }
+    }
+
+    /// The pod will not change status on its own from here (stopped, complete, or failed).
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            PodStatus::Stopped | PodStatus::Complete | PodStatus::Error | PodStatus::Failed
+        )
+    }
+}
+
+impl fmt::Display for PodStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PodStatus::Unknown(s) => write!(f, "{}", s),
+            other => write!(f, "{}", format!("{:?}", other).to_uppercase()),
+        }
+    }
+}
+
 /// Deployment related errors
 /// We can bind the message to this enum variant for more detailed error information
 /// 1. TapisAuthFailed(String) - Authentication to Tapis failed
@@ -55,6 +340,11 @@ pub enum DeploymentResult {
 /// 7. ModelUploadingFailed(String) - Model uploading failed not because of any of the reasons from 1-6.
 /// 8. PodCreationFailed(String) - Pod creation failed not because of any of the reasons from 1-6.
 /// 9. JobCreationFailed(String) - Job creation failed not because of any of the reasons from 1-6.
+/// 10. InferenceFailed(String) - A request to the deployed inference server (e.g. warmup) failed.
+/// 11. CreateFailed { stage, source, rollback_errors } - create() failed at `stage`; `source` is the underlying
+///     error and `rollback_errors` lists resources that could not be cleaned up afterwards.
+/// 12. IncompatibleImage { image, backend } - the pod image is known not to support the backend.
+///
 /// Each variant carries a message; implements Display, Error, and Serialize so call sites can
 /// show messages, use `?`, and return JSON from HTTP handlers (e.g. `HttpResponse::BadRequest().json(err)`).
 #[derive(Debug, Serialize)]
@@ -69,6 +359,18 @@ pub enum DeploymentError {
     ModelUploadingFailed(String),
     PodCreationFailed(String),
     JobCreationFailed(String),
+    InferenceFailed(String),
+    CreateFailed {
+        stage: CreateStage,
+        source: Box<DeploymentError>,
+        /// Resources create() made but could not delete again while rolling back.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        rollback_errors: Vec<String>,
+    },
+    IncompatibleImage {
+        image: String,
+        backend: String,
+    },
     // TODO: extra errors
     // JobExecutionFailed
 }
@@ -92,11 +394,135 @@ impl fmt::Display for DeploymentError {
             DeploymentError::ModelUploadingFailed(msg) => write!(f, "Model upload failed: {}", msg),
             DeploymentError::PodCreationFailed(msg) => write!(f, "Pod creation failed: {}", msg),
             DeploymentError::JobCreationFailed(msg) => write!(f, "Job creation failed: {}", msg),
+            DeploymentError::InferenceFailed(msg) => write!(f, "Inference request failed: {}", msg),
+            DeploymentError::CreateFailed {
+                stage,
+                source,
+                rollback_errors,
+            } => {
+                write!(f, "Create failed during {}: {}", stage, source)?;
+                if !rollback_errors.is_empty() {
+                    write!(f, " (rollback failed: {})", rollback_errors.join("; "))?;
+                }
+                Ok(())
+            }
+            DeploymentError::IncompatibleImage { image, backend } => {
+                write!(f, "Image {} does not support backend {}", image, backend)
+            }
         }
     }
 }
 
-impl std::error::Error for DeploymentError {}
+impl std::error::Error for DeploymentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeploymentError::CreateFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Step of [FlexServDeployment::create] that failed, carried by [DeploymentError::CreateFailed].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CreateStage {
+    /// Deleting pods left over from an earlier deployment with the same ids.
+    Cleanup,
+    /// Creating (or preparing) the model volume.
+    VolumeCreate,
+    /// Creating the pod(s).
+    PodCreate,
+}
+
+impl fmt::Display for CreateStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreateStage::Cleanup => write!(f, "cleanup"),
+            CreateStage::VolumeCreate => write!(f, "volume create"),
+            CreateStage::PodCreate => write!(f, "pod create"),
+        }
+    }
+}
+
+impl DeploymentError {
+    /// Wrap `self` as a [DeploymentError::CreateFailed] at `stage`.
+    pub fn at_stage(self, stage: CreateStage) -> Self {
+        DeploymentError::CreateFailed {
+            stage,
+            source: Box::new(self),
+            rollback_errors: Vec::new(),
+        }
+    }
+
+    /// Attach rollback failures to a [DeploymentError::CreateFailed]; other errors are returned as is.
+    pub(crate) fn with_rollback_errors(mut self, errors: Vec<String>) -> Self {
+        if let DeploymentError::CreateFailed {
+            ref mut rollback_errors,
+            ..
+        } = self
+        {
+            rollback_errors.extend(errors);
+        }
+        self
+    }
+
+    /// Deletes that failed while rolling back a failed create(); empty when rollback succeeded
+    /// or nothing needed undoing. Each entry names the pod or volume left behind.
+    pub fn rollback_errors(&self) -> &[String] {
+        match self {
+            DeploymentError::CreateFailed {
+                rollback_errors, ..
+            } => rollback_errors,
+            _ => &[],
+        }
+    }
+
+    /// The create() stage that failed, if this error came from one.
+    pub fn stage(&self) -> Option<CreateStage> {
+        match self {
+            DeploymentError::CreateFailed { stage, .. } => Some(*stage),
+            _ => None,
+        }
+    }
+
+    /// The underlying error with any stage wrapper removed.
+    pub fn root_cause(&self) -> &DeploymentError {
+        match self {
+            DeploymentError::CreateFailed { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+
+    /// Transient TAPIS failures (unreachable, timeout, 5xx) that are worth retrying as-is.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.root_cause(),
+            DeploymentError::TapisAPIUnreachable(_)
+                | DeploymentError::TapisTimeout(_)
+                | DeploymentError::TapisInternalServerError(_)
+        )
+    }
+}
+
+/// Standard TAPIS error envelope (e.g. `{"message": "...", "status": "error", "version": "..."}`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapisErrorBody {
+    pub message: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl TapisErrorBody {
+    /// Human-readable message from a TAPIS error response body; falls back to the raw content
+    /// when it is not a TAPIS envelope or the message is empty.
+    pub fn message_or_raw(content: String) -> String {
+        match serde_json::from_str::<TapisErrorBody>(&content) {
+            Ok(body) if !body.message.trim().is_empty() => body.message,
+            _ => content,
+        }
+    }
+}
 
 /// FlexServ deployment trait.
 /// All methods are async to avoid deadlocks when called from an async runtime.
@@ -106,6 +532,9 @@ pub trait FlexServDeployment {
     async fn start(&self) -> Result<DeploymentResult, DeploymentError>;
     async fn stop(&self) -> Result<DeploymentResult, DeploymentError>;
     async fn terminate(&self) -> Result<DeploymentResult, DeploymentError>;
+    /// Read-only status snapshot. Implementations must issue only GET requests, must not
+    /// sleep or poll, and must not write to stdout/stderr, so it is safe to call in a tight
+    /// dashboard refresh loop.
     async fn monitor(&self) -> Result<DeploymentResult, DeploymentError>;
 }
 
@@ -129,18 +558,52 @@ mod tests {
         assert!(e.source().is_none());
     }
 
+    #[test]
+    fn test_create_failed_stage() {
+        let e =
+            DeploymentError::TapisTimeout("slow".to_string()).at_stage(CreateStage::VolumeCreate);
+        assert_eq!(e.stage(), Some(CreateStage::VolumeCreate));
+        assert!(matches!(e.root_cause(), DeploymentError::TapisTimeout(_)));
+        assert!(e.is_retryable());
+        assert_eq!(
+            e.to_string(),
+            "Create failed during volume create: TAPIS timeout: slow"
+        );
+        assert!(e.source().is_some());
+        assert_eq!(DeploymentError::UnknownError("x".to_string()).stage(), None);
+    }
+
+    #[test]
+    fn test_pod_status_parse_and_display() {
+        assert_eq!(PodStatus::parse("AVAILABLE"), PodStatus::Available);
+        assert_eq!(PodStatus::parse(" stopped "), PodStatus::Stopped);
+        assert_eq!(
+            PodStatus::parse("HIBERNATING"),
+            PodStatus::Unknown("HIBERNATING".to_string())
+        );
+        assert_eq!(PodStatus::Available.to_string(), "AVAILABLE");
+        assert_eq!(PodStatus::Unknown("X".to_string()).to_string(), "X");
+        assert!(PodStatus::Failed.is_terminal());
+        assert!(!PodStatus::Running.is_terminal());
+    }
+
     #[test]
     fn test_deployment_result_pod_variant() {
         let r = DeploymentResult::PodResult {
             pod_id: "p1".to_string(),
             volume_id: "v1".to_string(),
             pod_url: Some("http://pod:8000".to_string()),
+            pod_urls: vec!["http://pod:8000".to_string()],
             status: Some("AVAILABLE".to_string()),
+            resource_usage: None,
             pod_info: "info".to_string(),
             volume_info: "vol".to_string(),
+            volume: None,
+            conditions: Vec::new(),
             tapis_user: "u".to_string(),
             tapis_tenant: "t".to_string(),
             model_id: "m".to_string(),
+            model_revision: None,
         };
         match &r {
             DeploymentResult::PodResult {
@@ -152,4 +615,116 @@ mod tests {
             _ => panic!("expected PodResult"),
         }
     }
+
+    #[test]
+    fn test_to_api_json_omits_raw_blobs() {
+        let blob = "PodResponseModel { ... }".repeat(1000);
+        let r = DeploymentResult::PodResult {
+            pod_id: "p1".to_string(),
+            volume_id: "v1".to_string(),
+            pod_url: Some("http://pod:8000".to_string()),
+            pod_urls: vec!["http://pod:8000".to_string()],
+            status: Some("AVAILABLE".to_string()),
+            resource_usage: Some(ResourceUsage {
+                cpu_millicores: Some(250),
+                mem_mb: None,
+                gpu_percent: None,
+            }),
+            pod_info: blob.clone(),
+            volume_info: blob,
+            volume: None,
+            conditions: Vec::new(),
+            tapis_user: "u".to_string(),
+            tapis_tenant: "t".to_string(),
+            model_id: "m".to_string(),
+            model_revision: Some("main".to_string()),
+        };
+        let json = r.to_api_json();
+        assert_eq!(json["type"], "pod");
+        assert_eq!(json["pod_id"], "p1");
+        assert_eq!(json["pod_url"], "http://pod:8000");
+        assert_eq!(json["status"], "AVAILABLE");
+        assert_eq!(json["model_id"], "m");
+        assert_eq!(json["resource_usage"]["cpu_millicores"], 250);
+        assert!(json.get("pod_info").is_none());
+        assert!(json.get("volume_info").is_none());
+        assert!(json.to_string().len() < 1000);
+
+        let r = DeploymentResult::HPCResult {
+            job_uuid: "j1".to_string(),
+            status: Some("RUNNING".to_string()),
+            job: None,
+            hpc_url: None,
+            flexserv_token: None,
+        };
+        let json = r.to_api_json();
+        assert_eq!(json["type"], "hpc");
+        assert_eq!(json["job_uuid"], "j1");
+        assert!(json.get("job").is_none());
+    }
+
+    #[test]
+    fn test_tapis_error_body_extracts_message() {
+        let raw = r#"{"message":"Pod not found.","metadata":{},"result":null,"status":"error","version":"dev"}"#;
+        assert_eq!(TapisErrorBody::message_or_raw(raw.to_string()), "Pod not found.");
+    }
+
+    #[test]
+    fn test_tapis_error_body_falls_back_to_raw() {
+        assert_eq!(
+            TapisErrorBody::message_or_raw("<html>Bad Gateway</html>".to_string()),
+            "<html>Bad Gateway</html>"
+        );
+        let empty_message = r#"{"message":"","status":"error"}"#;
+        assert_eq!(
+            TapisErrorBody::message_or_raw(empty_message.to_string()),
+            empty_message
+        );
+    }
+
+    #[test]
+    fn test_deployment_result_accessors_pod() {
+        let r = DeploymentResult::PodResult {
+            pod_id: "p1".to_string(),
+            volume_id: "v1".to_string(),
+            pod_url: Some("http://pod:8000".to_string()),
+            pod_urls: vec!["http://pod:8000".to_string()],
+            status: Some("AVAILABLE".to_string()),
+            resource_usage: None,
+            pod_info: "info".to_string(),
+            volume_info: "vol".to_string(),
+            volume: None,
+            conditions: Vec::new(),
+            tapis_user: "u".to_string(),
+            tapis_tenant: "t".to_string(),
+            model_id: "m".to_string(),
+            model_revision: None,
+        };
+        assert_eq!(r.pod_id(), Some("p1"));
+        assert_eq!(r.pod_url(), Some("http://pod:8000"));
+        let pod = r.as_pod().unwrap();
+        assert_eq!(pod.volume_id, "v1");
+        assert_eq!(pod.status, Some("AVAILABLE"));
+        assert_eq!(pod.pod_urls.len(), 1);
+        assert!(r.as_hpc().is_none());
+    }
+
+    #[test]
+    fn test_deployment_result_accessors_hpc() {
+        let r = DeploymentResult::HPCResult {
+            job_uuid: "job-1".to_string(),
+            status: Some("RUNNING".to_string()),
+            job: None,
+            hpc_url: Some("https://node:8443".to_string()),
+            flexserv_token: None,
+        };
+        assert_eq!(r.pod_id(), None);
+        assert_eq!(r.pod_url(), None);
+        assert!(r.as_pod().is_none());
+        let hpc = r.as_hpc().unwrap();
+        assert_eq!(hpc.job_uuid, "job-1");
+        assert_eq!(hpc.status, Some("RUNNING"));
+        assert_eq!(hpc.hpc_url, Some("https://node:8443"));
+        assert!(hpc.job.is_none());
+    }
 }
@@ -1,7 +1,18 @@
-use super::{DeploymentError, DeploymentResult, FlexServDeployment};
-use crate::backend::Backend;
-use crate::server::{FlexServInstance, ModelConfig, TapisConfig, ValidationError};
-use reqwest::header::{HeaderMap, HeaderValue};
+use super::client::{flexserv_client, shared_tapis_client, tapis_client, TokenProvider};
+use super::inference::PodInferenceClient;
+use super::trace::{OperationTrace, TraceBuffer, TraceMiddleware};
+use super::{
+    CreateStage, DeploymentError, DeploymentResult, FlexServDeployment, PodCondition, PodStatus,
+    ResourceUsage, TapisErrorBody, TerminateOutcome, VolumeInfo,
+};
+use crate::backend::{Backend, BackendParameterSetBuilder, PodParameterSet, ResourceHints};
+use crate::polling::{poll_with_backoff, BackoffConfig, PollError};
+use crate::server::{
+    ExtraModel, FlexServInstance, ModelConfig, TapisConfig, ValidationError, DEFAULT_VOLUME_SIZE_MB,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tapis_sdk::pods::apis;
 use tapis_sdk::pods::apis::configuration;
 use tapis_sdk::pods::apis::pods_api;
@@ -14,9 +25,10 @@ use tapis_sdk::pods::models;
 pub struct PodDeploymentOptions {
     /// Optional deployment id (e.g. UUID from MLHub). When set, pod_id and volume_id are derived from this
     pub deployment_id: Option<String>,
-    /// Volume size in MB. Default 10240 (10 GB).
+    /// Volume size in MB. When None, create() estimates it from Hugging Face model metadata
+    /// and falls back to 10240 (10 GB) if the estimate is unavailable.
     pub volume_size_mb: Option<i32>,
-    /// Container image. Default "tapis/flexserv:1.0".
+    /// Container image. Default: `FLEXSERV_DEFAULT_IMAGE` env, else "tapis/flexserv:1.0".
     pub image: Option<String>,
     /// CPU request in millicpus (1000 = 1 CPU). Default 1000.
     pub cpu_request: Option<i32>,
@@ -28,12 +40,913 @@ pub struct PodDeploymentOptions {
     pub mem_limit_mb: Option<i32>,
     /// Number of GPUs. Default 0.
     pub gpus: Option<i32>,
+    /// Minimum memory per GPU in MB, passed as the `GPU_MEMORY_MB` env var (the Pods API has no
+    /// such field). Requires `gpus >= 1`. Default None.
+    pub gpu_memory_mb: Option<i32>,
+    /// MIG profile (e.g. `1g.10gb`) for a GPU slice, passed as the `GPU_MIG_PROFILE` env var.
+    /// Requires `gpus >= 1`. Default None.
+    pub mig_profile: Option<String>,
     /// Optional secret prepended to pod auth token. If None, uses `FLEXSERV_SECRET` env.
     pub flexserv_secret: Option<String>,
+    /// Extra PEM-encoded root certificate(s) to trust for TAPIS and the pod's FlexServ server
+    /// (private CA / on-prem tenants). System roots are still trusted. Default None.
+    pub ca_cert_pem: Option<String>,
+    /// Skip TLS certificate verification for TAPIS and FlexServ calls. Development only; default
+    /// false.
+    pub danger_accept_invalid_certs: bool,
+    /// Proxy URL (`http://` or `https://`) for outbound TAPIS and FlexServ calls. When None, the
+    /// standard `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` env vars are honored. Default None.
+    pub http_proxy: Option<String>,
+    /// Number of identical pods sharing the model volume. Default 1. With more than one,
+    /// replica ids are `{pod_id}{index}` (index in lowercase base36, so ids stay alphanumeric).
+    pub replicas: Option<u32>,
+    /// JSON object deep-merged into the serialized `NewPod` before submission, for Pods API
+    /// fields not modeled here (affinity, tolerations, ...). Its values win. Default None.
+    pub raw_pod_overrides: Option<serde_json::Value>,
+    /// Optional team/namespace tag inserted after the `p`/`v` prefixes (e.g. `team` gives
+    /// `pteam{suffix}` / `vteam{suffix}`). Normalized to lowercase alphanumeric like deployment_id.
+    pub id_prefix: Option<String>,
+    /// How the id suffix after the `p`/`v` marker and `id_prefix` is derived. Default
+    /// [IdStrategy::Default] (normalized `deployment_id`, else the config hash).
+    pub id_strategy: IdStrategy,
+    /// Use this existing (shared) volume instead of `v{suffix}`; create() never deletes it, and
+    /// terminate() only with `delete_shared_volume`. Must be a valid TAPIS id. Default None.
+    pub volume_id_override: Option<String>,
+    /// Leave monitor()'s `volume_info` Debug dump empty; the parsed `volume` field is still
+    /// filled. Default false (both are returned).
+    pub skip_volume_info_dump: bool,
+    /// Let terminate() delete a `volume_id_override` volume. Default false.
+    pub delete_shared_volume: bool,
+    /// Keep and reuse the model volume when pod creation fails, retrying pods on transient TAPIS
+    /// errors, so a large download is not repeated. Default false.
+    pub reuse_volume_on_pod_failure: bool,
+    /// Resolve the model revision to a commit sha via the Hugging Face API at create time, so
+    /// redeploys get identical weights. Default false.
+    pub pin_revision: bool,
+    /// Pre-built client for TAPIS calls that already sends `X-Tapis-Token`; the TLS, proxy and
+    /// `extra_headers` options are then ignored. Default None (built once per deployment).
+    pub http_client: Option<reqwest::Client>,
+    /// Ask this for the TAPIS token on every operation instead of using the fixed `tapis_token`.
+    /// Ignored with `http_client`. Default None.
+    pub token_provider: Option<TokenProvider>,
+    /// Single-line init commands run in order (`set -e`) by a `/bin/sh -c` startup script before
+    /// the backend command is `exec`ed. Default empty.
+    pub pre_start_commands: Vec<String>,
+    /// Send one small completion after [FlexServPodDeployment::wait_until_ready] succeeds, so the
+    /// first real request doesn't pay for cold caches. Default false.
+    pub warmup: bool,
+    /// Prompt for the warmup request. Default "Hello".
+    pub warmup_prompt: Option<String>,
+    /// Prompts sent in turn during warmup, replacing `warmup_prompt`; chat-template models are
+    /// warmed through `/v1/chat/completions`. Default empty.
+    pub warmup_prompts: Vec<String>,
+    /// `max_tokens` for the warmup request. Default 8.
+    pub warmup_max_tokens: Option<u32>,
+    /// `--log-level` for the served process (`debug`, `info`, `warning`, or `error`), e.g. to
+    /// debug one deployment. Transformers backend only. Default None (the server's default).
+    pub server_log_level: Option<String>,
+    /// `--continuous-batching` for the served process; higher throughput under concurrent
+    /// requests. Transformers backend only. Default None (the server's default).
+    pub continuous_batching: Option<bool>,
+    /// `--attn-implementation` for the served process (`eager`, `sdpa`, or `flash_attention_2`).
+    /// Transformers backend only. Default None (the server's default).
+    pub attn_implementation: Option<String>,
+    /// Write the FlexServ token to a file on the model volume and read it in the startup script,
+    /// keeping it out of the pod spec. Needs a backend command. Default false.
+    pub token_via_file: bool,
+    /// Turn [PodDeploymentOptions::validate] warnings (e.g. GPUs requested for a backend that
+    /// can't use them) into errors. Default false.
+    pub strict_validation: bool,
+    /// Poll `get_pod` for up to this many seconds for a `pod_url`, like [CreateMode::WaitUrl].
+    /// Only used with [CreateMode::Submit]. Default None (return immediately).
+    pub wait_for_url_secs: Option<u64>,
+    /// How long create() waits once TAPIS accepts the pod; see [CreateMode]. Default
+    /// [CreateMode::Submit].
+    pub create_mode: CreateMode,
+    /// Overall time limit for each [FlexServDeployment] operation; see [OperationTimeouts].
+    pub timeouts: OperationTimeouts,
+    /// Extra headers sent on every TAPIS call (e.g. an API gateway key). Invalid names or values
+    /// fail operations with TapisAuthFailed. Default empty.
+    pub extra_headers: std::collections::HashMap<String, String>,
+    /// Where the pod gets the model. Default [ModelSource::HuggingFace].
+    pub model_source: ModelSource,
+    /// Path under which the tenant's ingress serves the pod (e.g. `/pods/p123`), appended to the
+    /// networking URL for `pod_url`. Default None (served at the root).
+    pub url_path_prefix: Option<String>,
+    /// Python interpreter that runs the backend server, e.g. a CUDA-specific venv in a custom
+    /// image. Transformers backend only. Default [crate::backend::TRANSFORMERS_PYTHON_BIN].
+    pub python_bin: Option<String>,
+    /// Backend server script passed to `python_bin`. Transformers backend only. Default
+    /// [crate::backend::TRANSFORMERS_SERVER_PATH].
+    pub backend_server_path: Option<String>,
+    /// POST a [DeploymentEvent] here after create() and terminate(), best effort (failures are
+    /// only logged). Default None.
+    pub webhook_url: Option<String>,
+    /// Project tag, recorded as `[project: <id>]` in the pod and volume descriptions and as the
+    /// `TAPIS_PROJECT_ID` env var. Non-empty, no whitespace. Default None.
+    pub project_id: Option<String>,
+    /// Model directory under [FLEXSERV_MODEL_REPO] replacing the derived `org_model` name, e.g.
+    /// `org/model`. Relative, without `..`. Default None.
+    pub model_subdir: Option<String>,
+    /// Extra server arguments in CLI form (e.g. `--dtype half`), split like a shell and appended
+    /// after the builder's arguments. Default None.
+    pub raw_args: Option<String>,
+    /// Record every Pods API request and response, secrets redacted, for
+    /// [FlexServPodDeployment::take_trace]. Default false.
+    pub capture_trace: bool,
+    /// Dotenv-style file whose variables are added to the pod environment at create time; other
+    /// options and the backend take precedence. Default None.
+    pub env_file: Option<String>,
+    /// Pod restart policy, one of [RESTART_POLICIES]. Validated but not sent: the Pods API has no
+    /// such field, so TAPIS's own restart behavior applies. Default None.
+    pub restart_policy: Option<String>,
+    /// Have the startup script download Hugging Face models (main and `server.extra_models`) with
+    /// this tool. Needs a backend command. Default None (the image downloads the model).
+    pub download_tool: Option<DownloadTool>,
+    /// Fail create() with [DeploymentError::IncompatibleImage] when the image is known not to
+    /// support the backend, instead of warning. Default false.
+    pub strict_image_check: bool,
+    /// Retry each model download in the startup script instead of failing on the first error.
+    /// Default None (one attempt).
+    pub download_retries: Option<DownloadRetries>,
+    /// Fail create() up front when the Hugging Face model is larger than this many MB or an
+    /// explicit `volume_size_mb`; skipped if the size is unknown. Default None.
+    pub max_model_size_mb: Option<i32>,
+    /// Have create() first delete pods and the volume left under the same ids (see
+    /// [FlexServPodDeployment::cleanup]). Default false: create() assumes a clean slate.
+    pub cleanup_before_create: bool,
+    /// Fail create() when a gated Hugging Face model has no HF token, instead of only warning.
+    /// Default false.
+    pub require_hf_token_for_gated: bool,
+    /// Health endpoint for images without the stock `/v1/flexserv/health`; wait_until_ready also
+    /// waits for it when set. Default None.
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+/// Where [health_check] probes a FlexServ server and what counts as healthy. The defaults
+/// match the stock image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    /// Path joined onto the pod URL. Default `/v1/flexserv/health`.
+    pub path: String,
+    /// Only this status is healthy (e.g. 204). Default None (any 2xx).
+    pub expected_status: Option<u16>,
+    /// Request timeout. Default 10 s.
+    pub timeout: Duration,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            path: "/v1/flexserv/health".to_string(),
+            expected_status: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// JSON body POSTed to `PodDeploymentOptions::webhook_url` after a lifecycle operation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeploymentEvent {
+    pub pod_id: String,
+    /// `create` or `terminate`.
+    pub operation: String,
+    /// Pod status reported by the operation, if any.
+    pub status: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Set when the operation failed.
+    pub error: Option<String>,
+}
+
+/// What create() waits for after the Pods API accepts the pod(s).
+#[derive(Clone, Debug, Default)]
+pub enum CreateMode {
+    /// Return as soon as the pods are submitted. `pod_url` is whatever the create response
+    /// carried, usually None. `wait_for_url_secs`, when set, still applies and turns this into
+    /// [CreateMode::WaitUrl].
+    #[default]
+    Submit,
+    /// Poll the pod for up to `secs` seconds until networking assigns a URL. Succeeds with
+    /// `pod_url: None` if none appears in time; the server behind the URL may not be up yet.
+    WaitUrl { secs: u64 },
+    /// Poll with `backoff` until the pod reports `AVAILABLE` or `RUNNING`, then warm up when
+    /// `warmup` is set, as [FlexServPodDeployment::wait_until_ready]. Fails with
+    /// PodCreationFailed on `ERROR`/`FAILED` and TapisTimeout when `backoff` runs out; the pod
+    /// and volume are left in place either way.
+    WaitReady { backoff: BackoffConfig },
+}
+
+/// Pod resources used when the corresponding [PodDeploymentOptions] field is None.
+const DEFAULT_CPU_REQUEST: i32 = 1000;
+const DEFAULT_CPU_LIMIT: i32 = 2000;
+const DEFAULT_MEM_REQUEST_MB: i32 = 4096;
+const DEFAULT_MEM_LIMIT_MB: i32 = 8192;
+
+/// Result of [PodDeploymentOptions::estimate_fit]. Headroom is available minus requested, so it
+/// is negative by the shortfall when a request does not fit.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FitResult {
+    /// Every request (CPU, memory, GPUs) fits the available capacity.
+    pub fits: bool,
+    /// In millicpus.
+    pub cpu_headroom: i64,
+    pub mem_headroom_mb: i64,
+    pub gpu_headroom: i64,
+    /// Limits that exceed the available capacity even though the requests fit: the pod can be
+    /// scheduled but may be throttled or OOM-killed when it grows toward its limits.
+    pub warnings: Vec<String>,
+}
+
+/// Result of [FlexServPodDeployment::health_summary]: pod status, URL assignment, and server
+/// health in one value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HealthSummary {
+    /// Raw TAPIS pod status, or None when monitor failed.
+    pub status: Option<String>,
+    pub pod_url: Option<String>,
+    /// [health_check] passed (`GET {pod_url}/v1/flexserv/health` returned 2xx, unless
+    /// `options.health_check` says otherwise). False when there is no URL to check.
+    pub server_healthy: bool,
+    /// Status is `AVAILABLE`/`RUNNING`, a URL is assigned, and the server is healthy.
+    pub healthy: bool,
+    /// First failure seen (monitor or health request), if any.
+    pub error: Option<String>,
+}
+
+/// When [FlexServPodDeployment::watchdog] gives up on a deployment and terminates it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutoTerminatePolicy {
+    /// Time between health checks ([FlexServPodDeployment::health_summary]). Default 1 minute.
+    pub check_interval: Duration,
+    /// Terminate once no check has been healthy for this long, counted from the watchdog's
+    /// start or the last healthy check. Default 30 minutes.
+    pub inactivity_timeout: Duration,
+}
+
+impl Default for AutoTerminatePolicy {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(60),
+            inactivity_timeout: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+/// What create() would do with the current configuration, from [FlexServPodDeployment::plan],
+/// for confirm-before-apply flows.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DeploymentPlan {
+    /// Pods to create, one per replica.
+    pub pod_ids: Vec<String>,
+    pub volume_id: String,
+    pub image: String,
+    pub backend: String,
+    pub model_id: String,
+    pub model_revision: Option<String>,
+    pub model_source: ModelSource,
+    /// Volume size to request. None when create() estimates it from Hugging Face metadata.
+    pub volume_size_mb: Option<i32>,
+    /// The volume is shared (`volume_id_override`) and reused if it exists.
+    pub shared_volume: bool,
+    /// Requested resources, defaults applied, as in [FlexServPodDeployment::render_new_pod].
+    pub cpu_request: i32,
+    pub cpu_limit: i32,
+    pub mem_request_mb: i32,
+    pub mem_limit_mb: i32,
+    pub gpus: i32,
+    /// Rough seconds until the server is ready ([crate::estimate_create_duration], taking the
+    /// volume size as the model size). None when the volume size is not known yet.
+    pub estimated_ready_secs: Option<u64>,
+    /// Resources create() deletes before creating anything (`pod <id>`, `volume <id>`), in
+    /// order. Empty unless `cleanup_before_create` is set.
+    pub deletes: Vec<String>,
+    /// Resources create() makes, in order (`volume <id>`, `pod <id>`).
+    pub creates: Vec<String>,
+}
+
+impl DeploymentPlan {
+    /// Whether create() would delete existing pods or volumes under these ids first.
+    pub fn is_destructive(&self) -> bool {
+        !self.deletes.is_empty()
+    }
+}
+
+/// Resource footprint and uptime of a pod, from [FlexServPodDeployment::cost_inputs].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CostInputs {
+    /// Requested CPU in millicores (defaults applied); requests are what the scheduler reserves.
+    pub cpu_millicores: i64,
+    /// Requested memory in MB (defaults applied).
+    pub mem_mb: i64,
+    pub gpus: i64,
+    /// Seconds since the pod instance started, or 0 when it is not running or TAPIS reports
+    /// no start time.
+    pub uptime_seconds: u64,
+}
+
+/// Hourly prices per resource for [estimate_cost].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostRates {
+    pub per_cpu_hour: f64,
+    pub per_gb_mem_hour: f64,
+    pub per_gpu_hour: f64,
+}
+
+/// Cost of running `inputs` for its uptime at `rates`: hours x (CPUs x `per_cpu_hour` +
+/// GB (1024 MB) x `per_gb_mem_hour` + GPUs x `per_gpu_hour`).
+pub fn estimate_cost(inputs: &CostInputs, rates: &CostRates) -> f64 {
+    let hours = inputs.uptime_seconds as f64 / 3600.0;
+    let cpus = inputs.cpu_millicores as f64 / 1000.0;
+    let mem_gb = inputs.mem_mb as f64 / 1024.0;
+    hours
+        * (cpus * rates.per_cpu_hour
+            + mem_gb * rates.per_gb_mem_hour
+            + inputs.gpus as f64 * rates.per_gpu_hour)
+}
+
+/// Where a pod deployment's model comes from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModelSource {
+    /// `server.default_model` on Hugging Face, expected on the volume under
+    /// `/app/models/<model_dir_name>`.
+    #[default]
+    HuggingFace,
+    /// A file or directory on a TAPIS files system. The Pods API can't mount files systems, so
+    /// the startup script downloads `path` (zipped) through the Files API and extracts it into
+    /// `/app/models/<model_dir_name>`. The download is skipped when that directory already
+    /// exists. Needs a backend command and `curl` and `python3` in the image.
+    ///
+    /// The deployment's TAPIS token is written to the volume for the download (not put in the
+    /// pod spec, and not when the model is already there) and removed right after the download
+    /// step, whether it ran, was skipped, or failed. It is a short-lived JWT: a pod that restarts
+    /// after it expires, before the model was downloaded, fails the download.
+    TapisFiles { system_id: String, path: String },
+}
+
+/// How the startup script downloads a Hugging Face model into the volume (see
+/// `PodDeploymentOptions::download_tool`). Every tool skips a directory that already exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DownloadTool {
+    /// `huggingface_hub.snapshot_download` run by the backend's Python interpreter (or `python3`
+    /// when the backend command is not Python), so it needs `huggingface_hub` there.
+    #[default]
+    PythonSnapshot,
+    /// `huggingface-cli download`, for images with the CLI on `PATH` but not the Python package.
+    HfCli,
+    /// `git clone` of `https://huggingface.co/<repo>` with git-lfs, for minimal images with
+    /// neither. Public repos only: `HF_TOKEN` is not used.
+    Git,
+}
+
+impl DownloadTool {
+    /// Shell step downloading `repo` (at `revision`, if any) into `dir` with `python` as the
+    /// interpreter for [DownloadTool::PythonSnapshot], retried per `retries`.
+    fn fetch_command(
+        self,
+        repo: &str,
+        revision: Option<&str>,
+        dir: &str,
+        python: &str,
+        retries: Option<DownloadRetries>,
+    ) -> String {
+        use crate::utils::shell_quote;
+        let dir = shell_quote(dir);
+        let fetch = match self {
+            DownloadTool::PythonSnapshot => format!(
+                "{} -c 'import sys; from huggingface_hub import snapshot_download; \
+                 snapshot_download(sys.argv[1], revision=sys.argv[2] or None, local_dir=sys.argv[3])' \
+                 {} {} {}",
+                shell_quote(python),
+                shell_quote(repo),
+                shell_quote(revision.unwrap_or("")),
+                dir
+            ),
+            DownloadTool::HfCli => format!(
+                "huggingface-cli download {}{} --local-dir {}",
+                shell_quote(repo),
+                revision
+                    .map(|r| format!(" --revision {}", shell_quote(r)))
+                    .unwrap_or_default(),
+                dir
+            ),
+            DownloadTool::Git => {
+                let url = shell_quote(&format!("https://huggingface.co/{}", repo));
+                match revision {
+                    Some(r) => format!(
+                        "{{ git lfs install && git clone {} {} && git -C {} checkout {}; }}",
+                        url,
+                        dir,
+                        dir,
+                        shell_quote(r)
+                    ),
+                    None => format!("{{ git lfs install && git clone {} {}; }}", url, dir),
+                }
+            }
+        };
+        // git clone refuses to write into the directory a failed attempt left behind.
+        let cleanup = (self == DownloadTool::Git).then(|| format!("rm -rf {}", dir));
+        download_step(&dir, &fetch, retries, cleanup.as_deref())
+    }
+}
+
+/// Suffix function for [IdStrategy::Custom].
+pub type IdSuffixFn = Arc<dyn Fn(&FlexServInstance, &PodDeploymentOptions) -> String + Send + Sync>;
+
+/// How pod and volume ids are derived (`PodDeploymentOptions::id_strategy`). The strategy
+/// yields the suffix; the ids are `p{id_prefix}{suffix}` and `v{id_prefix}{suffix}`, and
+/// create() rejects them unless they are valid TAPIS ids (1-[MAX_TAPIS_ID_LEN] lowercase
+/// alphanumeric characters).
+#[derive(Clone, Default)]
+pub enum IdStrategy {
+    /// `deployment_id` normalized to lowercase alphanumeric, else the config hash (one pod per
+    /// user+model). Too-long suffixes are shortened and end in a hash, so the ids always fit.
+    #[default]
+    Default,
+    /// Suffix returned by this function, used as is: no normalization or shortening.
+    Custom(IdSuffixFn),
+}
+
+impl IdStrategy {
+    pub fn custom(
+        f: impl Fn(&FlexServInstance, &PodDeploymentOptions) -> String + Send + Sync + 'static,
+    ) -> Self {
+        IdStrategy::Custom(Arc::new(f))
+    }
+}
+
+impl std::fmt::Debug for IdStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdStrategy::Default => f.write_str("Default"),
+            IdStrategy::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// How often the startup script retries a failed model download (see
+/// `PodDeploymentOptions::download_retries`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadRetries {
+    /// Attempts per download, including the first. 0 and 1 both mean no retry. Default 3.
+    pub attempts: u32,
+    /// Wait after the first failed attempt, doubled after each further failure. Default 10.
+    pub initial_delay_secs: u64,
+}
+
+impl Default for DownloadRetries {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_delay_secs: 10,
+        }
+    }
+}
+
+/// Startup-script step running `fetch` unless `dir` (already shell-quoted) exists. `cleanup`
+/// runs after a failed attempt, so a partial `dir` is not mistaken for a finished download.
+/// With more than one attempt in `retries`, `fetch` runs in a loop that sleeps between attempts
+/// and exits the script once the last attempt fails.
+fn download_step(
+    dir: &str,
+    fetch: &str,
+    retries: Option<DownloadRetries>,
+    cleanup: Option<&str>,
+) -> String {
+    let Some(retries) = retries.filter(|r| r.attempts > 1) else {
+        return match cleanup {
+            Some(cleanup) => format!("[ -d {} ] || {} || {{ {}; exit 1; }}", dir, fetch, cleanup),
+            None => format!("[ -d {} ] || {}", dir, fetch),
+        };
+    };
+    format!(
+        "[ -d {dir} ] || {{ attempt=1; delay={delay}; until {fetch}; do \
+         if [ \"$attempt\" -ge {n} ]; then {cleanup}echo \"Model download failed after {n} attempts\" >&2; exit 1; fi; \
+         echo \"Model download failed (attempt $attempt of {n}), retrying in ${{delay}}s\" >&2; \
+         {cleanup}sleep \"$delay\"; attempt=$((attempt + 1)); delay=$((delay * 2)); done; }}",
+        dir = dir,
+        fetch = fetch,
+        n = retries.attempts,
+        delay = retries.initial_delay_secs,
+        cleanup = cleanup.map(|c| format!("{}; ", c)).unwrap_or_default(),
+    )
+}
+
+/// Per-operation limits for [FlexServPodDeployment]'s [FlexServDeployment] methods, separate
+/// from any HTTP client timeout. An operation that runs longer is abandoned and fails with
+/// TapisTimeout; resources it already created are left in place (a later create() or
+/// terminate() cleans them up).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OperationTimeouts {
+    /// Default 30 minutes (covers cleanup, volume size estimation, and revision pinning).
+    pub create: Duration,
+    /// Default 2 minutes.
+    pub start: Duration,
+    /// Default 2 minutes.
+    pub stop: Duration,
+    /// Default 2 minutes.
+    pub terminate: Duration,
+    /// Default 30 seconds, so dashboards polling monitor() stay responsive.
+    pub monitor: Duration,
+}
+
+impl Default for OperationTimeouts {
+    fn default() -> Self {
+        Self {
+            create: Duration::from_secs(30 * 60),
+            start: Duration::from_secs(120),
+            stop: Duration::from_secs(120),
+            terminate: Duration::from_secs(120),
+            monitor: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Span around one [FlexServDeployment] operation (`tracing` feature).
+#[cfg(feature = "tracing")]
+type OperationSpan = tracing::Span;
+/// Placeholder for the operation span without the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+struct OperationSpan;
+
+/// Run `fut` inside `span`.
+#[cfg(feature = "tracing")]
+async fn in_span<T>(span: OperationSpan, fut: impl std::future::Future<Output = T>) -> T {
+    tracing::Instrument::instrument(fut, span).await
+}
+#[cfg(not(feature = "tracing"))]
+async fn in_span<T>(_span: OperationSpan, fut: impl std::future::Future<Output = T>) -> T {
+    fut.await
+}
+
+/// Run `fut`, failing with TapisTimeout if it takes longer than `limit`.
+async fn with_timeout<T>(
+    op: &str,
+    limit: Duration,
+    fut: impl std::future::Future<Output = Result<T, DeploymentError>>,
+) -> Result<T, DeploymentError> {
+    tokio::time::timeout(limit, fut).await.unwrap_or_else(|_| {
+        Err(DeploymentError::TapisTimeout(format!(
+            "{} did not finish within {:?}",
+            op, limit
+        )))
+    })
+}
+
+impl PodDeploymentOptions {
+    /// Check these options against `backend`: GPUs requested for a backend that can't use them,
+    /// or none for one that needs them (vLLM, SGLang, TRT-LLM). Problems are logged and returned
+    /// as warnings, or fail with [DeploymentError::InvalidConfiguration] when
+    /// `strict_validation` is set. GPU hints (`gpu_memory_mb`, `mig_profile`) without a GPU,
+    /// a non-positive `gpu_memory_mb`, or a malformed `mig_profile` always fail. Called by create().
+    pub fn validate(&self, backend: &Backend) -> Result<Vec<String>, DeploymentError> {
+        let gpus = self.gpus.unwrap_or(0);
+        self.validate_gpu_hints(gpus)?;
+        self.validate_restart_policy()?;
+        let mut warnings = Vec::new();
+        if gpus > 0 && !backend.supports_gpu() {
+            warnings.push(format!(
+                "gpus = {} requested but backend {} cannot use GPUs",
+                gpus,
+                backend.as_str()
+            ));
+        }
+        if gpus <= 0 && backend.requires_gpu() {
+            warnings.push(format!(
+                "backend {} needs a GPU but gpus = {}",
+                backend.as_str(),
+                gpus
+            ));
+        }
+        if self.strict_validation && !warnings.is_empty() {
+            return Err(DeploymentError::InvalidConfiguration(warnings.join("; ")));
+        }
+        for warning in &warnings {
+            log::warn!("{}", warning);
+        }
+        Ok(warnings)
+    }
+
+    fn validate_restart_policy(&self) -> Result<(), DeploymentError> {
+        match self.restart_policy {
+            Some(ref policy) if !RESTART_POLICIES.contains(&policy.as_str()) => {
+                Err(DeploymentError::InvalidConfiguration(format!(
+                    "restart_policy must be one of {}: {:?}",
+                    RESTART_POLICIES.join(", "),
+                    policy
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_gpu_hints(&self, gpus: i32) -> Result<(), DeploymentError> {
+        let invalid = |msg: String| Err(DeploymentError::InvalidConfiguration(msg));
+        if (self.gpu_memory_mb.is_some() || self.mig_profile.is_some()) && gpus < 1 {
+            return invalid(format!(
+                "gpu_memory_mb and mig_profile require gpus >= 1 (gpus = {})",
+                gpus
+            ));
+        }
+        if let Some(mb) = self.gpu_memory_mb.filter(|mb| *mb <= 0) {
+            return invalid(format!("gpu_memory_mb must be positive: {}", mb));
+        }
+        if let Some(ref profile) = self.mig_profile {
+            // <compute>g.<memory>gb, e.g. 1g.10gb or 3g.40gb.
+            let valid = profile
+                .strip_suffix("gb")
+                .and_then(|p| p.split_once("g."))
+                .is_some_and(|(c, m)| {
+                    !c.is_empty()
+                        && !m.is_empty()
+                        && c.chars().all(|ch| ch.is_ascii_digit())
+                        && m.chars().all(|ch| ch.is_ascii_digit())
+                });
+            if !valid {
+                return invalid(format!("mig_profile must look like 1g.10gb: {:?}", profile));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill the resources the caller left unset from `hints` (see
+    /// [Backend::default_resource_hints]): requests and GPUs as given, limits at twice the hint
+    /// but never below the request. Explicit values are kept. Called by
+    /// [FlexServPodDeployment::with_options] and
+    /// [FlexServPodDeployment::from_existing_with_options] with the server's backend.
+    pub fn merge_resource_hints(&mut self, hints: &ResourceHints) {
+        let cpu_request = *self.cpu_request.get_or_insert(hints.cpu);
+        self.cpu_limit
+            .get_or_insert((hints.cpu * 2).max(cpu_request));
+        let mem_request = *self.mem_request_mb.get_or_insert(hints.mem_mb);
+        self.mem_limit_mb
+            .get_or_insert((hints.mem_mb * 2).max(mem_request));
+        self.gpus.get_or_insert(hints.gpus);
+    }
+
+    /// Set `cpu_request` from a CPU quantity like `"2"`, `"0.5"`, or `"500m"`.
+    pub fn with_cpu_request(mut self, quantity: &str) -> Result<Self, ValidationError> {
+        self.cpu_request = Some(crate::utils::parse_cpu_millis(quantity)?);
+        Ok(self)
+    }
+
+    /// Set `cpu_limit` from a CPU quantity like `"2"`, `"0.5"`, or `"500m"`.
+    pub fn with_cpu_limit(mut self, quantity: &str) -> Result<Self, ValidationError> {
+        self.cpu_limit = Some(crate::utils::parse_cpu_millis(quantity)?);
+        Ok(self)
+    }
+
+    /// Set `mem_request_mb` from a memory quantity like `"512Mi"` or `"8Gi"` (unit required).
+    pub fn with_mem_request(mut self, quantity: &str) -> Result<Self, ValidationError> {
+        self.mem_request_mb = Some(crate::utils::parse_memory_mb(quantity)?);
+        Ok(self)
+    }
+
+    /// Set `mem_limit_mb` from a memory quantity like `"512Mi"` or `"8Gi"` (unit required).
+    pub fn with_mem_limit(mut self, quantity: &str) -> Result<Self, ValidationError> {
+        self.mem_limit_mb = Some(crate::utils::parse_memory_mb(quantity)?);
+        Ok(self)
+    }
+
+    /// Pre-flight check of the requested resources (defaults applied) against the capacity of
+    /// the target: `available_cpu` in millicpus, `available_mem_mb` in MB. Makes no TAPIS calls.
+    pub fn estimate_fit(
+        &self,
+        available_cpu: i32,
+        available_mem_mb: i32,
+        available_gpus: i32,
+    ) -> FitResult {
+        let cpu_request = self.cpu_request.unwrap_or(DEFAULT_CPU_REQUEST);
+        let cpu_limit = self.cpu_limit.unwrap_or(DEFAULT_CPU_LIMIT);
+        let mem_request = self.mem_request_mb.unwrap_or(DEFAULT_MEM_REQUEST_MB);
+        let mem_limit = self.mem_limit_mb.unwrap_or(DEFAULT_MEM_LIMIT_MB);
+        let gpus = self.gpus.unwrap_or(0);
+
+        let cpu_headroom = available_cpu as i64 - cpu_request as i64;
+        let mem_headroom_mb = available_mem_mb as i64 - mem_request as i64;
+        let gpu_headroom = available_gpus as i64 - gpus as i64;
+        let fits = cpu_headroom >= 0 && mem_headroom_mb >= 0 && gpu_headroom >= 0;
+
+        let mut warnings = Vec::new();
+        if fits {
+            if cpu_limit > available_cpu {
+                warnings.push(format!(
+                    "cpu_limit {} exceeds available {} millicpus",
+                    cpu_limit, available_cpu
+                ));
+            }
+            if mem_limit > available_mem_mb {
+                warnings.push(format!(
+                    "mem_limit_mb {} exceeds available {} MB",
+                    mem_limit, available_mem_mb
+                ));
+            }
+        }
+        FitResult {
+            fits,
+            cpu_headroom,
+            mem_headroom_mb,
+            gpu_headroom,
+            warnings,
+        }
+    }
+}
+
+/// Whether the tenant has at least `required` GPUs free. The Pods API exposes no GPU
+/// capacity, so this is a documented no-op returning `Ok(true)`; GPU requests that cannot be
+/// scheduled surface as pod errors instead. Kept so callers need not change if TAPIS adds one.
+pub async fn check_gpu_availability(
+    _tenant_url: &str,
+    _tapis_token: &str,
+    _required: i32,
+) -> Result<bool, DeploymentError> {
+    Ok(true)
+}
+
+/// Check a running FlexServ server with `GET {pod_url}{config.path}`, authenticating with
+/// `flexserv_token`. A status other than `config.expected_status` (any 2xx by default) or a
+/// transport failure is InferenceFailed.
+pub async fn health_check(
+    pod_url: &str,
+    flexserv_token: &str,
+    config: &HealthCheckConfig,
+) -> Result<(), DeploymentError> {
+    check_health(&PodInferenceClient::new(pod_url, flexserv_token), config).await
+}
+
+/// [health_check] through `client`, which carries the URL, token, and TLS/proxy settings.
+async fn check_health(
+    client: &PodInferenceClient,
+    config: &HealthCheckConfig,
+) -> Result<(), DeploymentError> {
+    let endpoint = client.endpoint(&config.path);
+    let resp = client
+        .request(reqwest::Method::GET, &config.path)
+        .timeout(config.timeout)
+        .send()
+        .await
+        .map_err(|e| DeploymentError::InferenceFailed(format!("{}: {}", endpoint, e)))?;
+    let status = resp.status();
+    let healthy = match config.expected_status {
+        Some(expected) => status.as_u16() == expected,
+        None => status.is_success(),
+    };
+    if healthy {
+        return Ok(());
+    }
+    let text = resp.text().await.unwrap_or_default();
+    Err(DeploymentError::InferenceFailed(format!(
+        "health returned {}: {}",
+        status, text
+    )))
+}
+
+/// Per-request timeout for [FlexServPodDeployment::warmup] calls.
+const WARMUP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Deployments [monitor_all] polls at once.
+pub const MONITOR_ALL_CONCURRENCY: usize = 8;
+
+/// [FlexServPodDeployment::monitor_pod_only] for each deployment, at most
+/// [MONITOR_ALL_CONCURRENCY] at a time. Results are in the order of `deployments`.
+pub async fn monitor_all(
+    deployments: &[FlexServPodDeployment],
+) -> Vec<Result<DeploymentResult, DeploymentError>> {
+    use futures::stream::StreamExt;
+    futures::stream::iter(deployments.iter().map(|d| d.monitor_pod_only()))
+        .buffered(MONITOR_ALL_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// Model ids a running FlexServ server reports at `GET {pod_url}/v1/models` (the `id` of each
+/// `data` entry), authenticating with `flexserv_token`. Failures are InferenceFailed.
+pub async fn served_models(
+    pod_url: &str,
+    flexserv_token: &str,
+) -> Result<Vec<String>, DeploymentError> {
+    list_served_models(&PodInferenceClient::new(pod_url, flexserv_token)).await
+}
+
+/// [served_models] through `client`, which carries the URL, token, and TLS/proxy settings.
+async fn list_served_models(client: &PodInferenceClient) -> Result<Vec<String>, DeploymentError> {
+    let endpoint = client.endpoint("/v1/models");
+    let resp = client
+        .request(reqwest::Method::GET, "/v1/models")
+        .timeout(tokio::time::Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| DeploymentError::InferenceFailed(format!("{}: {}", endpoint, e)))?;
+    let status = resp.status();
+    let text = resp.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(DeploymentError::InferenceFailed(format!(
+            "models returned {}: {}",
+            status, text
+        )));
+    }
+    let body: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| DeploymentError::InferenceFailed(format!("invalid models response: {}", e)))?;
+    Ok(body["data"]
+        .as_array()
+        .map(|models| {
+            models
+                .iter()
+                .filter_map(|m| m["id"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// True when a Pods API call failed with 409 Conflict, i.e. the volume or pod id is taken.
+fn is_already_exists<E>(err: &apis::Error<E>) -> bool {
+    matches!(err, apis::Error::ResponseError(resp) if resp.status == reqwest::StatusCode::CONFLICT)
+}
+
+/// True when a Pods API call failed with 404 Not Found, i.e. the volume or pod is gone.
+fn is_not_found<E>(err: &apis::Error<E>) -> bool {
+    matches!(err, apis::Error::ResponseError(resp) if resp.status == reqwest::StatusCode::NOT_FOUND)
+}
+
+/// Values accepted for `PodDeploymentOptions::server_log_level`.
+const SERVER_LOG_LEVELS: &[&str] = &["debug", "info", "warning", "error"];
+
+/// Values accepted for `PodDeploymentOptions::attn_implementation`.
+const ATTN_IMPLEMENTATIONS: &[&str] = &["eager", "sdpa", "flash_attention_2"];
+
+/// One changed field from [FlexServPodDeployment::diff]. `path` is a JSON pointer into the pod
+/// spec (e.g. `/resources/cpu_limit`); `old`/`new` are None when the field is absent on that side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpecDiff {
+    pub path: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
+/// Mount path of the model volume inside FlexServ pods; each model lives in
+/// `{FLEXSERV_MODEL_REPO}/<model_dir_name>`.
+pub const FLEXSERV_MODEL_REPO: &str = "/app/models";
+
+/// In-pod path of `model_id`, which is also the `model` value for inference requests
+/// (e.g. `openai-community/gpt2` -> `/app/models/openai-community_gpt2`).
+pub fn model_path(model_id: &str) -> String {
+    format!("{}/{}", FLEXSERV_MODEL_REPO, model_dir_name(model_id))
+}
+
+/// Directory name of `model_id` under [FLEXSERV_MODEL_REPO]: `/` replaced by `_`.
+fn model_dir_name(model_id: &str) -> String {
+    model_id.replace('/', "_")
+}
+
+/// Token a FlexServ server for `model_id` accepts (`Authorization: Bearer` /
+/// `X-FlexServ-Secret`): `secret` followed by the model directory name, e.g.
+/// `("s3cr3t", "org/Model")` -> `s3cr3torg_Model`.
+pub fn flexserv_token(secret: &str, model_id: &str) -> String {
+    flexserv_token_for_dir(secret, &model_dir_name(model_id))
+}
+
+/// [flexserv_token] for a model directory name that is already known (e.g. `model_subdir`).
+fn flexserv_token_for_dir(secret: &str, model_dir: &str) -> String {
+    format!("{}{}", secret, model_dir)
+}
+
+/// Longest pod/volume id [FlexServPodDeployment] derives (including any replica index). TAPIS
+/// builds Kubernetes names from the pod id, which must stay within DNS label limits.
+pub const MAX_TAPIS_ID_LEN: usize = 48;
+
+/// A normalized `deployment_id` shorter than this is accepted but logged as collision-prone.
+const MIN_DEPLOYMENT_ID_LEN: usize = 4;
+
+/// Accepted values of `PodDeploymentOptions::restart_policy`.
+pub const RESTART_POLICIES: &[&str] = &["Always", "OnFailure", "Never"];
+
+/// Name of the token file written to the model volume with `token_via_file`.
+const TOKEN_FILE_NAME: &str = ".flexserv_token";
+
+/// Name of the TAPIS token file written to the model volume for a [ModelSource::TapisFiles]
+/// download.
+const TAPIS_TOKEN_FILE_NAME: &str = ".tapis_token";
+
+/// Env vars whose values are never reported by [FlexServPodDeployment::diff].
+/// Names containing [SECRET_ENV_MARKERS] are treated the same way.
+const SECRET_ENV_VARS: &[&str] = &[
+    "FLEXSERV_SECRET",
+    "FLEXSERV_TOKEN",
+    "HF_TOKEN",
+    "TAPIS_TOKEN",
+];
+
+/// Substrings (uppercase) marking any other env var name as secret, e.g. from `env_file`.
+const SECRET_ENV_MARKERS: &[&str] = &["TOKEN", "SECRET", "PASSWORD", "KEY"];
+
+/// Whether `name`'s value must not be logged or reported.
+fn is_secret_env_var(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SECRET_ENV_VARS.contains(&name) || SECRET_ENV_MARKERS.iter().any(|m| upper.contains(m))
 }
 
 /// Pod-based deployment
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct FlexServPodDeployment {
     pub server: FlexServInstance,
     /// Tapis JWT token used to authenticate against the Pods API
@@ -46,23 +959,36 @@ pub struct FlexServPodDeployment {
     pub pod_id: String,
     pub volume_info: Option<String>,
     pub pod_info: Option<String>,
+    /// Commit sha resolved by create() when `options.pin_revision` is set.
+    pub resolved_revision: Option<String>,
+    /// Client built on first TAPIS call when `options.http_client` is None.
+    client: OnceLock<reqwest::Client>,
+    /// Exchanges recorded with `options.capture_trace`, drained by take_trace().
+    trace: TraceBuffer,
+    /// Ids were given to [Self::with_explicit_ids]; [Self::set_model] keeps them.
+    explicit_ids: bool,
+    /// Replica ids given to [Self::with_replica_ids], used by [Self::pod_ids] as is.
+    replica_ids: Option<Vec<String>>,
 }
 
 impl FlexServPodDeployment {
-    /// Create a new pod deployment with default options (volume 10 GB, image tapis/flexserv:1.0, 2 CPU / 8 GB RAM).
+    /// Create a new pod deployment with default options (volume 10 GB, default image, 2 CPU / 8 GB RAM).
     /// Secrets fall back to env: FLEXSERV_SECRET, HF_TOKEN.
     pub fn new(server: FlexServInstance, tapis_token: String) -> Self {
         Self::with_options(server, tapis_token, PodDeploymentOptions::default())
     }
 
     /// Create a new pod deployment with explicit options (volume size, image, CPU/memory, secrets, deployment_id).
+    /// Resources left unset in `options` come from the backend's
+    /// [Backend::default_resource_hints] (e.g. one GPU for vLLM).
     /// When `options.deployment_id` is set (e.g. UUID from MLHub), pod_id and volume_id are derived from it
     /// so multiple pods for the same model can coexist. Otherwise they are derived from server config (one pod per user+model).
     pub fn with_options(
         server: FlexServInstance,
         tapis_token: String,
-        options: PodDeploymentOptions,
+        mut options: PodDeploymentOptions,
     ) -> Self {
+        options.merge_resource_hints(&server.backend.default_resource_hints());
         let (pod_id, volume_id) = Self::ids_from_options(&server, &options);
         Self {
             server,
@@ -72,26 +998,289 @@ impl FlexServPodDeployment {
             pod_id,
             volume_info: None,
             pod_info: None,
+            resolved_revision: None,
+            client: OnceLock::new(),
+            trace: TraceBuffer::default(),
+            explicit_ids: false,
+            replica_ids: None,
         }
     }
 
     /// Derive pod_id and volume_id from options.deployment_id (if set) or from server deployment_hash.
-    /// deployment_id is normalized to lowercase alphanumeric (e.g. UUID with dashes stripped).
+    /// deployment_id and id_prefix are normalized to lowercase alphanumeric (e.g. UUID with dashes stripped).
+    /// A deployment_id with no alphanumerics falls back to the hash. Ids are kept within
+    /// [MAX_TAPIS_ID_LEN], leaving room for the replica index [Self::pod_ids] appends: an
+    /// id_prefix is cut to half of it, and a longer suffix is cut and ends in 8 hex chars of its
+    /// SHA-256 so distinct long ids stay distinct.
     fn ids_from_options(
         server: &FlexServInstance,
         options: &PodDeploymentOptions,
     ) -> (String, String) {
+        let mut prefix = options
+            .id_prefix
+            .as_deref()
+            .map(crate::utils::normalize_to_lowercase_alphanumeric)
+            .unwrap_or_default();
+        prefix.truncate(MAX_TAPIS_ID_LEN / 2);
+        let suffix = match options.id_strategy {
+            IdStrategy::Default => Self::default_id_suffix(
+                server,
+                options,
+                prefix.len() + Self::replica_index_len(options),
+            ),
+            IdStrategy::Custom(ref f) => f(server, options),
+        };
+        let volume_id = match options.volume_id_override {
+            Some(ref id) => id.clone(),
+            None => format!("v{}{}", prefix, suffix),
+        };
+        (format!("p{}{}", prefix, suffix), volume_id)
+    }
+
+    /// [IdStrategy::Default] suffix, shortened to fit next to `reserved_len` other characters
+    /// (the id_prefix and the replica index).
+    fn default_id_suffix(
+        server: &FlexServInstance,
+        options: &PodDeploymentOptions,
+        reserved_len: usize,
+    ) -> String {
         let suffix = if let Some(ref id) = options.deployment_id {
             let normalized = crate::utils::normalize_to_lowercase_alphanumeric(id);
             if normalized.is_empty() {
+                log::warn!(
+                    "deployment_id {:?} has no alphanumeric characters; using the config hash",
+                    id
+                );
                 server.deployment_hash().to_lowercase()
             } else {
+                if normalized.len() < MIN_DEPLOYMENT_ID_LEN {
+                    log::warn!(
+                        "deployment_id {:?} is shorter than {} characters and may collide",
+                        id,
+                        MIN_DEPLOYMENT_ID_LEN
+                    );
+                }
                 normalized
             }
         } else {
             server.deployment_hash().to_lowercase()
         };
-        (format!("p{}", suffix), format!("v{}", suffix))
+        // 1 for the `p`/`v` marker.
+        let budget = MAX_TAPIS_ID_LEN - 1 - reserved_len;
+        if suffix.len() > budget {
+            use sha2::{Digest, Sha256};
+            let digest = Sha256::digest(suffix.as_bytes());
+            let hash: String = digest
+                .iter()
+                .take(4)
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            format!("{}{}", &suffix[..budget - hash.len()], hash)
+        } else {
+            suffix
+        }
+    }
+
+    /// Characters [Self::pod_ids] appends for the replica index: none for a single pod,
+    /// otherwise the base-36 width of the last index.
+    fn replica_index_len(options: &PodDeploymentOptions) -> usize {
+        match options.replicas {
+            Some(n) if n > 1 => crate::utils::to_base36(n - 1).len(),
+            _ => 0,
+        }
+    }
+
+    /// Delete any pods and volume left under this deployment's ids, which create() does first
+    /// when `options.cleanup_before_create` is set. Missing pods are fine; other pod errors fail
+    /// with stage [CreateStage::Cleanup]. The volume is kept with `reuse_volume_on_pod_failure` or
+    /// when shared, and errors deleting it are ignored. Waits a few seconds for the deletions to
+    /// settle. Honors `options.timeouts.terminate`.
+    pub async fn cleanup(&self) -> Result<(), DeploymentError> {
+        let limit = self.options.timeouts.terminate;
+        with_timeout("cleanup", limit, async {
+            let config = self.pods_config().await?;
+            self.cleanup_with(&config).await
+        })
+        .await
+    }
+
+    /// [Self::cleanup], then create() without a second cleanup, whatever
+    /// `options.cleanup_before_create` says.
+    pub async fn recreate(&mut self) -> Result<DeploymentResult, DeploymentError> {
+        self.cleanup().await?;
+        let cleanup = std::mem::replace(&mut self.options.cleanup_before_create, false);
+        let result = self.create().await;
+        self.options.cleanup_before_create = cleanup;
+        result
+    }
+
+    async fn cleanup_with(
+        &self,
+        config: &configuration::Configuration,
+    ) -> Result<(), DeploymentError> {
+        // Delete pods first, then volume (volume deletion may fail if a pod still exists, so its
+        // errors are ignored).
+        for pod_id in &self.pod_ids() {
+            match pods_api::delete_pod(config, pod_id).await {
+                Err(ref e) if is_not_found(e) => {}
+                Err(e) => return Err(Self::map_pods_error(e).at_stage(CreateStage::Cleanup)),
+                Ok(_) => {}
+            }
+        }
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        if !self.keeps_volume() {
+            let _ = volumes_api::delete_volume(config, &self.volume_id).await;
+        }
+
+        // Wait for deletions to complete (volumes can take a moment)
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        Ok(())
+    }
+
+    /// Catch a gated Hugging Face model requested without an HF token before its download fails
+    /// in the pod. With `options.require_hf_token_for_gated`, asks the Hub at `endpoint` (falling
+    /// back to the built-in list if that fails) and errors; otherwise warns for listed models.
+    async fn check_gated_model_token(&self, endpoint: &str) -> Result<(), DeploymentError> {
+        if self.options.model_source != ModelSource::HuggingFace {
+            return Ok(());
+        }
+        let has_token = self
+            .server
+            .hf_token
+            .clone()
+            .or_else(|| std::env::var("HF_TOKEN").ok())
+            .is_some_and(|t| !t.trim().is_empty());
+        if has_token {
+            return Ok(());
+        }
+        let model = &self.server.default_model;
+        if !self.options.require_hf_token_for_gated {
+            if crate::server::is_known_gated_model(model) {
+                log::warn!(
+                    "{} is a gated Hugging Face model but no HF token is set; its download will likely fail",
+                    model
+                );
+            }
+            return Ok(());
+        }
+        let gated =
+            crate::server::model_is_gated(endpoint, model, self.model_revision().as_deref())
+                .await
+                .unwrap_or_else(|| crate::server::is_known_gated_model(model));
+        if gated {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "{} is a gated Hugging Face model; set hf_token (or HF_TOKEN) with access to it",
+                model
+            )));
+        }
+        Ok(())
+    }
+
+    /// Look up the model's size on the Hub at `endpoint`, once per create, at the resolved
+    /// revision. `None` when neither the size guard nor volume sizing needs it.
+    async fn model_size_for_create(&self, endpoint: &str) -> Option<u64> {
+        if self.options.model_source != ModelSource::HuggingFace {
+            return None;
+        }
+        if self.options.max_model_size_mb.is_none() && self.options.volume_size_mb.is_some() {
+            return None;
+        }
+        crate::server::model_size_mb(
+            endpoint,
+            &self.server.default_model,
+            self.model_revision().as_deref(),
+            self.server.hf_token.as_deref(),
+        )
+        .await
+    }
+
+    /// With `options.max_model_size_mb`, fail when the looked-up `size_mb` exceeds that limit
+    /// or an explicit `volume_size_mb`.
+    fn check_model_size(&self, size_mb: Option<u64>) -> Result<(), DeploymentError> {
+        let Some(max_mb) = self.options.max_model_size_mb else {
+            return Ok(());
+        };
+        if self.options.model_source != ModelSource::HuggingFace {
+            return Ok(());
+        }
+        let model = &self.server.default_model;
+        let Some(size_mb) = size_mb else {
+            log::warn!(
+                "Size of {} is unavailable, skipping the max_model_size_mb check",
+                model
+            );
+            return Ok(());
+        };
+        if size_mb > max_mb.max(0) as u64 {
+            return Err(DeploymentError::PodCreationFailed(format!(
+                "model {} is {} MB, over max_model_size_mb {} MB",
+                model, size_mb, max_mb
+            )));
+        }
+        match self.options.volume_size_mb {
+            Some(volume_mb) if size_mb > volume_mb.max(0) as u64 => {
+                Err(DeploymentError::PodCreationFailed(format!(
+                    "model {} is {} MB, larger than the {} MB volume",
+                    model, size_mb, volume_mb
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject pod/volume ids that are not legal TAPIS ids, e.g. from an [IdStrategy::Custom].
+    /// Every replica id from [Self::pod_ids] is checked, so none fails after others exist.
+    fn validate_ids(&self) -> Result<(), DeploymentError> {
+        let valid = |id: &str| {
+            !id.is_empty()
+                && id.len() <= MAX_TAPIS_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        };
+        let pod_ids = self.pod_ids();
+        let ids = pod_ids
+            .iter()
+            .map(|id| ("pod", id))
+            .chain([("volume", &self.volume_id)]);
+        for (kind, id) in ids {
+            if !valid(id) {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "{} id must be 1-{} lowercase alphanumeric characters: {:?}",
+                    kind, MAX_TAPIS_ID_LEN, id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The volume comes from `options.volume_id_override` and may be used by other pods.
+    fn shares_volume(&self) -> bool {
+        self.options.volume_id_override.is_some()
+    }
+
+    /// create() must not delete an existing volume: `reuse_volume_on_pod_failure`, or shared.
+    fn keeps_volume(&self) -> bool {
+        self.options.reuse_volume_on_pod_failure || self.shares_volume()
+    }
+
+    /// `options.volume_id_override` must be a valid TAPIS id.
+    fn validate_volume_id_override(&self) -> Result<(), DeploymentError> {
+        match self.options.volume_id_override {
+            Some(ref id)
+                if id.is_empty()
+                    || id.len() > MAX_TAPIS_ID_LEN
+                    || !id
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()) =>
+            {
+                Err(DeploymentError::InvalidConfiguration(format!(
+                    "volume_id_override must be 1-{} lowercase alphanumeric characters: {:?}",
+                    MAX_TAPIS_ID_LEN, id
+                )))
+            }
+            _ => Ok(()),
+        }
     }
 
     /// Create a deployment from [TapisConfig], [ModelConfig], backend, and options (no validation).
@@ -102,6 +1291,10 @@ impl FlexServPodDeployment {
         options: PodDeploymentOptions,
     ) -> Self {
         let server = FlexServInstance::from_configs(&tapis, &model, backend);
+        let mut options = options;
+        if options.token_provider.is_none() {
+            options.token_provider = tapis.token_provider;
+        }
         Self::with_options(server, tapis.tapis_token, options)
     }
 
@@ -127,629 +1320,6137 @@ impl FlexServPodDeployment {
     }
 
     /// Create a deployment from existing pod_id and volume_id (for start/stop/terminate/monitor).
+    /// The supplied ids are used as-is and never re-derived from the server config, so a pod
+    /// created with a `deployment_id` is still reached when reattaching without one.
     pub fn from_existing(
         server: FlexServInstance,
         tapis_token: String,
         pod_id: String,
         volume_id: String,
     ) -> Self {
+        Self::from_existing_with_options(
+            server,
+            tapis_token,
+            pod_id,
+            volume_id,
+            PodDeploymentOptions::default(),
+        )
+    }
+
+    /// [Self::from_existing] with explicit options (e.g. the `deployment_id` the pod was created
+    /// with). The supplied ids always win; when they differ from the ids `options` and `server`
+    /// would derive, a warning is logged since operations after a later [Self::set_model] would
+    /// target the derived ids instead. Unset resources are filled from the backend's hints, as
+    /// in [Self::with_options].
+    pub fn from_existing_with_options(
+        server: FlexServInstance,
+        tapis_token: String,
+        pod_id: String,
+        volume_id: String,
+        mut options: PodDeploymentOptions,
+    ) -> Self {
+        options.merge_resource_hints(&server.backend.default_resource_hints());
+        let (derived_pod, derived_volume) = Self::ids_from_options(&server, &options);
+        if derived_pod != pod_id || derived_volume != volume_id {
+            log::warn!(
+                "Supplied ids {}/{} differ from the ids derived from config ({}/{}); using the supplied ids",
+                pod_id,
+                volume_id,
+                derived_pod,
+                derived_volume
+            );
+        }
         Self {
             server,
             tapis_token,
-            options: PodDeploymentOptions::default(),
+            options,
             volume_id,
             pod_id,
             volume_info: None,
             pod_info: None,
+            resolved_revision: None,
+            client: OnceLock::new(),
+            trace: TraceBuffer::default(),
+            explicit_ids: false,
+            replica_ids: None,
         }
     }
 
-    /// Build Pods API configuration (base URL + reqwest client with X-Tapis-Token).
-    /// Base must be the v3 API root (e.g. https://tacc.tapis.io/v3).
-    fn pods_config(&self) -> Result<configuration::Configuration, DeploymentError> {
-        let base = self.server.tenant_url.trim_end_matches('/');
-        let api_base = format!("{}/v3", base);
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            "X-Tapis-Token",
-            HeaderValue::from_str(&self.tapis_token)
-                .map_err(|e| DeploymentError::TapisAuthFailed(e.to_string()))?,
-        );
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()
-            .map_err(|e| DeploymentError::TapisAuthFailed(e.to_string()))?;
-        let mut config = configuration::Configuration::default();
-        config.base_path = api_base;
-        config.client = reqwest_middleware::ClientBuilder::new(client).build();
-        Ok(config)
-    }
-
-    /// Extract pod URL from API response (networking.default.url).
-    fn _pod_url_from_result(result: &tapis_sdk::pods::models::PodResponseModel) -> Option<String> {
-        result
-            .networking
-            .as_ref()
-            .and_then(|n| n.get("default"))
-            .and_then(|net| net.url.clone())
+    /// Use exactly `pod_ids` for every per-pod call (e.g. the ids a registry record kept)
+    /// instead of deriving them from `pod_id` and `options.replicas`. An empty list is ignored;
+    /// a later [Self::set_model] drops them unless the ids are explicit.
+    pub fn with_replica_ids(mut self, pod_ids: Vec<String>) -> Self {
+        self.replica_ids = Some(pod_ids).filter(|ids| !ids.is_empty());
+        self
     }
 
-    /// Map a tapis-pods error into our DeploymentError, based on HTTP status / network.
-    fn map_pods_error<E: std::fmt::Debug>(err: apis::Error<E>) -> DeploymentError {
-        match err {
-            apis::Error::Reqwest(e) => {
-                if e.is_timeout() {
-                    DeploymentError::TapisTimeout(e.to_string())
-                } else if e.is_connect() {
-                    DeploymentError::TapisAPIUnreachable(e.to_string())
-                } else {
-                    DeploymentError::UnknownError(e.to_string())
-                }
-            }
-            apis::Error::ReqwestMiddleware(e) => DeploymentError::UnknownError(e.to_string()),
-            apis::Error::Serde(e) => DeploymentError::UnknownError(e.to_string()),
-            apis::Error::Io(e) => DeploymentError::UnknownError(e.to_string()),
-            apis::Error::ResponseError(resp) => {
-                let code = resp.status.as_u16();
-                if code == 401 || code == 403 {
-                    DeploymentError::TapisAuthFailed(resp.content)
-                } else if code == 400 {
-                    DeploymentError::TapisBadRequest(resp.content)
-                } else if (500..600).contains(&code) {
-                    DeploymentError::TapisInternalServerError(resp.content)
-                } else {
-                    DeploymentError::UnknownError(resp.content)
-                }
+    /// Create a deployment whose pod and volume ids are exactly `pod_id` and `volume_id`, for
+    /// ids made by other tooling. Unlike [Self::from_existing] this is meant for create() too,
+    /// and the ids are never re-derived (not from the config hash, `deployment_id`,
+    /// `id_strategy`, or a later [Self::set_model]). Fails with InvalidConfiguration unless both
+    /// are legal TAPIS ids (1-[MAX_TAPIS_ID_LEN] lowercase alphanumerics), or when
+    /// `options.volume_id_override` names a different volume.
+    pub fn with_explicit_ids(
+        server: FlexServInstance,
+        tapis_token: String,
+        pod_id: String,
+        volume_id: String,
+        options: PodDeploymentOptions,
+    ) -> Result<Self, DeploymentError> {
+        if let Some(ref shared) = options.volume_id_override {
+            if *shared != volume_id {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "volume_id_override {:?} conflicts with explicit volume id {:?}",
+                    shared, volume_id
+                )));
             }
         }
+        let mut deployment = Self::with_options(server, tapis_token, options);
+        deployment.pod_id = pod_id;
+        deployment.volume_id = volume_id;
+        deployment.explicit_ids = true;
+        deployment.validate_ids()?;
+        Ok(deployment)
     }
-}
-
-impl FlexServDeployment for FlexServPodDeployment {
-    async fn create(&mut self) -> Result<DeploymentResult, DeploymentError> {
-        let config = self.pods_config()?;
-
-        // Clean up any existing pod/volume with these ids.
-        // Ignore errors (404 means they don't exist, which is fine).
-        // Delete pod first, then volume (volume deletion may fail if pod still exists).
-        let _ = pods_api::delete_pod(&config, &self.pod_id).await;
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-        let _ = volumes_api::delete_volume(&config, &self.volume_id).await;
 
-        // Wait for deletions to complete (volumes can take a moment)
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-
-        // --- Create volume ---
-        let volume_size_mb = self.options.volume_size_mb.unwrap_or(10 * 1024);
-        let volume_desc = format!(
-            "Volume for {}@{}",
-            self.server.tapis_user, self.server.default_model
-        );
-        let new_volume = models::NewVolume {
-            volume_id: self.volume_id.clone(),
-            description: Some(volume_desc),
-            size_limit: Some(volume_size_mb),
+    /// Poll [FlexServDeployment::monitor] with exponential backoff until the pod reports
+    /// `AVAILABLE` or `RUNNING`. Fails fast with PodCreationFailed on `ERROR`/`FAILED`, and with
+    /// TapisTimeout when `backoff` is exhausted. With `options.health_check`, then polls
+    /// [health_check] on the pod URL (with a fresh `backoff`) until it passes. With
+    /// `options.warmup`, then calls [Self::warmup] on the pod URL.
+    pub async fn wait_until_ready(
+        &self,
+        backoff: &BackoffConfig,
+    ) -> Result<DeploymentResult, DeploymentError> {
+        let status_of = |r: &DeploymentResult| match r {
+            DeploymentResult::PodResult { status, .. } => status.clone().unwrap_or_default(),
+            _ => String::new(),
         };
-
-        // Try to create volume. If it already exists, delete and retry once.
-        let volume_result = volumes_api::create_volume(&config, new_volume.clone()).await;
-
-        match volume_result {
-            Ok(_) => {}
-            Err(e) => {
-                // If volume already exists, try deleting and recreating
-                if let apis::Error::ResponseError(ref resp) = e {
-                    if resp.content.contains("already exists")
-                        || resp.content.contains("UniqueViolation")
-                    {
-                        log::warn!(
-                            "Volume {} already exists, deleting and retrying...",
-                            self.volume_id
-                        );
-                        let _ = volumes_api::delete_volume(&config, &self.volume_id).await;
-                        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                        volumes_api::create_volume(&config, new_volume)
-                            .await
-                            .map_err(Self::map_pods_error)?;
-                    } else {
-                        return Err(Self::map_pods_error(e));
+        let result = poll_with_backoff(
+            |_| self.monitor(),
+            |r| {
+                matches!(
+                    status_of(r).to_uppercase().as_str(),
+                    "AVAILABLE" | "RUNNING" | "ERROR" | "FAILED"
+                )
+            },
+            backoff,
+        )
+        .await
+        .map_err(|e| match e {
+            PollError::Operation(e) => e,
+            PollError::Exhausted { attempts, .. } => DeploymentError::TapisTimeout(format!(
+                "pod {} not ready after {} attempt(s)",
+                self.pod_id, attempts
+            )),
+        })?;
+        match status_of(&result).to_uppercase().as_str() {
+            "ERROR" | "FAILED" => Err(DeploymentError::PodCreationFailed(format!(
+                "pod {} reported status {}",
+                self.pod_id,
+                status_of(&result)
+            ))),
+            _ => {
+                if let Some(ref config) = self.options.health_check {
+                    self.wait_until_healthy(&result, config, backoff).await?;
+                }
+                if self.options.warmup {
+                    match result.pod_url() {
+                        Some(url) => self.warmup(url).await?,
+                        None => log::warn!("Pod {} has no URL yet, skipping warmup", self.pod_id),
                     }
-                } else {
-                    return Err(Self::map_pods_error(e));
                 }
+                Ok(result)
             }
         }
+    }
 
-        // Model dir in volume: single directory name (e.g. openai-community/gpt2 -> openai-community_gpt2).
-        let model_dir_name = self.server.default_model.replace('/', "_");
-        // --- Create pod ---
-        let image = self
-            .options
-            .image
-            .clone()
-            .unwrap_or_else(|| "tapis/flexserv:1.0".to_string());
-
-        // volume_mounts: key = mount path, value = VolumeMountsValue (type, source_id, sub_path).
-        const MODEL_REPO_PATH: &str = "/app/models";
-        let mut volume_mounts = std::collections::HashMap::new();
-        let mut mount =
-            models::VolumeMountsValue::new(models::volume_mounts_value::Type::Tapisvolume);
-        mount.source_id = Some(Some(self.volume_id.clone()));
-        mount.sub_path = Some(String::new());
-        volume_mounts.insert(MODEL_REPO_PATH.to_string(), mount);
-        let flexserv_secret = self
-            .options
-            .flexserv_secret
-            .clone()
-            .unwrap_or_else(|| std::env::var("FLEXSERV_SECRET").unwrap_or_default());
-        let flexserv_token = format!("{}{}", flexserv_secret, model_dir_name);
-
-        let hf_token = self
-            .server
-            .hf_token
-            .clone()
-            .or_else(|| std::env::var("HF_TOKEN").ok());
-
-        // Default startup command + default args + user extra args (from server.backend).
-        let pod_params = self
-            .server
-            .backend
-            .parameter_set_builder()
-            .build_params_for_pod(&self.server);
-
-        let model_path = format!("{}/{}", MODEL_REPO_PATH, model_dir_name);
-        let mut arguments = pod_params.arguments.unwrap_or_default();
-        arguments.insert(0, model_path);
-        arguments.push("--flexserv-token".to_string());
-        arguments.push(flexserv_token.clone());
-
-        let mut env_vars: std::collections::HashMap<String, serde_json::Value> =
-            pod_params.environment_variables.unwrap_or_default();
-        env_vars.insert("MODEL_REPO".to_string(), serde_json::json!(MODEL_REPO_PATH));
-        env_vars.insert("FLEXSERV_PORT".to_string(), serde_json::json!("8000"));
-        env_vars.insert("MODEL_NAME".to_string(), serde_json::json!(model_dir_name));
-        env_vars.insert(
-            "FLEXSERV_SECRET".to_string(),
-            serde_json::json!(flexserv_secret),
-        );
-        env_vars.insert(
-            "FLEXSERV_TOKEN".to_string(),
-            serde_json::json!(flexserv_token),
-        );
-        if let Some(ref t) = hf_token {
-            env_vars.insert("HF_TOKEN".to_string(), serde_json::json!(t));
+    /// Poll [health_check] with `config` on the URL of a ready pod `result` until it passes.
+    /// Without a URL yet, the pod is re-monitored each attempt. TapisTimeout when `backoff` is
+    /// exhausted.
+    async fn wait_until_healthy(
+        &self,
+        result: &DeploymentResult,
+        config: &HealthCheckConfig,
+        backoff: &BackoffConfig,
+    ) -> Result<(), DeploymentError> {
+        let known_url = result.pod_url().map(str::to_string);
+        let check = poll_with_backoff(
+            |_| {
+                let known_url = known_url.clone();
+                async move {
+                    let url = match known_url {
+                        Some(url) => Some(url),
+                        None => self.monitor().await?.pod_url().map(str::to_string),
+                    };
+                    Ok::<_, DeploymentError>(match url {
+                        Some(url) => check_health(&self.inference_client(&url)?, config).await,
+                        None => Err(DeploymentError::InferenceFailed(format!(
+                            "pod {} has no URL yet",
+                            self.pod_id
+                        ))),
+                    })
+                }
+            },
+            |r| r.is_ok(),
+            backoff,
+        )
+        .await;
+        match check {
+            Ok(_) => Ok(()),
+            Err(PollError::Operation(e)) => Err(e),
+            Err(PollError::Exhausted { attempts, .. }) => {
+                Err(DeploymentError::TapisTimeout(format!(
+                    "pod {} not healthy after {} attempt(s)",
+                    self.pod_id, attempts
+                )))
+            }
         }
+    }
 
-        let mut net = models::ModelsPodsNetworking::new();
-        net.protocol = Some("http".to_string());
-        net.port = Some(8000);
-        let mut networking = std::collections::HashMap::new();
-        networking.insert("default".to_string(), net);
-
-        let mut resources = models::ModelsPodsResources::new();
-        resources.cpu_request = Some(self.options.cpu_request.unwrap_or(1000));
-        resources.cpu_limit = Some(self.options.cpu_limit.unwrap_or(2000));
-        resources.mem_request = Some(self.options.mem_request_mb.unwrap_or(4096));
-        resources.mem_limit = Some(self.options.mem_limit_mb.unwrap_or(8192));
-        resources.gpus = Some(self.options.gpus.unwrap_or(0));
+    /// Cheap pre-flight check that TAPIS is reachable and accepts the token, before any
+    /// mutating call: one authenticated `list_volumes`, which must return a 2xx TAPIS envelope
+    /// with status `success`. Any other status (404 included), a body that is not a TAPIS
+    /// response, auth failures, timeouts, and unreachable servers map to the usual
+    /// [DeploymentError] variants.
+    pub async fn check_tapis_reachable(&self) -> Result<(), DeploymentError> {
+        let config = self.pods_config().await?;
+        let resp = volumes_api::list_volumes(&config)
+            .await
+            .map_err(Self::map_pods_error)?;
+        if resp.status != "success" {
+            return Err(DeploymentError::UnknownError(format!(
+                "unexpected TAPIS response status {:?}: {}",
+                resp.status, resp.message
+            )));
+        }
+        Ok(())
+    }
 
-        let mut new_pod = models::NewPod::new(self.pod_id.clone());
-        new_pod.image = Some(image);
-        new_pod.description = Some(format!(
-            "FlexServ pod for {}@{}",
-            self.server.tapis_user, self.server.default_model
-        ));
-        new_pod.command = pod_params.command.map(Some);
-        new_pod.arguments = Some(Some(arguments));
-        new_pod.environment_variables = Some(env_vars);
-        new_pod.status_requested = Some("ON".to_string());
-        new_pod.volume_mounts = Some(volume_mounts);
-        new_pod.time_to_stop_default = Some(-1);
-        new_pod.time_to_stop_instance = Some(Some(-1));
-        new_pod.networking = Some(networking);
-        new_pod.resources = Some(Box::new(resources));
+    /// Ids of every pod this deployment's token can see in its tenant (`GET /v3/pods`), e.g.
+    /// to check that a pod belongs to the caller.
+    pub async fn visible_pod_ids(&self) -> Result<Vec<String>, DeploymentError> {
+        let config = self.pods_config().await?;
+        let resp = pods_api::list_pods(&config)
+            .await
+            .map_err(Self::map_pods_error)?;
+        Ok(resp.result.into_iter().map(|p| p.pod_id).collect())
+    }
 
-        // Log the exact Pods create_pod request body for debugging.
-        if let Ok(body) = serde_json::to_string_pretty(&new_pod) {
-            log::info!("Pods create_pod request body:\n{}", body);
+    /// What [FlexServDeployment::terminate] would delete, without deleting anything: reads each
+    /// pod and the volume and reports which exist. Returns a `PodResult` whose `pod_info` starts
+    /// with `DRY RUN` and lists each resource as `exists` or `not found`; `status` is the
+    /// primary pod's current status. Other read errors are returned as is.
+    pub async fn terminate_dry_run(&self) -> Result<DeploymentResult, DeploymentError> {
+        let config = self.pods_config().await?;
+        let mut lines = vec!["DRY RUN: nothing deleted".to_string()];
+        let mut status = None;
+        for pod_id in self.pod_ids() {
+            match pods_api::get_pod(&config, &pod_id, None, None).await {
+                Ok(resp) => {
+                    if pod_id == self.pod_id {
+                        status = resp.result.status.clone();
+                    }
+                    lines.push(format!("would delete pod {}: exists", pod_id));
+                }
+                Err(ref e) if is_not_found(e) => {
+                    lines.push(format!("would delete pod {}: not found", pod_id))
+                }
+                Err(e) => return Err(Self::map_pods_error(e)),
+            }
         }
-
-        // Create pod. If this fails, clean up the volume we just created.
-        let pod_resp = match pods_api::create_pod(&config, new_pod).await {
-            Ok(resp) => resp,
-            Err(e) => {
-                log::error!(
-                    "Pod creation failed, cleaning up volume {}...",
-                    self.volume_id
-                );
-                let _ = volumes_api::delete_volume(&config, &self.volume_id).await;
-                return Err(Self::map_pods_error(e));
+        if self.shares_volume() && !self.options.delete_shared_volume {
+            lines.push(format!("would keep shared volume {}", self.volume_id));
+        } else if !self.volume_id.is_empty() {
+            match volumes_api::get_volume(&config, &self.volume_id).await {
+                Ok(_) => lines.push(format!("would delete volume {}: exists", self.volume_id)),
+                Err(ref e) if is_not_found(e) => {
+                    lines.push(format!("would delete volume {}: not found", self.volume_id))
+                }
+                Err(e) => return Err(Self::map_pods_error(e)),
             }
-        };
-
-        // Store minimal info for monitoring later
-        self.pod_info = Some(format!("{:#?}", pod_resp.result));
-        self.volume_info = Some(self.volume_id.clone());
-
-        let pod_url = Self::_pod_url_from_result(&pod_resp.result);
-        let status = pod_resp.result.status.clone();
-
+        }
         Ok(DeploymentResult::PodResult {
             pod_id: self.pod_id.clone(),
             volume_id: self.volume_id.clone(),
-            pod_url,
+            pod_url: None,
+            pod_urls: Vec::new(),
             status,
-            pod_info: self.pod_info.clone().unwrap_or_default(),
-            volume_info: self.volume_info.clone().unwrap_or_default(),
+            resource_usage: None,
+            pod_info: lines.join("\n"),
+            volume_info: String::new(),
+            volume: None,
+            conditions: Vec::new(),
             tapis_user: self.server.tapis_user.clone(),
             tapis_tenant: self.server.tenant_url.clone(),
             model_id: self.server.default_model.clone(),
+            model_revision: self.model_revision(),
         })
     }
 
-    async fn start(&self) -> Result<DeploymentResult, DeploymentError> {
-        let config = self.pods_config()?;
-        let pod_resp = pods_api::start_pod(&config, &self.pod_id)
-            .await
-            .map_err(Self::map_pods_error)?;
+    /// Poll [FlexServDeployment::monitor] until the pod reports `target` or a terminal status
+    /// (e.g. STOPPED after stop(), or FAILED), returning the status observed. Fails with
+    /// TapisTimeout if neither happens within `timeout`; monitor errors are returned as is.
+    pub async fn wait_for_status(
+        &self,
+        target: PodStatus,
+        timeout: Duration,
+    ) -> Result<PodStatus, DeploymentError> {
+        let backoff = BackoffConfig {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+            timeout: Some(timeout),
+            ..Default::default()
+        };
+        poll_with_backoff(
+            |_| async {
+                let result = self.monitor().await?;
+                let status = result.as_pod().and_then(|p| p.status).unwrap_or_default();
+                Ok(PodStatus::parse(status))
+            },
+            |status| *status == target || status.is_terminal(),
+            &backoff,
+        )
+        .await
+        .map_err(|e| match e {
+            PollError::Operation(e) => e,
+            PollError::Exhausted { elapsed, .. } => DeploymentError::TapisTimeout(format!(
+                "pod {} did not reach {} within {:.1}s",
+                self.pod_id,
+                target,
+                elapsed.as_secs_f64()
+            )),
+        })
+    }
 
-        let pod_url = Self::_pod_url_from_result(&pod_resp.result);
-        let status = pod_resp.result.status.clone();
+    /// Prime a ready server with small completions, authenticating with the FlexServ token.
+    /// Sends `options.warmup_prompts` in order (through `/v1/chat/completions` when the model has
+    /// a chat template, see [PodDeploymentOptions::warmup_prompts]), or else one
+    /// `POST {url}/v1/completions` with `options.warmup_prompt`; `max_tokens` is
+    /// `options.warmup_max_tokens`. Any transport error or non-2xx response is InferenceFailed;
+    /// an invalid `ca_cert_pem` or `http_proxy` is InvalidConfiguration.
+    pub async fn warmup(&self, url: &str) -> Result<(), DeploymentError> {
+        let client = self.inference_client(url)?;
+        let (prompts, chat) = if self.options.warmup_prompts.is_empty() {
+            let prompt = self.options.warmup_prompt.as_deref().unwrap_or("Hello");
+            (vec![prompt.to_string()], false)
+        } else {
+            let chat = self.has_chat_template(&client).await;
+            (self.options.warmup_prompts.clone(), chat)
+        };
+        let path = if chat {
+            "/v1/chat/completions"
+        } else {
+            "/v1/completions"
+        };
+        let endpoint = client.endpoint(path);
+        let max_tokens = self.options.warmup_max_tokens.unwrap_or(8);
+        let started = std::time::Instant::now();
+        for prompt in &prompts {
+            let body = if chat {
+                serde_json::json!({
+                    "model": self.model_path(),
+                    "messages": [{"role": "user", "content": prompt}],
+                    "max_tokens": max_tokens,
+                })
+            } else {
+                serde_json::json!({
+                    "model": self.model_path(),
+                    "prompt": prompt,
+                    "max_tokens": max_tokens,
+                })
+            };
+            let resp = client
+                .request(reqwest::Method::POST, path)
+                .timeout(WARMUP_TIMEOUT)
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| DeploymentError::InferenceFailed(format!("{}: {}", endpoint, e)))?;
+            let status = resp.status();
+            if !status.is_success() {
+                let text = resp.text().await.unwrap_or_default();
+                return Err(DeploymentError::InferenceFailed(format!(
+                    "warmup returned {}: {}",
+                    status, text
+                )));
+            }
+        }
+        log::info!(
+            "Warmed up pod {} with {} prompt(s) in {} ms",
+            self.pod_id,
+            prompts.len(),
+            started.elapsed().as_millis()
+        );
+        Ok(())
+    }
 
-        Ok(DeploymentResult::PodResult {
-            pod_id: self.pod_id.clone(),
-            volume_id: self.volume_id.clone(),
-            pod_url,
-            status,
-            pod_info: format!("{:#?}", pod_resp.result),
-            volume_info: self.volume_id.clone(),
-            tapis_user: self.server.tapis_user.clone(),
-            tapis_tenant: self.server.tenant_url.clone(),
-            model_id: self.server.default_model.clone(),
+    /// [PodInferenceClient] for this deployment's server at `pod_url` (e.g. from
+    /// [DeploymentResult::pod_url]), with its FlexServ token and model path. Uses the
+    /// `ca_cert_pem`, `danger_accept_invalid_certs`, and `http_proxy` options; an invalid
+    /// certificate or proxy is InvalidConfiguration.
+    pub fn inference_client(&self, pod_url: &str) -> Result<PodInferenceClient, DeploymentError> {
+        let http = flexserv_client(
+            self.options.ca_cert_pem.as_deref(),
+            self.options.danger_accept_invalid_certs,
+            self.options.http_proxy.as_deref(),
+        )?;
+        Ok(PodInferenceClient::new(pod_url, self.flexserv_token())
+            .with_model(self.model_path())
+            .with_http_client(http))
+    }
+
+    /// Whether `GET {url}/v1/models` lists this model (or, failing that, the first model) with
+    /// a non-empty `chat_template`. False when the request fails.
+    async fn has_chat_template(&self, client: &PodInferenceClient) -> bool {
+        let endpoint = client.endpoint("/v1/models");
+        let body = match client
+            .request(reqwest::Method::GET, "/v1/models")
+            .timeout(WARMUP_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(resp) if resp.status().is_success() => resp.json::<serde_json::Value>().await,
+            Ok(resp) => {
+                log::warn!(
+                    "{} returned {}; warming up with completions",
+                    endpoint,
+                    resp.status()
+                );
+                return false;
+            }
+            Err(e) => {
+                log::warn!("{}: {}; warming up with completions", endpoint, e);
+                return false;
+            }
+        };
+        let Ok(body) = body else {
+            return false;
+        };
+        let models = body["data"].as_array().cloned().unwrap_or_default();
+        let model_path = self.model_path();
+        let entry = models
+            .iter()
+            .find(|m| m["id"].as_str() == Some(model_path.as_str()))
+            .or_else(|| models.first());
+        entry.is_some_and(|m| match &m["chat_template"] {
+            serde_json::Value::String(t) => !t.is_empty(),
+            serde_json::Value::Bool(b) => *b,
+            _ => false,
         })
     }
 
-    async fn stop(&self) -> Result<DeploymentResult, DeploymentError> {
-        let config = self.pods_config()?;
-        let pod_resp = pods_api::stop_pod(&config, &self.pod_id)
+    /// Models this deployment's server reports (see [served_models]), using the pod URL from
+    /// [FlexServDeployment::monitor]. InferenceFailed when the pod has no URL yet.
+    pub async fn served_models(&self) -> Result<Vec<String>, DeploymentError> {
+        let snapshot = self.monitor().await?;
+        let url = snapshot.pod_url().ok_or_else(|| {
+            DeploymentError::InferenceFailed(format!("pod {} has no URL yet", self.pod_id))
+        })?;
+        list_served_models(&self.inference_client(url)?).await
+    }
+
+    /// Requested resources from the options (defaults applied) plus the primary pod's uptime,
+    /// for chargeback with [estimate_cost]. Uptime counts from `start_instance_ts` (else
+    /// `creation_ts`) while the pod is `AVAILABLE`/`RUNNING`; otherwise it is 0.
+    pub async fn cost_inputs(&self) -> Result<CostInputs, DeploymentError> {
+        let config = self.pods_config().await?;
+        let pod = pods_api::get_pod(&config, &self.pod_id, None, None)
             .await
-            .map_err(Self::map_pods_error)?;
+            .map_err(Self::map_pods_error)?
+            .result;
+        let running = pod.status.as_deref().is_some_and(|s| {
+            matches!(
+                PodStatus::parse(s),
+                PodStatus::Available | PodStatus::Running
+            )
+        });
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let uptime_seconds = serde_json::to_value(&pod)
+            .ok()
+            .filter(|_| running)
+            .and_then(|v| Self::started_at_from_json(&v))
+            .map(|started| (now - started).max(0) as u64)
+            .unwrap_or(0);
+        Ok(CostInputs {
+            cpu_millicores: self.options.cpu_request.unwrap_or(DEFAULT_CPU_REQUEST) as i64,
+            mem_mb: self
+                .options
+                .mem_request_mb
+                .unwrap_or(DEFAULT_MEM_REQUEST_MB) as i64,
+            gpus: self.options.gpus.unwrap_or(0).max(0) as i64,
+            uptime_seconds,
+        })
+    }
 
-        let pod_url = Self::_pod_url_from_result(&pod_resp.result);
-        let status = pod_resp.result.status.clone();
+    /// Unix seconds of `start_instance_ts`, else `creation_ts`, in a serialized pod record.
+    fn started_at_from_json(pod: &serde_json::Value) -> Option<i64> {
+        ["start_instance_ts", "creation_ts"].iter().find_map(|key| {
+            pod.get(key)?
+                .as_str()
+                .and_then(crate::utils::parse_timestamp_secs)
+        })
+    }
 
-        Ok(DeploymentResult::PodResult {
-            pod_id: self.pod_id.clone(),
-            volume_id: self.volume_id.clone(),
-            pod_url,
+    /// Pod status from [Self::monitor_pod_only], whether a URL is assigned, and (when one is)
+    /// [health_check] against it, combined into one [HealthSummary]. Never fails: errors are
+    /// reported in `error` with `healthy: false`.
+    pub async fn health_summary(&self) -> HealthSummary {
+        let snapshot = match self.monitor_pod_only().await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                return HealthSummary {
+                    status: None,
+                    pod_url: None,
+                    server_healthy: false,
+                    healthy: false,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        let status = snapshot.as_pod().and_then(|p| p.status).map(str::to_string);
+        let pod_url = snapshot.pod_url().map(str::to_string);
+        let check = match pod_url {
+            Some(ref url) => Some(match self.inference_client(url) {
+                Ok(client) => {
+                    let config = self.options.health_check.clone().unwrap_or_default();
+                    check_health(&client, &config).await
+                }
+                Err(e) => Err(e),
+            }),
+            None => None,
+        };
+        let server_healthy = matches!(check, Some(Ok(())));
+        let running = status.as_deref().is_some_and(|s| {
+            matches!(
+                PodStatus::parse(s),
+                PodStatus::Available | PodStatus::Running
+            )
+        });
+        HealthSummary {
+            healthy: running && server_healthy,
             status,
-            pod_info: format!("{:#?}", pod_resp.result),
-            volume_info: self.volume_id.clone(),
-            tapis_user: self.server.tapis_user.clone(),
-            tapis_tenant: self.server.tenant_url.clone(),
-            model_id: self.server.default_model.clone(),
+            pod_url,
+            server_healthy,
+            error: check.and_then(|r| r.err()).map(|e| e.to_string()),
+        }
+    }
+
+    /// Spawn a background task that checks [Self::health_summary] every
+    /// `policy.check_interval` and calls terminate() once no check has been healthy for
+    /// `policy.inactivity_timeout`; the task then ends with terminate()'s result. It works on a
+    /// copy of this deployment, so later changes to `self` are not seen.
+    ///
+    /// Fire-and-forget: dropping the handle does not stop the task. Call `abort()` on it to
+    /// cancel (safe between checks; a terminate() already under way may be cut short). Must be
+    /// called inside a Tokio runtime, and the task ends with the runtime.
+    pub fn watchdog(
+        &self,
+        policy: AutoTerminatePolicy,
+    ) -> tokio::task::JoinHandle<Result<DeploymentResult, DeploymentError>> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut last_healthy = tokio::time::Instant::now();
+            loop {
+                tokio::time::sleep(policy.check_interval).await;
+                let summary = deployment.health_summary().await;
+                if summary.healthy {
+                    last_healthy = tokio::time::Instant::now();
+                    continue;
+                }
+                let idle = last_healthy.elapsed();
+                if idle >= policy.inactivity_timeout {
+                    log::warn!(
+                        "Pod {} unhealthy for {:.0}s ({}), terminating",
+                        deployment.pod_id,
+                        idle.as_secs_f64(),
+                        summary.error.as_deref().unwrap_or("no healthy check")
+                    );
+                    return deployment.terminate().await;
+                }
+            }
         })
     }
 
-    async fn terminate(&self) -> Result<DeploymentResult, DeploymentError> {
-        let config = self.pods_config()?;
+    /// In-pod path of this deployment's model (see [model_path]), honoring `options.model_subdir`.
+    pub fn model_path(&self) -> String {
+        format!("{}/{}", FLEXSERV_MODEL_REPO, self.model_dir_name())
+    }
 
-        // Delete pod and volume together. Try both even if one fails.
-        // Delete pod first (volume deletion may fail if pod still exists).
-        let mut pod_resp = None;
-        let mut pod_error = None;
-        match pods_api::delete_pod(&config, &self.pod_id).await {
-            Ok(resp) => pod_resp = Some(resp),
-            Err(e) => pod_error = Some(Self::map_pods_error(e)),
+    /// Model directory name inside the volume (e.g. openai-community/gpt2 -> openai-community_gpt2),
+    /// or `options.model_subdir` when set.
+    fn model_dir_name(&self) -> String {
+        match self.options.model_subdir {
+            Some(ref dir) => dir.trim_end_matches('/').to_string(),
+            None => model_dir_name(&self.server.default_model),
+        }
+    }
+
+    /// FlexServ secret: `options.flexserv_secret`, else the `FLEXSERV_SECRET` env var.
+    fn flexserv_secret(&self) -> String {
+        self.options
+            .flexserv_secret
+            .clone()
+            .unwrap_or_else(|| std::env::var("FLEXSERV_SECRET").unwrap_or_default())
+    }
+
+    /// Token the server accepts; see [flexserv_token]. Built from the same directory name as
+    /// the model path, so it follows `options.model_subdir`.
+    fn flexserv_token(&self) -> String {
+        flexserv_token_for_dir(&self.flexserv_secret(), &self.model_dir_name())
+    }
+
+    /// Revision passed to the pod: the pinned sha when resolved, else `server.model_revision`.
+    fn model_revision(&self) -> Option<String> {
+        self.resolved_revision
+            .clone()
+            .or_else(|| self.server.model_revision.clone())
+    }
+
+    /// Delete the pod(s) and volume, reporting exactly what was cleaned up. Both deletions are
+    /// always attempted; per-resource failures are collected in [TerminateOutcome::errors] instead
+    /// of failing the call, so a leaked volume is visible even when the pod was deleted. Returns
+    /// Err only when the TAPIS client cannot be configured.
+    pub async fn terminate_with_outcome(
+        &self,
+    ) -> Result<(DeploymentResult, TerminateOutcome), DeploymentError> {
+        let config = self.pods_config().await?;
+        let mut errors = Vec::new();
+
+        // Delete pods first (volume deletion may fail if a pod still exists).
+        let mut pod_resps = Vec::new();
+        let mut pod_deleted = true;
+        for pod_id in self.pod_ids() {
+            match pods_api::delete_pod(&config, &pod_id).await {
+                Ok(resp) => pod_resps.push(resp),
+                Err(e) => {
+                    let e = Self::map_pods_error(e);
+                    log::warn!("Pod {} deletion failed: {:?}", pod_id, e);
+                    pod_deleted = false;
+                    errors.push(e);
+                }
+            }
         }
 
         let mut vol_resp = None;
-        let mut vol_error = None;
-        if !self.volume_id.is_empty() {
+        let mut volume_deleted = true;
+        let keep_shared = self.shares_volume() && !self.options.delete_shared_volume;
+        if keep_shared {
+            log::info!("Keeping shared volume {}", self.volume_id);
+            volume_deleted = false;
+        } else if !self.volume_id.is_empty() {
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
             match volumes_api::delete_volume(&config, &self.volume_id).await {
                 Ok(resp) => vol_resp = Some(resp),
-                Err(e) => vol_error = Some(Self::map_pods_error(e)),
+                Err(e) => {
+                    let e = Self::map_pods_error(e);
+                    log::warn!("Volume {} deletion failed: {:?}", self.volume_id, e);
+                    volume_deleted = false;
+                    errors.push(e);
+                }
             }
         }
 
-        // If both failed, return the pod error (more critical)
-        if pod_error.is_some() && vol_error.is_some() {
-            return Err(pod_error.unwrap());
-        }
-        // If only one failed, log it but continue (partial cleanup is better than none)
-        if let Some(ref e) = pod_error {
-            log::warn!("Pod deletion failed (but volume deleted): {:?}", e);
-        }
-        if let Some(ref e) = vol_error {
-            log::warn!("Volume deletion failed (but pod deleted): {:?}", e);
-        }
-
         let vol_info = if self.volume_id.is_empty() {
             "no volume".to_string()
+        } else if keep_shared {
+            "kept (shared)".to_string()
+        } else if !volume_deleted {
+            "not deleted".to_string()
         } else {
             vol_resp
                 .as_ref()
                 .map(|r| format!("{:#?}", r))
                 .unwrap_or_else(|| "deleted".to_string())
         };
-        let combined_info = format!(
-            "pod: {:#?}\nvolume: {}",
-            pod_resp
-                .as_ref()
+        let pod_part = if pod_resps.is_empty() {
+            if pod_deleted {
+                "deleted"
+            } else {
+                "not deleted"
+            }
+            .to_string()
+        } else {
+            pod_resps
+                .iter()
                 .map(|r| format!("{:#?}", r))
-                .unwrap_or_else(|| "deleted".to_string()),
-            vol_info
-        );
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let combined_info = format!("pod: {}\nvolume: {}", pod_part, vol_info);
 
-        Ok(DeploymentResult::PodResult {
+        let result = DeploymentResult::PodResult {
             pod_id: self.pod_id.clone(),
             volume_id: self.volume_id.clone(),
             pod_url: None, // pod deleted
+            pod_urls: Vec::new(),
             status: None,
+            resource_usage: None,
             pod_info: combined_info,
             volume_info: String::new(),
+            volume: None,
+            conditions: Vec::new(),
             tapis_user: self.server.tapis_user.clone(),
             tapis_tenant: self.server.tenant_url.clone(),
             model_id: self.server.default_model.clone(),
-        })
-    }
-
-    async fn monitor(&self) -> Result<DeploymentResult, DeploymentError> {
-        let config = self.pods_config()?;
-
-        let pod_resp = pods_api::get_pod(&config, &self.pod_id, None, None)
-            .await
-            .map_err(Self::map_pods_error)?;
-
-        log::debug!("pods_api::get_pod result:\n{:#?}", pod_resp);
-
-        let volume_info = if self.volume_id.is_empty() {
-            String::new()
-        } else {
-            match volumes_api::get_volume(&config, &self.volume_id).await {
-                Ok(vol_resp) => format!("{:#?}", vol_resp.result),
-                Err(_) => String::new(),
-            }
+            model_revision: self.model_revision(),
+        };
+        let outcome = TerminateOutcome {
+            pod_deleted,
+            volume_deleted,
+            errors,
         };
+        Ok((result, outcome))
+    }
 
-        let pod_info = format!("{:#?}", pod_resp.result);
-        let pod_url = Self::_pod_url_from_result(&pod_resp.result);
-        let status = pod_resp.result.status.clone();
+    /// Like monitor(), but skips the volume lookup (`volume_info` is empty): one GET per pod.
+    /// Meant for dashboards polling many deployments (see [monitor_all]).
+    pub async fn monitor_pod_only(&self) -> Result<DeploymentResult, DeploymentError> {
+        let limit = self.options.timeouts.monitor;
+        with_timeout("monitor", limit, self.monitor_pods(false)).await
+    }
 
-        Ok(DeploymentResult::PodResult {
-            pod_id: self.pod_id.clone(),
-            volume_id: self.volume_id.clone(),
-            pod_url,
-            status,
-            pod_info,
-            volume_info,
-            tapis_user: self.server.tapis_user.clone(),
-            tapis_tenant: self.server.tenant_url.clone(),
-            model_id: self.server.default_model.clone(),
+    /// The primary pod's [models::PodResponseModel] as returned by the Pods API, for fields
+    /// that monitor() does not surface. Honors `options.timeouts.monitor`.
+    pub async fn monitor_raw(&self) -> Result<models::PodResponseModel, DeploymentError> {
+        let limit = self.options.timeouts.monitor;
+        with_timeout("monitor", limit, async {
+            let config = self.pods_config().await?;
+            let pod_resp = pods_api::get_pod(&config, &self.pod_id, None, None)
+                .await
+                .map_err(Self::map_pods_error)?;
+            Ok(*pod_resp.result)
         })
+        .await
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::backend::Backend;
-    use crate::server::{FlexServInstance, ModelConfig, TapisConfig};
 
-    fn is_lowercase_alphanumeric(s: &str) -> bool {
-        s.chars()
-            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    /// Pods API exchanges recorded since the last call (with `options.capture_trace`), oldest
+    /// first. Call it after an operation, whether it succeeded or failed; the buffer is emptied.
+    pub fn take_trace(&self) -> OperationTrace {
+        let mut buffer = self.trace.lock().unwrap_or_else(|e| e.into_inner());
+        OperationTrace {
+            entries: std::mem::take(&mut *buffer),
+        }
     }
 
-    #[test]
+    /// Rotate the FlexServ secret without losing the model volume, returning the new auth token
+    /// (see [flexserv_token]) so clients can switch right away. Updates each pod in place
+    /// ([pods_api::update_pod] with the re-rendered command, arguments, and environment; with
+    /// `token_via_file`, the token file is rewritten too) and then restarts them. If the Pods API
+    /// rejects the first update (404/405/501), the pods are deleted and recreated with the volume
+    /// kept, as with `reuse_volume_on_pod_failure`. `options.flexserv_secret` changes only on
+    /// success. Honors `options.timeouts.create`.
+    pub async fn rotate_token(&mut self, new_secret: &str) -> Result<String, DeploymentError> {
+        if new_secret.trim().is_empty() {
+            return Err(DeploymentError::InvalidConfiguration(
+                "new FlexServ secret must be non-empty".to_string(),
+            ));
+        }
+        let saved = self.options.clone();
+        self.options.flexserv_secret = Some(new_secret.to_string());
+        let limit = self.options.timeouts.create;
+        let result = with_timeout("rotate_token", limit, self.rotate_token_inner()).await;
+        // Also undoes the fallback's temporary option changes if it timed out midway.
+        self.options = saved;
+        if result.is_ok() {
+            self.options.flexserv_secret = Some(new_secret.to_string());
+        }
+        result
+    }
+
+    async fn rotate_token_inner(&mut self) -> Result<String, DeploymentError> {
+        let token = self.flexserv_token();
+        let config = self.pods_config().await?;
+        let new_pod = self.render_new_pod();
+        let update = models::UpdatePod {
+            command: new_pod.command,
+            arguments: new_pod.arguments,
+            environment_variables: Some(new_pod.environment_variables),
+            ..models::UpdatePod::new()
+        };
+        let pod_ids = self.pod_ids();
+        for (i, pod_id) in pod_ids.iter().enumerate() {
+            match pods_api::update_pod(&config, pod_id, update.clone()).await {
+                Ok(_) => {}
+                // Only the first update decides the fallback, so no replica is changed yet.
+                Err(apis::Error::ResponseError(ref resp))
+                    if i == 0 && matches!(resp.status.as_u16(), 404 | 405 | 501) =>
+                {
+                    log::warn!(
+                        "Pod {} cannot be updated in place ({}), recreating pods with volume {} kept",
+                        pod_id,
+                        resp.status,
+                        self.volume_id
+                    );
+                    self.options.reuse_volume_on_pod_failure = true;
+                    self.options.cleanup_before_create = false;
+                    self.cleanup_with(&config).await?;
+                    self.create_inner().await?;
+                    return Ok(token);
+                }
+                Err(e) => return Err(Self::map_pods_error(e)),
+            }
+        }
+        if self.options.token_via_file {
+            self.upload_token_file(&config).await?;
+        }
+        for pod_id in &pod_ids {
+            pods_api::restart_pod(&config, pod_id, None)
+                .await
+                .map_err(Self::map_pods_error)?;
+        }
+        log::info!("Rotated FlexServ token for pod {}", self.pod_id);
+        Ok(token)
+    }
+
+    /// Send a raw Pods API request the SDK cannot make (e.g. an upload from memory, where the
+    /// SDK's `upload_to_volume` reads a local file), returning the status code and body. Only
+    /// transport failures are errors.
+    async fn send_pods_request(
+        &self,
+        request: reqwest_middleware::RequestBuilder,
+    ) -> Result<(u16, String), DeploymentError> {
+        let resp = request.send().await.map_err(|e| match e {
+            reqwest_middleware::Error::Reqwest(e) => Self::map_transport_error(e),
+            other => DeploymentError::UnknownError(other.to_string()),
+        })?;
+        let code = resp.status().as_u16();
+        let content = resp.text().await.map_err(Self::map_transport_error)?;
+        Ok((code, content))
+    }
+
+    /// Point this deployment at a different model: clears the revision (it belonged to the old
+    /// model) and any cached pod/volume info, and re-derives pod_id/volume_id from the new config
+    /// (ids from [Self::with_explicit_ids] are kept). Does not touch TAPIS; resources under the old ids are left as they are.
+    pub fn set_model(&mut self, model_id: impl Into<String>) {
+        self.server.default_model = model_id.into();
+        self.server.model_revision = None;
+        self.resolved_revision = None;
+        self.volume_info = None;
+        self.pod_info = None;
+        if self.explicit_ids {
+            return;
+        }
+        self.replica_ids = None;
+        let (pod_id, volume_id) = Self::ids_from_options(&self.server, &self.options);
+        self.pod_id = pod_id;
+        self.volume_id = volume_id;
+    }
+
+    /// Terminate the current deployment and create a new one serving `new_model`. Deleting the
+    /// pod also stops an in-progress model download, so this need not wait for it to finish.
+    /// If the old pod cannot be deleted the deployment is left unchanged (ids still point at the
+    /// old pod) and the error is returned; for a deployment that was never created, use
+    /// [Self::set_model] and [FlexServDeployment::create] directly.
+    pub async fn replace_model(
+        &mut self,
+        new_model: String,
+    ) -> Result<DeploymentResult, DeploymentError> {
+        let (_, outcome) = with_timeout(
+            "terminate",
+            self.options.timeouts.terminate,
+            self.terminate_with_outcome(),
+        )
+        .await?;
+        if !outcome.pod_deleted {
+            return Err(outcome.errors.into_iter().next().unwrap_or_else(|| {
+                DeploymentError::UnknownError(format!("pod {} not deleted", self.pod_id))
+            }));
+        }
+        if !outcome.volume_deleted {
+            log::warn!(
+                "Volume {} was not deleted while replacing model {}",
+                self.volume_id,
+                self.server.default_model
+            );
+        }
+        log::info!(
+            "Replacing model {} with {} on {}",
+            self.server.default_model,
+            new_model,
+            self.pod_id
+        );
+        self.set_model(new_model);
+        self.create().await
+    }
+
+    /// TAPIS pod ids for every replica: `[pod_id]` for a single pod, otherwise
+    /// `{pod_id}0`, `{pod_id}1`, ... (see [PodDeploymentOptions::replicas]), or the ids given to
+    /// [Self::with_replica_ids].
+    pub fn pod_ids(&self) -> Vec<String> {
+        if let Some(ref ids) = self.replica_ids {
+            return ids.clone();
+        }
+        let replicas = self.options.replicas.unwrap_or(1);
+        if replicas <= 1 {
+            return vec![self.pod_id.clone()];
+        }
+        (0..replicas)
+            .map(|i| format!("{}{}", self.pod_id, crate::utils::to_base36(i)))
+            .collect()
+    }
+
+    /// TAPIS client: `options.http_client` if set; with `options.token_provider`, the shared
+    /// client for the current token; otherwise one built from the token and TLS options on
+    /// first use and cached for later operations on this deployment.
+    async fn http_client(&self) -> Result<reqwest::Client, DeploymentError> {
+        if let Some(ref client) = self.options.http_client {
+            return Ok(client.clone());
+        }
+        if let Some(ref provider) = self.options.token_provider {
+            return shared_tapis_client(
+                &provider.token().await?,
+                self.options.ca_cert_pem.as_deref(),
+                self.options.danger_accept_invalid_certs,
+                self.options.http_proxy.as_deref(),
+                &self.options.extra_headers,
+            );
+        }
+        if let Some(client) = self.client.get() {
+            return Ok(client.clone());
+        }
+        let client = tapis_client(
+            &self.tapis_token,
+            self.options.ca_cert_pem.as_deref(),
+            self.options.danger_accept_invalid_certs,
+            self.options.http_proxy.as_deref(),
+            &self.options.extra_headers,
+        )?;
+        Ok(self.client.get_or_init(|| client).clone())
+    }
+
+    /// Token for this operation: from `options.token_provider` when set (falling back to
+    /// `tapis_token` with a warning if it fails), else `tapis_token`.
+    async fn current_tapis_token(&self) -> String {
+        match self.options.token_provider {
+            Some(ref provider) => provider.token().await.unwrap_or_else(|e| {
+                log::warn!("Token provider failed, using tapis_token: {}", e);
+                self.tapis_token.clone()
+            }),
+            None => self.tapis_token.clone(),
+        }
+    }
+
+    /// Build Pods API configuration (base URL + reqwest client with X-Tapis-Token).
+    /// Base is the v3 API root (e.g. https://tacc.tapis.io/v3), whether or not `tenant_url`
+    /// already ends in `/v3`.
+    /// Honors `options.http_client` or the TLS and proxy options used to build a client.
+    async fn pods_config(&self) -> Result<configuration::Configuration, DeploymentError> {
+        let api_base = crate::utils::tapis_api_base(&self.server.tenant_url);
+        let client = self.http_client().await?;
+        let mut config = configuration::Configuration::default();
+        config.base_path = api_base;
+        let mut builder = reqwest_middleware::ClientBuilder::new(client);
+        if self.options.capture_trace {
+            let hf_token = self
+                .server
+                .hf_token
+                .clone()
+                .or_else(|| std::env::var("HF_TOKEN").ok());
+            // Without a secret the FlexServ token is just the model directory name.
+            let flexserv_secret = Some(self.flexserv_secret()).filter(|s| !s.is_empty());
+            let secrets = [
+                Some(self.current_tapis_token().await),
+                flexserv_secret.as_ref().map(|_| self.flexserv_token()),
+                flexserv_secret,
+                hf_token,
+            ];
+            builder = builder.with(TraceMiddleware::new(
+                self.trace.clone(),
+                secrets.into_iter().flatten().collect(),
+            ));
+        }
+        config.client = builder.build();
+        Ok(config)
+    }
+
+    /// Poll `get_pod` until the pod reports a URL, for up to `secs` seconds. Lookup errors are
+    /// retried; returns None (with a warning) if no URL appears in time.
+    async fn wait_for_pod_url(
+        &self,
+        config: &configuration::Configuration,
+        pod_id: &str,
+        secs: u64,
+    ) -> Option<String> {
+        let backoff = BackoffConfig {
+            initial_delay: tokio::time::Duration::from_millis(500),
+            max_delay: tokio::time::Duration::from_secs(5),
+            timeout: Some(tokio::time::Duration::from_secs(secs)),
+            ..Default::default()
+        };
+        let result = poll_with_backoff(
+            |_| async {
+                let url = pods_api::get_pod(config, pod_id, None, None)
+                    .await
+                    .ok()
+                    .and_then(|r| self.pod_url_from_result(&r.result));
+                Ok::<_, DeploymentError>(url)
+            },
+            |url| url.is_some(),
+            &backoff,
+        )
+        .await;
+        match result {
+            Ok(url) => url,
+            Err(_) => {
+                log::warn!("Pod {} has no URL after {}s", pod_id, secs);
+                None
+            }
+        }
+    }
+
+    /// Extract pod URL from API response (networking.default.url), keeping any path it has,
+    /// with `options.url_path_prefix` appended (unless the URL already ends with it).
+    fn pod_url_from_result(
+        &self,
+        result: &tapis_sdk::pods::models::PodResponseModel,
+    ) -> Option<String> {
+        let url = result
+            .networking
+            .as_ref()
+            .and_then(|n| n.get("default"))
+            .and_then(|net| net.url.clone())?;
+        let prefix = self
+            .options
+            .url_path_prefix
+            .as_deref()
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty());
+        match prefix {
+            Some(p) if !url.trim_end_matches('/').ends_with(&format!("/{}", p)) => {
+                Some(crate::utils::join_url_path(&url, p))
+            }
+            _ => Some(url),
+        }
+    }
+
+    /// Extract utilization from a serialized pod record. Looks for a `resource_usage`, `usage`,
+    /// or `metrics` object at the top level or under `status_container`, accepting plain numbers
+    /// or Kubernetes quantities (`250m`, `1.5`, `512Mi`, `2Gi`). Returns None if nothing is reported.
+    fn resource_usage_from_json(pod: &serde_json::Value) -> Option<ResourceUsage> {
+        let scopes = [Some(pod), pod.get("status_container")];
+        let usage = scopes.iter().flatten().find_map(|scope| {
+            ["resource_usage", "usage", "metrics"]
+                .iter()
+                .find_map(|k| scope.get(*k).filter(|v| v.is_object()))
+        })?;
+        let field = |keys: &[&str]| keys.iter().find_map(|k| usage.get(*k));
+        let result = ResourceUsage {
+            cpu_millicores: field(&["cpu_millicores", "cpu"]).and_then(parse_cpu_millicores),
+            mem_mb: field(&["mem_mb", "memory"]).and_then(parse_mem_mb),
+            gpu_percent: field(&["gpu_percent", "gpu"]).and_then(|v| match v {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => s.trim().trim_end_matches('%').parse().ok(),
+                _ => None,
+            }),
+        };
+        if result == ResourceUsage::default() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Conditions and events from a serialized pod: `conditions` and `events` arrays on the pod or
+    /// its `status_container`. Each entry needs a `reason` (or `type`) or a `message`; the time
+    /// comes from `timestamp`, `last_transition_time`, `last_timestamp`, or `event_time` (camelCase
+    /// accepted). Sorted oldest first; entries without a time keep their place at the end.
+    fn conditions_from_json(pod: &serde_json::Value) -> Vec<PodCondition> {
+        let text = |entry: &serde_json::Value, keys: &[&str]| {
+            keys.iter()
+                .find_map(|k| entry.get(*k).and_then(|v| v.as_str()))
+                .map(str::to_string)
+        };
+        let scopes = [Some(pod), pod.get("status_container")];
+        let mut conditions: Vec<PodCondition> = scopes
+            .iter()
+            .flatten()
+            .flat_map(|scope| ["conditions", "events"].map(|k| scope.get(k)))
+            .flatten()
+            .filter_map(|list| list.as_array())
+            .flatten()
+            .filter_map(|entry| {
+                let reason = text(entry, &["reason", "type"]).unwrap_or_default();
+                let message = text(entry, &["message", "note"]).unwrap_or_default();
+                if reason.is_empty() && message.is_empty() {
+                    return None;
+                }
+                Some(PodCondition {
+                    reason,
+                    message,
+                    timestamp: text(
+                        entry,
+                        &[
+                            "timestamp",
+                            "last_transition_time",
+                            "lastTransitionTime",
+                            "last_timestamp",
+                            "lastTimestamp",
+                            "event_time",
+                            "eventTime",
+                        ],
+                    ),
+                })
+            })
+            .collect();
+        // Stable, so untimed entries stay in TAPIS order.
+        conditions.sort_by_key(|c| {
+            c.timestamp
+                .as_deref()
+                .and_then(crate::utils::parse_timestamp_secs)
+                .unwrap_or(i64::MAX)
+        });
+        conditions
+    }
+
+    /// Extract volume details from a serialized volume record: `volume_id` (else `fallback_id`),
+    /// `size_limit` and `size`/`size_used` (MB numbers or quantities like `10Gi`), and `status`.
+    fn volume_info_from_json(volume: &serde_json::Value, fallback_id: &str) -> VolumeInfo {
+        let field = |keys: &[&str]| keys.iter().find_map(|k| volume.get(*k));
+        VolumeInfo {
+            id: field(&["volume_id", "id"])
+                .and_then(|v| v.as_str())
+                .unwrap_or(fallback_id)
+                .to_string(),
+            size_limit_mb: field(&["size_limit_mb", "size_limit"]).and_then(parse_mem_mb),
+            size_used_mb: field(&["size_used_mb", "size_used", "size"]).and_then(parse_mem_mb),
+            status: field(&["status"])
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+        }
+    }
+
+    /// Map a tapis-pods error into our DeploymentError, based on HTTP status / network.
+    fn map_pods_error<E: std::fmt::Debug>(err: apis::Error<E>) -> DeploymentError {
+        match err {
+            apis::Error::Reqwest(e) => Self::map_transport_error(e),
+            apis::Error::ReqwestMiddleware(e) => DeploymentError::UnknownError(e.to_string()),
+            apis::Error::Serde(e) => DeploymentError::UnknownError(e.to_string()),
+            apis::Error::Io(e) => DeploymentError::UnknownError(e.to_string()),
+            apis::Error::ResponseError(resp) => {
+                Self::map_status_error(resp.status.as_u16(), resp.content)
+            }
+        }
+    }
+
+    /// Map a non-success HTTP status and body from the Pods API into our DeploymentError.
+    fn map_status_error(code: u16, content: String) -> DeploymentError {
+        let message = TapisErrorBody::message_or_raw(content);
+        if code == 401 || code == 403 {
+            DeploymentError::TapisAuthFailed(message)
+        } else if code == 400 {
+            DeploymentError::TapisBadRequest(message)
+        } else if (500..600).contains(&code) {
+            DeploymentError::TapisInternalServerError(message)
+        } else {
+            DeploymentError::UnknownError(message)
+        }
+    }
+
+    /// Map a reqwest transport error (no HTTP response) into our DeploymentError.
+    fn map_transport_error(e: reqwest::Error) -> DeploymentError {
+        if e.is_timeout() {
+            DeploymentError::TapisTimeout(e.to_string())
+        } else if e.is_connect() {
+            DeploymentError::TapisAPIUnreachable(e.to_string())
+        } else {
+            DeploymentError::UnknownError(e.to_string())
+        }
+    }
+
+    /// Backoff used to retry pod creation when `options.reuse_volume_on_pod_failure` is set.
+    fn pod_create_retry() -> BackoffConfig {
+        BackoffConfig {
+            initial_delay: tokio::time::Duration::from_secs(1),
+            max_delay: tokio::time::Duration::from_secs(4),
+            max_attempts: Some(3),
+            timeout: None,
+            ..Default::default()
+        }
+    }
+
+    /// Create the model volume. An existing volume with the same id is reused when
+    /// `options.reuse_volume_on_pod_failure` is set or the volume is shared; otherwise the 409 is
+    /// returned, since only [Self::cleanup] deletes leftovers. Returns whether this call created
+    /// the volume (false when an existing one was reused).
+    async fn ensure_volume(
+        &self,
+        config: &configuration::Configuration,
+        new_volume: models::NewVolume,
+    ) -> Result<bool, DeploymentError> {
+        let e = match volumes_api::create_volume(config, new_volume).await {
+            Ok(_) => return Ok(true),
+            Err(e) => e,
+        };
+        if is_already_exists(&e) && self.keeps_volume() {
+            log::info!("Volume {} already exists, reusing it", self.volume_id);
+            return Ok(false);
+        }
+        Err(Self::map_pods_error(e))
+    }
+
+    /// Best-effort undo of a failed create(): delete the pods in `pods` and, with
+    /// `delete_volume`, the volume. Only resources this create() made are passed in.
+    /// Returns one message per delete that failed (a 404 counts as already gone).
+    async fn rollback_create(
+        &self,
+        config: &configuration::Configuration,
+        pods: &[String],
+        delete_volume: bool,
+    ) -> Vec<String> {
+        let mut errors = Vec::new();
+        for pod_id in pods {
+            match pods_api::delete_pod(config, pod_id).await {
+                Ok(_) => {}
+                Err(ref e) if is_not_found(e) => {}
+                Err(e) => errors.push(format!("pod {}: {}", pod_id, Self::map_pods_error(e))),
+            }
+        }
+        if delete_volume {
+            match volumes_api::delete_volume(config, &self.volume_id).await {
+                Ok(_) => {}
+                Err(ref e) if is_not_found(e) => {}
+                Err(e) => errors.push(format!(
+                    "volume {}: {}",
+                    self.volume_id,
+                    Self::map_pods_error(e)
+                )),
+            }
+        }
+        for e in &errors {
+            log::error!("Rollback of create() failed, clean up manually: {}", e);
+        }
+        errors
+    }
+
+    /// Submit one pod; with `options.reuse_volume_on_pod_failure`, retryable errors are retried
+    /// per [Self::pod_create_retry], deleting any half-created pod before each new attempt.
+    async fn create_pod_step(
+        &self,
+        config: &configuration::Configuration,
+        new_pod: &models::NewPod,
+    ) -> Result<models::PodResponse, DeploymentError> {
+        let backoff = Self::pod_create_retry();
+        let max_attempts = if self.options.reuse_volume_on_pod_failure {
+            backoff.max_attempts.unwrap_or(1)
+        } else {
+            1
+        };
+        let mut attempt = 1;
+        loop {
+            match self
+                .submit_pod(config, new_pod.clone())
+                .await
+                .map_err(Self::map_pods_error)
+            {
+                Err(e) if e.is_retryable() && attempt < max_attempts => {
+                    log::warn!(
+                        "Pod {} creation attempt {} failed ({}), retrying...",
+                        new_pod.pod_id,
+                        attempt,
+                        e
+                    );
+                    let _ = pods_api::delete_pod(config, &new_pod.pod_id).await;
+                    tokio::time::sleep(backoff.delay_for_attempt(attempt - 1, 0.5)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// The `NewPod` create() submits for the primary pod (before `raw_pod_overrides`): image,
+    /// command/arguments, environment, volume mount, networking, and resources.
+    pub fn render_new_pod(&self) -> models::NewPod {
+        let model_dir_name = self.model_dir_name();
+        let image = self.image();
+
+        // volume_mounts: key = mount path, value = VolumeMountsValue (type, source_id, sub_path).
+        let mut volume_mounts = std::collections::HashMap::new();
+        let mut mount =
+            models::VolumeMountsValue::new(models::volume_mounts_value::Type::Tapisvolume);
+        mount.source_id = Some(Some(self.volume_id.clone()));
+        mount.sub_path = Some(String::new());
+        volume_mounts.insert(FLEXSERV_MODEL_REPO.to_string(), mount);
+        let flexserv_secret = self.flexserv_secret();
+        let flexserv_token = self.flexserv_token();
+
+        let hf_token = self
+            .server
+            .hf_token
+            .clone()
+            .or_else(|| std::env::var("HF_TOKEN").ok());
+
+        let pod_params = self.pod_params();
+        let StartupInputs {
+            pre_start,
+            command,
+            arguments,
+            token_file,
+        } = self.startup_inputs();
+
+        // env_file first, so everything set below takes precedence.
+        let mut env_vars: std::collections::HashMap<String, serde_json::Value> = self
+            .env_file_vars()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(k, v)| (k, serde_json::json!(v)))
+            .collect();
+        env_vars.extend(pod_params.environment_variables.unwrap_or_default());
+        env_vars.insert(
+            "MODEL_REPO".to_string(),
+            serde_json::json!(FLEXSERV_MODEL_REPO),
+        );
+        env_vars.insert("FLEXSERV_PORT".to_string(), serde_json::json!("8000"));
+        env_vars.insert("MODEL_NAME".to_string(), serde_json::json!(model_dir_name));
+        if !self.options.token_via_file {
+            env_vars.insert(
+                "FLEXSERV_SECRET".to_string(),
+                serde_json::json!(flexserv_secret),
+            );
+            env_vars.insert(
+                "FLEXSERV_TOKEN".to_string(),
+                serde_json::json!(flexserv_token),
+            );
+        }
+        if let Some(ref t) = hf_token {
+            env_vars.insert("HF_TOKEN".to_string(), serde_json::json!(t));
+        }
+        if let Some(revision) = self.model_revision() {
+            env_vars.insert("MODEL_REVISION".to_string(), serde_json::json!(revision));
+        }
+        if let Some(mb) = self.options.gpu_memory_mb {
+            env_vars.insert(
+                "GPU_MEMORY_MB".to_string(),
+                serde_json::json!(mb.to_string()),
+            );
+        }
+        if let Some(ref profile) = self.options.mig_profile {
+            env_vars.insert("GPU_MIG_PROFILE".to_string(), serde_json::json!(profile));
+        }
+        if let Some(ref project) = self.options.project_id {
+            env_vars.insert("TAPIS_PROJECT_ID".to_string(), serde_json::json!(project));
+        }
+
+        let mut net = models::ModelsPodsNetworking::new();
+        net.protocol = Some("http".to_string());
+        net.port = Some(8000);
+        let mut networking = std::collections::HashMap::new();
+        networking.insert("default".to_string(), net);
+
+        let mut resources = models::ModelsPodsResources::new();
+        resources.cpu_request = Some(self.options.cpu_request.unwrap_or(DEFAULT_CPU_REQUEST));
+        resources.cpu_limit = Some(self.options.cpu_limit.unwrap_or(DEFAULT_CPU_LIMIT));
+        resources.mem_request = Some(
+            self.options
+                .mem_request_mb
+                .unwrap_or(DEFAULT_MEM_REQUEST_MB),
+        );
+        resources.mem_limit = Some(self.options.mem_limit_mb.unwrap_or(DEFAULT_MEM_LIMIT_MB));
+        resources.gpus = Some(self.options.gpus.unwrap_or(0));
+
+        let mut new_pod = models::NewPod::new(self.pod_id.clone());
+        new_pod.image = Some(image);
+        new_pod.description = Some(self.with_project_tag(format!(
+            "FlexServ pod for {}@{}",
+            self.server.tapis_user, self.server.default_model
+        )));
+        match command {
+            Some(command) if !pre_start.is_empty() || token_file.is_some() => {
+                let script =
+                    startup_script(&pre_start, &command, &arguments, token_file.as_deref());
+                new_pod.command = Some(Some(vec!["/bin/sh".to_string(), "-c".to_string(), script]));
+                new_pod.arguments = Some(Some(vec![]));
+            }
+            command => {
+                new_pod.command = command.map(Some);
+                new_pod.arguments = Some(Some(arguments));
+            }
+        }
+        new_pod.environment_variables = Some(env_vars);
+        new_pod.status_requested = Some("ON".to_string());
+        new_pod.volume_mounts = Some(volume_mounts);
+        new_pod.time_to_stop_default = Some(-1);
+        new_pod.time_to_stop_instance = Some(Some(-1));
+        new_pod.networking = Some(networking);
+        new_pod.resources = Some(Box::new(resources));
+        new_pod
+    }
+
+    /// The resolved pieces of the pod's startup command: model fetches and pre-start
+    /// commands, the backend command, its arguments, and the token file (with `token_via_file`).
+    fn startup_inputs(&self) -> StartupInputs {
+        // Default startup command + default args + user extra args (from server.backend).
+        let pod_params = self.pod_params();
+        let mut arguments = pod_params.arguments.unwrap_or_default();
+        arguments.insert(0, self.model_path());
+        arguments.extend(self.raw_args().unwrap_or_default());
+        if !self.options.token_via_file {
+            arguments.push("--flexserv-token".to_string());
+            arguments.push(self.flexserv_token());
+        }
+
+        // The model fetches (if any) run before the user's pre-start commands.
+        let mut pre_start: Vec<String> = self.model_fetch_command().into_iter().collect();
+        pre_start.extend(self.extra_model_fetch_commands());
+        pre_start.extend(self.options.pre_start_commands.iter().cloned());
+
+        StartupInputs {
+            pre_start,
+            command: pod_params.command,
+            arguments,
+            token_file: self
+                .options
+                .token_via_file
+                .then(|| format!("{}/{}", FLEXSERV_MODEL_REPO, TOKEN_FILE_NAME)),
+        }
+    }
+
+    /// The shell script the pod runs at startup, fully substituted (model path, port, token,
+    /// downloads, pre-start commands). When create() would run the backend command directly
+    /// (no pre-start steps), this is the equivalent `exec` line. Empty when the backend uses
+    /// the image entrypoint. Contains secrets; see [Self::rendered_startup_script_redacted].
+    pub fn rendered_startup_script(&self) -> String {
+        let inputs = self.startup_inputs();
+        match inputs.command {
+            Some(command) if !command.is_empty() => startup_script(
+                &inputs.pre_start,
+                &command,
+                &inputs.arguments,
+                inputs.token_file.as_deref(),
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// [Self::rendered_startup_script] with the pod's secret values (FlexServ secret and token,
+    /// HF and Tapis tokens, secret-looking `env_file` entries) replaced by `[REDACTED]`, for
+    /// logs and API responses.
+    pub fn rendered_startup_script_redacted(&self) -> String {
+        let mut secrets: Vec<String> = self
+            .render_new_pod()
+            .environment_variables
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| is_secret_env_var(key))
+            .filter_map(|(_, v)| v.as_str().map(str::to_string))
+            .collect();
+        secrets.push(self.flexserv_token());
+        redact_secrets(&self.rendered_startup_script(), secrets)
+    }
+
+    /// What create() would do, from the resolved options and without any API calls: the ids,
+    /// image, model and its source, volume size, resources, a rough ready time, and what is
+    /// deleted first and then created. The size estimate from Hugging Face, revision pinning,
+    /// and the pre-create checks that need the network are not run, so create() may still fail
+    /// or pick a different volume size.
+    pub fn plan(&self) -> DeploymentPlan {
+        let pod_ids = self.pod_ids();
+        let volume_size_mb = match self.options.model_source {
+            ModelSource::HuggingFace => self.options.volume_size_mb,
+            ModelSource::TapisFiles { .. } => Some(
+                self.options
+                    .volume_size_mb
+                    .unwrap_or(DEFAULT_VOLUME_SIZE_MB),
+            ),
+        };
+        let resources = self.render_new_pod().resources.unwrap_or_default();
+        let gpus = resources.gpus.unwrap_or(0);
+
+        let mut deletes = Vec::new();
+        if self.options.cleanup_before_create {
+            deletes.extend(pod_ids.iter().map(|id| format!("pod {}", id)));
+            if !self.keeps_volume() {
+                deletes.push(format!("volume {}", self.volume_id));
+            }
+        }
+        let mut creates = vec![format!("volume {}", self.volume_id)];
+        creates.extend(pod_ids.iter().map(|id| format!("pod {}", id)));
+
+        DeploymentPlan {
+            volume_id: self.volume_id.clone(),
+            image: self.image(),
+            backend: self.server.backend.as_str().to_string(),
+            model_id: self.server.default_model.clone(),
+            model_revision: self.server.model_revision.clone(),
+            model_source: self.options.model_source.clone(),
+            volume_size_mb,
+            shared_volume: self.shares_volume(),
+            cpu_request: resources.cpu_request.unwrap_or(DEFAULT_CPU_REQUEST),
+            cpu_limit: resources.cpu_limit.unwrap_or(DEFAULT_CPU_LIMIT),
+            mem_request_mb: resources.mem_request.unwrap_or(DEFAULT_MEM_REQUEST_MB),
+            mem_limit_mb: resources.mem_limit.unwrap_or(DEFAULT_MEM_LIMIT_MB),
+            gpus,
+            estimated_ready_secs: volume_size_mb.map(|mb| {
+                crate::backend::estimate_create_duration(
+                    mb.max(0) as u64,
+                    &self.server.backend,
+                    gpus.max(0) as u32,
+                )
+                .as_secs()
+            }),
+            deletes,
+            creates,
+            pod_ids,
+        }
+    }
+
+    /// Full Pods API request body create() would send: [Self::render_new_pod] with
+    /// `raw_pod_overrides` merged in.
+    pub fn render_pod_spec(&self) -> serde_json::Value {
+        let new_pod = self.render_new_pod();
+        self.pod_request_body(&new_pod).unwrap_or_default()
+    }
+
+    /// Field-level differences between this deployment's pod spec and `other`'s (see
+    /// [Self::render_pod_spec]), e.g. to detect drift between deployed and desired specs.
+    /// `old` values come from `self`, `new` from `other`. Secrets (FlexServ secret/token, HF
+    /// token) are redacted on both sides first, so changes to them are not reported.
+    pub fn diff(&self, other: &FlexServPodDeployment) -> Vec<SpecDiff> {
+        let old = redact_pod_spec(self.render_pod_spec());
+        let new = redact_pod_spec(other.render_pod_spec());
+        crate::utils::json_diff(&old, &new)
+            .into_iter()
+            .map(|(path, old, new)| SpecDiff { path, old, new })
+            .collect()
+    }
+
+    /// `options.pre_start_commands` entries must be single non-empty lines that don't `exec`
+    /// (which would end the script before the server starts), and need a backend command to run;
+    /// so does `options.token_via_file`.
+    fn validate_pre_start_commands(&self) -> Result<(), DeploymentError> {
+        if self.options.token_via_file && !self.has_startup_command() {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "token_via_file needs a startup command; backend {} uses the image entrypoint",
+                self.server.backend.as_str()
+            )));
+        }
+        if let ModelSource::TapisFiles {
+            ref system_id,
+            ref path,
+        } = self.options.model_source
+        {
+            let bad = |s: &str| s.trim().is_empty() || s.contains(['\n', '\r', '\0']);
+            if bad(system_id) || bad(path) {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "TapisFiles model source needs a system_id and path: {:?} {:?}",
+                    system_id, path
+                )));
+            }
+            if !self.has_startup_command() {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "TapisFiles model source needs a startup command; backend {} uses the image entrypoint",
+                    self.server.backend.as_str()
+                )));
+            }
+        }
+        if self.options.pre_start_commands.is_empty() {
+            return Ok(());
+        }
+        for cmd in &self.options.pre_start_commands {
+            let trimmed = cmd.trim();
+            if trimmed.is_empty() || cmd.contains(['\n', '\r', '\0']) {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "pre_start_commands entries must be single non-empty lines: {:?}",
+                    cmd
+                )));
+            }
+            if trimmed == "exec" || trimmed.starts_with("exec ") {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "pre_start_commands must not exec (the script ends by starting the server): {:?}",
+                    cmd
+                )));
+            }
+        }
+        if !self.has_startup_command() {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "pre_start_commands need a startup command; backend {} uses the image entrypoint",
+                self.server.backend.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Startup-script step that downloads the model into the volume: a
+    /// [ModelSource::TapisFiles] model, or a Hugging Face model with `options.download_tool`.
+    /// None when the image downloads it.
+    fn model_fetch_command(&self) -> Option<String> {
+        let ModelSource::TapisFiles {
+            ref system_id,
+            ref path,
+        } = self.options.model_source
+        else {
+            let tool = self.options.download_tool?;
+            return Some(tool.fetch_command(
+                &self.server.default_model,
+                self.model_revision().as_deref(),
+                &self.model_path(),
+                &self.download_python(),
+                self.options.download_retries,
+            ));
+        };
+        let url = format!(
+            "{}/v3/files/content/{}/{}?zip=true",
+            crate::utils::canonical_tenant_url(&self.server.tenant_url),
+            crate::utils::percent_encode_path(system_id.trim()).replace('/', "%2F"),
+            crate::utils::percent_encode_path(path.trim().trim_start_matches('/'))
+        );
+        let model_dir = crate::utils::shell_quote(&self.model_path());
+        let token_file = format!("{}/{}", FLEXSERV_MODEL_REPO, TAPIS_TOKEN_FILE_NAME);
+        let fetch = format!(
+            "{{ curl -fsSL -H \"X-Tapis-Token: $(cat {token})\" {url} -o /tmp/model.zip \
+             && mkdir -p {dir} && python3 -m zipfile -e /tmp/model.zip {dir} && rm -f /tmp/model.zip; }}",
+            dir = model_dir,
+            token = token_file,
+            url = crate::utils::shell_quote(&url)
+        );
+        let cleanup = format!("rm -rf {} /tmp/model.zip", model_dir);
+        // The EXIT trap removes the token if the step fails (set -e ends the script); otherwise
+        // it is removed right after, including when the download is skipped.
+        Some(format!(
+            "trap 'rm -f {token}' EXIT; {step}; rm -f {token}; trap - EXIT",
+            token = token_file,
+            step = download_step(
+                &model_dir,
+                &fetch,
+                self.options.download_retries,
+                Some(&cleanup),
+            )
+        ))
+    }
+
+    /// Whether the backend has an explicit command a startup script can `exec`.
+    fn has_startup_command(&self) -> bool {
+        self.pod_params().command.is_some_and(|c| !c.is_empty())
+    }
+
+    /// Write the secrets the startup script reads to the model volume: the FlexServ token with
+    /// `token_via_file`, and the TAPIS token ([TAPIS_TOKEN_FILE_NAME]) for a
+    /// [ModelSource::TapisFiles] download, unless an existing volume already holds the model.
+    async fn upload_volume_secrets(
+        &self,
+        config: &configuration::Configuration,
+        new_volume: bool,
+    ) -> Result<(), DeploymentError> {
+        if self.options.token_via_file {
+            self.upload_token_file(config).await?;
+        }
+        if let ModelSource::TapisFiles { .. } = self.options.model_source {
+            if !new_volume && self.model_dir_on_volume(config).await {
+                log::info!(
+                    "Model already on volume {}, not uploading the TAPIS token",
+                    self.volume_id
+                );
+                return Ok(());
+            }
+            let token = self.current_tapis_token().await;
+            self.upload_volume_file(config, TAPIS_TOKEN_FILE_NAME, &token)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Whether the volume lists the model directory. False when the listing fails, so the
+    /// caller errs on the side of providing what the download needs.
+    async fn model_dir_on_volume(&self, config: &configuration::Configuration) -> bool {
+        let dir = self.model_dir_name();
+        match volumes_api::list_volume_files(config, &self.volume_id).await {
+            Ok(resp) => resp.result.iter().any(|f| {
+                let path = f.path.trim_matches('/');
+                path == dir || path.starts_with(&format!("{}/", dir))
+            }),
+            Err(e) => {
+                log::debug!("Could not list volume {}: {:?}", self.volume_id, e);
+                false
+            }
+        }
+    }
+
+    /// Write the FlexServ token to [TOKEN_FILE_NAME] on the model volume (`token_via_file`).
+    async fn upload_token_file(
+        &self,
+        config: &configuration::Configuration,
+    ) -> Result<(), DeploymentError> {
+        self.upload_volume_file(config, TOKEN_FILE_NAME, &self.flexserv_token())
+            .await
+    }
+
+    /// Upload `contents` as `file_name` on the model volume via the Pods volume upload endpoint.
+    /// The multipart body is built in memory, so secrets never touch the local disk.
+    async fn upload_volume_file(
+        &self,
+        config: &configuration::Configuration,
+        file_name: &str,
+        contents: &str,
+    ) -> Result<(), DeploymentError> {
+        let url = format!(
+            "{}/pods/volumes/{}/upload/{}",
+            config.base_path, self.volume_id, file_name
+        );
+        let boundary = format!("flexserv-{}", self.volume_id);
+        let body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{f}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n{contents}\r\n--{b}--\r\n",
+            b = boundary,
+            f = file_name,
+        );
+        let request = config
+            .client
+            .post(&url)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .body(body);
+        let (code, content) = self.send_pods_request(request).await?;
+        if (200..300).contains(&code) {
+            return Ok(());
+        }
+        Err(Self::map_status_error(code, content))
+    }
+
+    /// Backend parameters for the pod, with `options.server_log_level`,
+    /// `options.continuous_batching`, `options.attn_implementation`, `options.python_bin`, and
+    /// `options.backend_server_path` applied.
+    fn pod_params(&self) -> PodParameterSet {
+        let Ok(mut builder) = self.server.backend.try_transformers() else {
+            return self
+                .server
+                .backend
+                .parameter_set_builder()
+                .build_params_for_pod(&self.server);
+        };
+        if let Some(ref level) = self.options.server_log_level {
+            builder = builder.log_level(level);
+        }
+        if let Some(enabled) = self.options.continuous_batching {
+            builder = builder.continuous_batching(enabled);
+        }
+        if let Some(ref implementation) = self.options.attn_implementation {
+            builder = builder.attn_implementation(implementation);
+        }
+        let mut params = builder.build_params_for_pod(&self.server);
+        if let Some(ref mut command) = params.command {
+            // Default Transformers command: [python_bin, backend_server_path].
+            if let (Some(bin), Some(slot)) = (&self.options.python_bin, command.get_mut(0)) {
+                *slot = bin.clone();
+            }
+            if let (Some(path), Some(slot)) =
+                (&self.options.backend_server_path, command.get_mut(1))
+            {
+                *slot = path.clone();
+            }
+        }
+        params
+    }
+
+    /// `options.server_log_level` must be one of [SERVER_LOG_LEVELS] and needs a backend that
+    /// takes `--log-level`.
+    fn validate_server_log_level(&self) -> Result<(), DeploymentError> {
+        let Some(ref level) = self.options.server_log_level else {
+            return Ok(());
+        };
+        if !SERVER_LOG_LEVELS.contains(&level.as_str()) {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "server_log_level must be one of {}: {:?}",
+                SERVER_LOG_LEVELS.join(", "),
+                level
+            )));
+        }
+        if self.server.backend.try_transformers().is_err() {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "server_log_level is not supported by backend {}",
+                self.server.backend.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// `options.attn_implementation` must be one of [ATTN_IMPLEMENTATIONS]; it,
+    /// `options.continuous_batching`, `options.python_bin`, and `options.backend_server_path`
+    /// need the Transformers backend.
+    fn validate_transformers_tuning(&self) -> Result<(), DeploymentError> {
+        if let Some(ref implementation) = self.options.attn_implementation {
+            if !ATTN_IMPLEMENTATIONS.contains(&implementation.as_str()) {
+                return Err(DeploymentError::InvalidConfiguration(format!(
+                    "attn_implementation must be one of {}: {:?}",
+                    ATTN_IMPLEMENTATIONS.join(", "),
+                    implementation
+                )));
+            }
+        }
+        let requested = self.options.attn_implementation.is_some()
+            || self.options.continuous_batching.is_some();
+        if requested && self.server.backend.try_transformers().is_err() {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "continuous_batching and attn_implementation are not supported by backend {}",
+                self.server.backend.as_str()
+            )));
+        }
+        let paths = [&self.options.python_bin, &self.options.backend_server_path];
+        if paths.iter().any(|p| p.is_some()) && self.server.backend.try_transformers().is_err() {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "python_bin and backend_server_path are not supported by backend {}",
+                self.server.backend.as_str()
+            )));
+        }
+        if paths
+            .iter()
+            .flat_map(|p| p.as_deref())
+            .any(|p| p.trim().is_empty() || p.contains('\n'))
+        {
+            return Err(DeploymentError::InvalidConfiguration(
+                "python_bin and backend_server_path must be non-empty single lines".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `options.project_id` must be non-empty without whitespace when set.
+    fn validate_project_id(&self) -> Result<(), DeploymentError> {
+        match self.options.project_id {
+            Some(ref id) if id.is_empty() || id.chars().any(|c| c.is_whitespace()) => {
+                Err(DeploymentError::InvalidConfiguration(format!(
+                    "project_id must be non-empty without whitespace: {:?}",
+                    id
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Variables from `options.env_file`, in file order; empty when unset.
+    fn env_file_vars(&self) -> Result<Vec<(String, String)>, DeploymentError> {
+        let Some(ref path) = self.options.env_file else {
+            return Ok(Vec::new());
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            DeploymentError::InvalidConfiguration(format!("env_file {}: {}", path, e))
+        })?;
+        crate::utils::parse_dotenv(&text)
+            .map_err(|e| DeploymentError::InvalidConfiguration(format!("env_file {}: {}", path, e)))
+    }
+
+    /// `options.env_file` must be readable and parse; logs the loaded variables, secret values
+    /// redacted.
+    fn validate_env_file(&self) -> Result<(), DeploymentError> {
+        let vars = self.env_file_vars()?;
+        if let Some(ref path) = self.options.env_file {
+            let listed: Vec<String> = vars
+                .iter()
+                .map(|(k, v)| {
+                    if is_secret_env_var(k) {
+                        format!("{}=[REDACTED]", k)
+                    } else {
+                        format!("{}={}", k, v)
+                    }
+                })
+                .collect();
+            log::info!(
+                "Loaded {} env vars from {}: {}",
+                vars.len(),
+                path,
+                listed.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// `options.raw_args` split into words; None when unset or unsplittable.
+    fn raw_args(&self) -> Option<Vec<String>> {
+        crate::utils::shell_split(self.options.raw_args.as_deref()?)
+    }
+
+    /// `options.raw_args` must split cleanly (balanced quotes) and have no control characters,
+    /// which would break the startup script.
+    fn validate_raw_args(&self) -> Result<(), DeploymentError> {
+        let Some(ref raw) = self.options.raw_args else {
+            return Ok(());
+        };
+        if raw.chars().any(|c| c.is_control() && c != '\t') {
+            return Err(DeploymentError::InvalidConfiguration(
+                "raw_args must not contain newlines or control characters".to_string(),
+            ));
+        }
+        if self.raw_args().is_none() {
+            return Err(DeploymentError::InvalidConfiguration(format!(
+                "raw_args has an unterminated quote or escape: {:?}",
+                raw
+            )));
+        }
+        Ok(())
+    }
+
+    /// `options.model_subdir` must be a non-empty relative path with no `..` components.
+    fn validate_model_subdir(&self) -> Result<(), DeploymentError> {
+        let Some(ref dir) = self.options.model_subdir else {
+            return Ok(());
+        };
+        if is_safe_subdir(dir) {
+            Ok(())
+        } else {
+            Err(DeploymentError::InvalidConfiguration(format!(
+                "model_subdir must be a relative path without '..': {:?}",
+                dir
+            )))
+        }
+    }
+
+    /// Each of `server.extra_models` needs a valid repo id, a safe `subdir`, a single-word
+    /// revision, and a backend command for the startup script that downloads it.
+    fn validate_extra_models(&self) -> Result<(), DeploymentError> {
+        let invalid = |msg: String| Err(DeploymentError::InvalidConfiguration(msg));
+        for extra in &self.server.extra_models {
+            if let Err(e) = crate::utils::normalize_hf_model_id(&extra.repo) {
+                return invalid(format!("extra model {:?}: {}", extra.repo, e));
+            }
+            if extra.subdir.as_deref().is_some_and(|d| !is_safe_subdir(d)) {
+                return invalid(format!(
+                    "extra model {:?}: subdir must be a relative path without '..': {:?}",
+                    extra.repo, extra.subdir
+                ));
+            }
+            if extra.revision.as_deref().is_some_and(|r| {
+                r.is_empty() || r.chars().any(|c| c.is_whitespace() || c.is_control())
+            }) {
+                return invalid(format!(
+                    "extra model {:?}: invalid revision {:?}",
+                    extra.repo, extra.revision
+                ));
+            }
+        }
+        if !self.server.extra_models.is_empty() && !self.has_startup_command() {
+            return invalid(format!(
+                "extra_models need a startup command; backend {} uses the image entrypoint",
+                self.server.backend.as_str()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Models downloaded next to the main one: `server.extra_models`, plus
+    /// `server.default_embedding_model` (when not already listed and the backend has a startup
+    /// script to download it in).
+    fn extra_downloads(&self) -> Vec<ExtraModel> {
+        let mut models = self.server.extra_models.clone();
+        if let Some(ref embedding) = self.server.default_embedding_model {
+            let listed = models.iter().any(|m| &m.repo == embedding);
+            if !listed && self.has_startup_command() {
+                models.push(ExtraModel::new(embedding.clone()));
+            } else if !listed {
+                log::warn!(
+                    "Embedding model {} not downloaded: backend {} has no startup command",
+                    embedding,
+                    self.server.backend.as_str()
+                );
+            }
+        }
+        models
+    }
+
+    /// Startup-script steps that download each of [Self::extra_downloads] into
+    /// `{FLEXSERV_MODEL_REPO}/<subdir>` with `options.download_tool` (default
+    /// `huggingface-cli`, which reads `HF_TOKEN`), skipping directories that already exist.
+    fn extra_model_fetch_commands(&self) -> Vec<String> {
+        let tool = self.options.download_tool.unwrap_or(DownloadTool::HfCli);
+        let python = self.download_python();
+        self.extra_downloads()
+            .iter()
+            .map(|m| {
+                let subdir = m
+                    .subdir
+                    .as_deref()
+                    .map(|d| d.trim_end_matches('/').to_string())
+                    .unwrap_or_else(|| model_dir_name(&m.repo));
+                let dir = format!("{}/{}", FLEXSERV_MODEL_REPO, subdir);
+                tool.fetch_command(
+                    &m.repo,
+                    m.revision.as_deref(),
+                    &dir,
+                    &python,
+                    self.options.download_retries,
+                )
+            })
+            .collect()
+    }
+
+    /// Interpreter for [DownloadTool::PythonSnapshot]: the backend command's first word when it
+    /// is a Python binary (e.g. the transformers venv), otherwise `python3`.
+    fn download_python(&self) -> String {
+        self.pod_params()
+            .command
+            .and_then(|c| c.into_iter().next())
+            .filter(|bin| {
+                bin.rsplit('/')
+                    .next()
+                    .is_some_and(|name| name.starts_with("python"))
+            })
+            .unwrap_or_else(|| "python3".to_string())
+    }
+
+    /// Pod image: `options.image`, else [crate::server::default_image].
+    fn image(&self) -> String {
+        self.options
+            .image
+            .clone()
+            .unwrap_or_else(crate::server::default_image)
+    }
+
+    /// Warn (or fail, with `options.strict_image_check`) when the image is known not to
+    /// support the backend.
+    fn check_image_compatibility(&self) -> Result<(), DeploymentError> {
+        let image = self.image();
+        let backend = &self.server.backend;
+        if crate::utils::image_supports_backend(&image, backend) != Some(false) {
+            return Ok(());
+        }
+        if self.options.strict_image_check {
+            return Err(DeploymentError::IncompatibleImage {
+                image,
+                backend: backend.as_str().to_string(),
+            });
+        }
+        log::warn!(
+            "Image {} does not support backend {}; the server will likely fail to start",
+            image,
+            backend.as_str()
+        );
+        Ok(())
+    }
+
+    /// `options.download_tool` runs in the startup script, so the backend needs a command.
+    fn validate_download_tool(&self) -> Result<(), DeploymentError> {
+        match self.options.download_tool {
+            Some(tool) if !self.has_startup_command() => {
+                Err(DeploymentError::InvalidConfiguration(format!(
+                    "download_tool {:?} needs a startup command; backend {} uses the image entrypoint",
+                    tool,
+                    self.server.backend.as_str()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `description` with ` [project: <id>]` appended when `options.project_id` is set.
+    fn with_project_tag(&self, description: String) -> String {
+        match self.options.project_id {
+            Some(ref id) => format!("{} [project: {}]", description, id),
+            None => description,
+        }
+    }
+
+    /// `options.raw_pod_overrides` must be a JSON object when set.
+    fn validate_raw_pod_overrides(&self) -> Result<(), DeploymentError> {
+        match self.options.raw_pod_overrides {
+            Some(ref v) if !v.is_object() => Err(DeploymentError::InvalidConfiguration(
+                "raw_pod_overrides must be a JSON object".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Serialize `new_pod` and deep-merge `options.raw_pod_overrides` into it (overrides win).
+    fn pod_request_body(
+        &self,
+        new_pod: &models::NewPod,
+    ) -> Result<serde_json::Value, serde_json::Error> {
+        let mut body = serde_json::to_value(new_pod)?;
+        if let Some(ref overrides) = self.options.raw_pod_overrides {
+            crate::utils::deep_merge_json(&mut body, overrides);
+        }
+        Ok(body)
+    }
+
+    /// Submit a pod. Without overrides this is `pods_api::create_pod`; otherwise the merged JSON
+    /// is POSTed to `/pods` directly so fields the typed `NewPod` lacks are preserved.
+    async fn submit_pod(
+        &self,
+        config: &configuration::Configuration,
+        new_pod: models::NewPod,
+    ) -> Result<models::PodResponse, apis::Error<pods_api::CreatePodError>> {
+        if self.options.raw_pod_overrides.is_none() {
+            return pods_api::create_pod(config, new_pod).await;
+        }
+        let body = self.pod_request_body(&new_pod)?;
+        let url = format!("{}/pods", config.base_path);
+        let resp = config
+            .client
+            .post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| match e {
+                reqwest_middleware::Error::Reqwest(e) => apis::Error::Reqwest(e),
+                other => apis::Error::ReqwestMiddleware(other),
+            })?;
+        let status = resp.status();
+        let content = resp.text().await?;
+        if !status.is_success() {
+            return Err(apis::Error::ResponseError(apis::ResponseContent {
+                status,
+                content,
+                entity: None,
+            }));
+        }
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// CPU quantity to millicores: numbers are millicores, strings are Kubernetes quantities
+/// (`"250m"` = 250, `"1.5"` = 1500).
+fn parse_cpu_millicores(v: &serde_json::Value) -> Option<i64> {
+    match v {
+        serde_json::Value::Number(n) => n.as_i64(),
+        serde_json::Value::String(s) => {
+            let s = s.trim();
+            match s.strip_suffix('m') {
+                Some(milli) => milli.parse().ok(),
+                None => s.parse::<f64>().ok().map(|cores| (cores * 1000.0).round() as i64),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Memory quantity to MB: numbers are MB, strings are Kubernetes quantities (`Ki`/`Mi`/`Gi`/`Ti`,
+/// `K`/`M`/`G`/`T`, or bytes).
+fn parse_mem_mb(v: &serde_json::Value) -> Option<i64> {
+    match v {
+        serde_json::Value::Number(n) => n.as_i64(),
+        serde_json::Value::String(s) => {
+            let s = s.trim();
+            let split = s
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(s.len());
+            let (num, unit) = s.split_at(split);
+            let num: f64 = num.parse().ok()?;
+            let bytes_per_unit: f64 = match unit {
+                "" => 1.0,
+                "Ki" => 1024.0,
+                "Mi" => 1024.0 * 1024.0,
+                "Gi" => 1024.0 * 1024.0 * 1024.0,
+                "Ti" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+                "K" | "k" => 1e3,
+                "M" => 1e6,
+                "G" => 1e9,
+                "T" => 1e12,
+                _ => return None,
+            };
+            Some((num * bytes_per_unit / (1024.0 * 1024.0)).round() as i64)
+        }
+        _ => None,
+    }
+}
+
+/// Whether `dir` is a non-empty relative path with no `.`/`..` components or whitespace.
+fn is_safe_subdir(dir: &str) -> bool {
+    !dir.trim().is_empty()
+        && !dir.starts_with('/')
+        && !dir.chars().any(|c| c.is_whitespace() || c.is_control())
+        && dir.split('/').all(|part| part != ".." && part != ".")
+}
+
+/// `/bin/sh -c` script: `set -e`, each pre-start command in order, then `exec` the server so it
+/// runs as the container's main process. With `token_file`, the token argument is read from
+/// that file at startup.
+fn startup_script(
+    pre_start: &[String],
+    command: &[String],
+    arguments: &[String],
+    token_file: Option<&str>,
+) -> String {
+    let mut script = String::from("set -e\n");
+    for cmd in pre_start {
+        script.push_str(cmd.trim());
+        script.push('\n');
+    }
+    let exec_line: Vec<String> = command
+        .iter()
+        .chain(arguments)
+        .map(|w| crate::utils::shell_quote(w))
+        .collect();
+    script.push_str("exec ");
+    script.push_str(&exec_line.join(" "));
+    if let Some(path) = token_file {
+        script.push_str(&format!(
+            " --flexserv-token \"$(cat {})\"",
+            crate::utils::shell_quote(path)
+        ));
+    }
+    script.push('\n');
+    script
+}
+
+/// Resolved inputs to [startup_script] (see [FlexServPodDeployment::rendered_startup_script]).
+struct StartupInputs {
+    pre_start: Vec<String>,
+    command: Option<Vec<String>>,
+    arguments: Vec<String>,
+    token_file: Option<String>,
+}
+
+/// Replace every occurrence of `secrets` in `text` with `[REDACTED]`.
+fn redact_secrets(text: &str, mut secrets: Vec<String>) -> String {
+    secrets.retain(|s| !s.is_empty());
+    // Longest first, so a token that embeds the secret is replaced whole.
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    secrets.iter().fold(text.to_string(), |t, secret| {
+        t.replace(secret, "[REDACTED]")
+    })
+}
+
+/// Replace secret env values with `[REDACTED]`, along with any occurrence of them in `command`
+/// or `arguments` (e.g. the `--flexserv-token` argument or a startup script).
+fn redact_pod_spec(mut spec: serde_json::Value) -> serde_json::Value {
+    let mut secrets: Vec<String> = Vec::new();
+    if let Some(env) = spec
+        .get_mut("environment_variables")
+        .and_then(|v| v.as_object_mut())
+    {
+        for (key, v) in env.iter_mut() {
+            if is_secret_env_var(key) {
+                if let Some(value) = v.as_str().filter(|s| !s.is_empty()) {
+                    secrets.push(value.to_string());
+                }
+                *v = serde_json::json!("[REDACTED]");
+            }
+        }
+    }
+    for field in ["command", "arguments"] {
+        if let Some(words) = spec.get_mut(field).and_then(|v| v.as_array_mut()) {
+            for word in words.iter_mut() {
+                if let Some(text) = word.as_str() {
+                    let redacted = redact_secrets(text, secrets.clone());
+                    *word = serde_json::json!(redacted);
+                }
+            }
+        }
+    }
+    spec
+}
+
+impl FlexServPodDeployment {
+    /// POST a [DeploymentEvent] for `operation`'s outcome to `options.webhook_url`, if set.
+    /// Failures are logged only.
+    async fn notify_webhook(
+        &self,
+        operation: &str,
+        outcome: &Result<DeploymentResult, DeploymentError>,
+    ) {
+        let Some(ref url) = self.options.webhook_url else {
+            return;
+        };
+        let event = DeploymentEvent {
+            pod_id: self.pod_id.clone(),
+            operation: operation.to_string(),
+            status: outcome
+                .as_ref()
+                .ok()
+                .and_then(|r| r.as_pod())
+                .and_then(|p| p.status)
+                .map(str::to_string),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            error: outcome.as_ref().err().map(|e| e.to_string()),
+        };
+        let sent = match reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+        {
+            Ok(client) => client.post(url).json(&event).send().await,
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => log::warn!("Webhook {} returned {}", url, resp.status()),
+            Err(e) => log::warn!("Webhook {} failed: {}", url, e),
+        }
+    }
+
+    /// Span carrying the ids that correlate one operation's events.
+    #[cfg(feature = "tracing")]
+    fn operation_span(&self, op: &'static str) -> OperationSpan {
+        tracing::info_span!(
+            "flexserv_pod",
+            op,
+            pod_id = %self.pod_id,
+            volume_id = %self.volume_id,
+            tenant = %self.server.tenant_url,
+        )
+    }
+    #[cfg(not(feature = "tracing"))]
+    fn operation_span(&self, _op: &'static str) -> OperationSpan {
+        OperationSpan
+    }
+
+    async fn create_inner(&mut self) -> Result<DeploymentResult, DeploymentError> {
+        log::info!("Creating pod deployment {}", self.server.summary());
+        self.validate_raw_pod_overrides()?;
+        self.validate_pre_start_commands()?;
+        self.validate_server_log_level()?;
+        self.validate_transformers_tuning()?;
+        self.validate_project_id()?;
+        self.validate_volume_id_override()?;
+        self.validate_ids()?;
+        self.validate_model_subdir()?;
+        self.validate_raw_args()?;
+        self.validate_extra_models()?;
+        self.validate_env_file()?;
+        self.validate_download_tool()?;
+        self.check_image_compatibility()?;
+        self.options.validate(&self.server.backend)?;
+        if let Some(ref policy) = self.options.restart_policy {
+            log::warn!(
+                "restart_policy {} is not supported by the Pods API and is ignored",
+                policy
+            );
+        }
+        let config = self.pods_config().await?;
+
+        self.resolved_revision = None;
+        if self.options.pin_revision && self.options.model_source == ModelSource::HuggingFace {
+            self.resolved_revision = crate::server::resolve_model_revision(
+                &crate::server::hf_endpoint(),
+                &self.server.default_model,
+                self.server.model_revision.as_deref(),
+                self.server.hf_token.as_deref(),
+            )
+            .await;
+            match self.resolved_revision {
+                Some(ref sha) => log::info!(
+                    "Pinned {}@{} to commit {}",
+                    self.server.default_model,
+                    self.server.model_revision.as_deref().unwrap_or("default"),
+                    sha
+                ),
+                None => log::warn!(
+                    "Could not resolve revision for {}, using {:?}",
+                    self.server.default_model,
+                    self.server.model_revision
+                ),
+            }
+        }
+
+        let model_size_mb = self
+            .model_size_for_create(&crate::server::hf_endpoint())
+            .await;
+        self.check_model_size(model_size_mb)?;
+        self.check_gated_model_token(&crate::server::hf_endpoint())
+            .await?;
+
+        if self.options.cleanup_before_create {
+            self.cleanup_with(&config).await?;
+        }
+        let pod_ids = self.pod_ids();
+
+        // --- Create volume ---
+        // Hugging Face metadata only describes Hugging Face models.
+        let from_hf = self.options.model_source == ModelSource::HuggingFace;
+        let volume_size_mb = match self.options.volume_size_mb {
+            Some(mb) => mb,
+            None if !from_hf => DEFAULT_VOLUME_SIZE_MB,
+            None => model_size_mb
+                .and_then(crate::server::volume_mb_for_model_size)
+                .unwrap_or(DEFAULT_VOLUME_SIZE_MB),
+        };
+        let volume_desc = self.with_project_tag(format!(
+            "Volume for {}@{}",
+            self.server.tapis_user, self.server.default_model
+        ));
+        let new_volume = models::NewVolume {
+            volume_id: self.volume_id.clone(),
+            description: Some(volume_desc),
+            size_limit: Some(volume_size_mb),
+        };
+
+        // From here on, a failure rolls back what this call created: the volume (unless it was
+        // reused or is kept for the next create()) and the pods submitted so far.
+        let volume_created = self
+            .ensure_volume(&config, new_volume)
+            .await
+            .map_err(|e| e.at_stage(CreateStage::VolumeCreate))?;
+        let rollback_volume = volume_created && !self.keeps_volume();
+        if let Err(e) = self.upload_volume_secrets(&config, volume_created).await {
+            let rollback_errors = self.rollback_create(&config, &[], rollback_volume).await;
+            return Err(e
+                .at_stage(CreateStage::VolumeCreate)
+                .with_rollback_errors(rollback_errors));
+        }
+
+        let new_pod = self.render_new_pod();
+
+        // Log the exact Pods create_pod request body (with any raw overrides, secrets redacted).
+        if let Ok(body) = self.pod_request_body(&new_pod) {
+            log::info!(
+                "Pods create_pod request body:\n{:#}",
+                redact_pod_spec(body)
+            );
+        }
+
+        // Create one pod per replica (same spec, different id). If any fails, roll back the
+        // replicas created so far and the volume this call created.
+        let mut created = Vec::with_capacity(pod_ids.len());
+        for pod_id in &pod_ids {
+            let mut replica = new_pod.clone();
+            replica.pod_id = pod_id.clone();
+            match self.create_pod_step(&config, &replica).await {
+                Ok(resp) => created.push(resp),
+                Err(e) => {
+                    log::error!(
+                        "Pod {} creation failed, cleaning up {} replica(s)...",
+                        pod_id,
+                        created.len()
+                    );
+                    if self.keeps_volume() {
+                        log::info!("Keeping volume {} for the next create()", self.volume_id);
+                    }
+                    let rollback_errors = self
+                        .rollback_create(&config, &pod_ids[..created.len()], rollback_volume)
+                        .await;
+                    return Err(e
+                        .at_stage(CreateStage::PodCreate)
+                        .with_rollback_errors(rollback_errors));
+                }
+            }
+        }
+
+        // Store minimal info for monitoring later
+        self.pod_info = Some(
+            created
+                .iter()
+                .map(|r| format!("{:#?}", r.result))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        self.volume_info = Some(self.volume_id.clone());
+
+        let wait_url_secs = match self.options.create_mode {
+            CreateMode::Submit => self.options.wait_for_url_secs,
+            CreateMode::WaitUrl { secs } => Some(secs),
+            CreateMode::WaitReady { .. } => None,
+        };
+        let mut pod_urls = Vec::with_capacity(created.len());
+        for (pod_id, resp) in pod_ids.iter().zip(&created) {
+            let url = match (self.pod_url_from_result(&resp.result), wait_url_secs) {
+                (None, Some(secs)) => self.wait_for_pod_url(&config, pod_id, secs).await,
+                (url, _) => url,
+            };
+            pod_urls.extend(url);
+        }
+        let pod_url = pod_urls.first().cloned();
+        let status = created.first().and_then(|r| r.result.status.clone());
+
+        if let CreateMode::WaitReady { ref backoff } = self.options.create_mode {
+            return self.wait_until_ready(backoff).await;
+        }
+
+        Ok(DeploymentResult::PodResult {
+            pod_id: self.pod_id.clone(),
+            volume_id: self.volume_id.clone(),
+            pod_url,
+            pod_urls,
+            status,
+            resource_usage: None,
+            pod_info: self.pod_info.clone().unwrap_or_default(),
+            volume_info: self.volume_info.clone().unwrap_or_default(),
+            volume: None,
+            conditions: Vec::new(),
+            tapis_user: self.server.tapis_user.clone(),
+            tapis_tenant: self.server.tenant_url.clone(),
+            model_id: self.server.default_model.clone(),
+            model_revision: self.model_revision(),
+        })
+    }
+
+    async fn start_inner(&self) -> Result<DeploymentResult, DeploymentError> {
+        let config = self.pods_config().await?;
+        let mut results = Vec::new();
+        for pod_id in self.pod_ids() {
+            let pod_resp = pods_api::start_pod(&config, &pod_id)
+                .await
+                .map_err(Self::map_pods_error)?;
+            results.push(pod_resp.result);
+        }
+
+        let pod_urls: Vec<String> = results
+            .iter()
+            .filter_map(|r| self.pod_url_from_result(r))
+            .collect();
+        let pod_url = pod_urls.first().cloned();
+        let status = results.first().and_then(|r| r.status.clone());
+
+        Ok(DeploymentResult::PodResult {
+            pod_id: self.pod_id.clone(),
+            volume_id: self.volume_id.clone(),
+            pod_url,
+            pod_urls,
+            status,
+            resource_usage: None,
+            pod_info: results
+                .iter()
+                .map(|r| format!("{:#?}", r))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            volume_info: self.volume_id.clone(),
+            volume: None,
+            conditions: Vec::new(),
+            tapis_user: self.server.tapis_user.clone(),
+            tapis_tenant: self.server.tenant_url.clone(),
+            model_id: self.server.default_model.clone(),
+            model_revision: self.model_revision(),
+        })
+    }
+
+    async fn stop_inner(&self) -> Result<DeploymentResult, DeploymentError> {
+        let config = self.pods_config().await?;
+        let mut results = Vec::new();
+        for pod_id in self.pod_ids() {
+            let pod_resp = pods_api::stop_pod(&config, &pod_id)
+                .await
+                .map_err(Self::map_pods_error)?;
+            results.push(pod_resp.result);
+        }
+
+        let pod_urls: Vec<String> = results
+            .iter()
+            .filter_map(|r| self.pod_url_from_result(r))
+            .collect();
+        let pod_url = pod_urls.first().cloned();
+        let status = results.first().and_then(|r| r.status.clone());
+
+        Ok(DeploymentResult::PodResult {
+            pod_id: self.pod_id.clone(),
+            volume_id: self.volume_id.clone(),
+            pod_url,
+            pod_urls,
+            status,
+            resource_usage: None,
+            pod_info: results
+                .iter()
+                .map(|r| format!("{:#?}", r))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            volume_info: self.volume_id.clone(),
+            volume: None,
+            conditions: Vec::new(),
+            tapis_user: self.server.tapis_user.clone(),
+            tapis_tenant: self.server.tenant_url.clone(),
+            model_id: self.server.default_model.clone(),
+            model_revision: self.model_revision(),
+        })
+    }
+
+    async fn terminate_inner(&self) -> Result<DeploymentResult, DeploymentError> {
+        let (result, outcome) = self.terminate_with_outcome().await?;
+        // Partial cleanup is better than none: only fail when nothing was deleted, returning
+        // the first (pod) error. Use terminate_with_outcome() for the full picture.
+        if !outcome.pod_deleted && !outcome.volume_deleted {
+            if let Some(e) = outcome.errors.into_iter().next() {
+                return Err(e);
+            }
+        }
+        Ok(result)
+    }
+
+    async fn monitor_inner(&self) -> Result<DeploymentResult, DeploymentError> {
+        self.monitor_pods(true).await
+    }
+
+    /// Monitor result from the pods; the volume is only looked up with `include_volume`.
+    async fn monitor_pods(
+        &self,
+        include_volume: bool,
+    ) -> Result<DeploymentResult, DeploymentError> {
+        let config = self.pods_config().await?;
+
+        let mut results = Vec::new();
+        for pod_id in self.pod_ids() {
+            let pod_resp = pods_api::get_pod(&config, &pod_id, None, None)
+                .await
+                .map_err(Self::map_pods_error)?;
+            log::debug!("pods_api::get_pod result:\n{:#?}", pod_resp);
+            results.push(pod_resp.result);
+        }
+
+        let (volume_info, volume) = if !include_volume || self.volume_id.is_empty() {
+            (String::new(), None)
+        } else {
+            match volumes_api::get_volume(&config, &self.volume_id).await {
+                Ok(vol_resp) => {
+                    let volume = serde_json::to_value(&vol_resp.result)
+                        .ok()
+                        .map(|v| Self::volume_info_from_json(&v, &self.volume_id));
+                    let dump = if self.options.skip_volume_info_dump {
+                        String::new()
+                    } else {
+                        format!("{:#?}", vol_resp.result)
+                    };
+                    (dump, volume)
+                }
+                Err(_) => (String::new(), None),
+            }
+        };
+
+        let pod_info = results
+            .iter()
+            .map(|r| format!("{:#?}", r))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let pod_urls: Vec<String> = results
+            .iter()
+            .filter_map(|r| self.pod_url_from_result(r))
+            .collect();
+        let pod_url = pod_urls.first().cloned();
+        let status = results.first().and_then(|r| r.status.clone());
+        let primary = results.first().and_then(|r| serde_json::to_value(r).ok());
+        let resource_usage = primary
+            .as_ref()
+            .and_then(Self::resource_usage_from_json);
+        let conditions = primary
+            .as_ref()
+            .map(Self::conditions_from_json)
+            .unwrap_or_default();
+
+        Ok(DeploymentResult::PodResult {
+            pod_id: self.pod_id.clone(),
+            volume_id: self.volume_id.clone(),
+            pod_url,
+            pod_urls,
+            status,
+            resource_usage,
+            pod_info,
+            volume_info,
+            volume,
+            conditions,
+            tapis_user: self.server.tapis_user.clone(),
+            tapis_tenant: self.server.tenant_url.clone(),
+            model_id: self.server.default_model.clone(),
+            model_revision: self.model_revision(),
+        })
+    }
+}
+
+impl FlexServDeployment for FlexServPodDeployment {
+    async fn create(&mut self) -> Result<DeploymentResult, DeploymentError> {
+        let limit = self.options.timeouts.create;
+        let span = self.operation_span("create");
+        let outcome = in_span(span, with_timeout("create", limit, self.create_inner())).await;
+        self.notify_webhook("create", &outcome).await;
+        outcome
+    }
+
+    async fn start(&self) -> Result<DeploymentResult, DeploymentError> {
+        with_timeout("start", self.options.timeouts.start, self.start_inner()).await
+    }
+
+    async fn stop(&self) -> Result<DeploymentResult, DeploymentError> {
+        with_timeout("stop", self.options.timeouts.stop, self.stop_inner()).await
+    }
+
+    async fn terminate(&self) -> Result<DeploymentResult, DeploymentError> {
+        let limit = self.options.timeouts.terminate;
+        let span = self.operation_span("terminate");
+        let outcome = in_span(
+            span,
+            with_timeout("terminate", limit, self.terminate_inner()),
+        )
+        .await;
+        self.notify_webhook("terminate", &outcome).await;
+        outcome
+    }
+
+    async fn monitor(&self) -> Result<DeploymentResult, DeploymentError> {
+        let limit = self.options.timeouts.monitor;
+        let span = self.operation_span("monitor");
+        in_span(span, with_timeout("monitor", limit, self.monitor_inner())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Backend;
+    use crate::server::{FlexServInstance, ModelConfig, TapisConfig};
+    use crate::test_support::{
+        existing_deployment, mock_deployment, test_instance, MockServer, POD_RESPONSE_JSON,
+    };
+
+    /// True for `/v3/pods/{pod_id}...` paths; volume routes also live under `/v3/pods/`.
+    fn is_pod_path(path: &str) -> bool {
+        path.starts_with("/v3/pods/") && !path.starts_with("/v3/pods/volumes")
+    }
+
+    fn is_lowercase_alphanumeric(s: &str) -> bool {
+        s.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    }
+
+    #[test]
     fn test_pod_deployment_creation() {
         let server = FlexServInstance::new(
             "https://tacc.tapis.io".to_string(),
             "testuser".to_string(),
-            "meta-llama/Llama-2-7b".to_string(),
+            "meta-llama/Llama-2-7b".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+
+        let deployment = FlexServPodDeployment::new(server, "dummy-token".to_string());
+        assert_eq!(deployment.server.tapis_user, "testuser");
+    }
+
+    #[tokio::test]
+    async fn test_pods_config_tolerates_v3_in_tenant_url() {
+        let mut d = existing_deployment("https://tacc.tapis.io", "pabc", "vabc");
+        for url in [
+            "https://tacc.tapis.io",
+            "https://tacc.tapis.io/v3",
+            "https://tacc.tapis.io/v3/",
+        ] {
+            d.server.tenant_url = url.to_string();
+            assert_eq!(
+                d.pods_config().await.unwrap().base_path,
+                "https://tacc.tapis.io/v3",
+                "{url:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pod_deployment_with_options() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "myuser".to_string(),
+            "openai-community/gpt2".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(20 * 1024),
+            image: Some("myregistry/flexserv:2.0".to_string()),
+            cpu_request: Some(2000),
+            flexserv_secret: Some("mysecret".to_string()),
+            ..Default::default()
+        };
+        let deployment = FlexServPodDeployment::with_options(server, "token".to_string(), options);
+        assert_eq!(deployment.server.tapis_user, "myuser");
+        assert_eq!(deployment.options.volume_size_mb, Some(20 * 1024));
+        assert_eq!(
+            deployment.options.image.as_deref(),
+            Some("myregistry/flexserv:2.0")
+        );
+        assert_eq!(deployment.options.cpu_request, Some(2000));
+        assert_eq!(
+            deployment.options.flexserv_secret.as_deref(),
+            Some("mysecret")
+        );
+    }
+
+    #[test]
+    fn test_backend_resource_hints_fill_unset_options() {
+        let server = |backend: Backend| {
+            FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "u".to_string(),
+                "org/model".to_string(),
+                None,
+                None,
+                None,
+                backend,
+            )
+        };
+        let d = FlexServPodDeployment::new(
+            server(Backend::VLlm { command: vec![] }),
+            "token".to_string(),
+        );
+        let resources = d.render_new_pod().resources.unwrap();
+        assert_eq!(resources.gpus, Some(1));
+        assert_eq!(resources.cpu_request, Some(4000));
+        assert_eq!(resources.cpu_limit, Some(8000));
+        assert_eq!(resources.mem_request, Some(32768));
+        assert_eq!(resources.mem_limit, Some(65536));
+        assert!(d.options.validate(&d.server.backend).unwrap().is_empty());
+
+        // Explicit options win, including an explicit 0 GPUs; limits stay at or above requests.
+        let options = PodDeploymentOptions {
+            gpus: Some(0),
+            cpu_request: Some(16000),
+            mem_limit_mb: Some(40000),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(
+            server(Backend::TrtLlm { command: vec![] }),
+            "token".to_string(),
+            options,
+        );
+        assert_eq!(d.options.gpus, Some(0));
+        assert_eq!(d.options.cpu_request, Some(16000));
+        assert_eq!(d.options.cpu_limit, Some(16000));
+        assert_eq!(d.options.mem_request_mb, Some(49152));
+        assert_eq!(d.options.mem_limit_mb, Some(40000));
+
+        // Transformers keeps the general defaults.
+        let d = FlexServPodDeployment::new(
+            server(Backend::Transformers { command: vec![] }),
+            "token".to_string(),
+        );
+        let resources = d.render_new_pod().resources.unwrap();
+        assert_eq!(resources.gpus, Some(0));
+        assert_eq!(resources.cpu_request, Some(DEFAULT_CPU_REQUEST));
+        assert_eq!(resources.cpu_limit, Some(DEFAULT_CPU_LIMIT));
+        assert_eq!(resources.mem_request, Some(DEFAULT_MEM_REQUEST_MB));
+        assert_eq!(resources.mem_limit, Some(DEFAULT_MEM_LIMIT_MB));
+
+        // Reattaching gets the same hints as creating.
+        let d = FlexServPodDeployment::from_existing(
+            server(Backend::VLlm { command: vec![] }),
+            "token".to_string(),
+            "pabc".to_string(),
+            "vabc".to_string(),
+        );
+        assert_eq!(d.options.gpus, Some(1));
+        assert_eq!(d.options.mem_request_mb, Some(32768));
+    }
+
+    #[test]
+    fn test_from_configs() {
+        let tapis = TapisConfig {
+            tenant_url: "https://tacc.tapis.io".to_string(),
+            tapis_user: "u".to_string(),
+            tapis_token: "jwt".to_string(),
+            token_provider: None,
+        };
+        let model = ModelConfig {
+            model_id: "openai-community/gpt2".to_string(),
+            model_revision: None,
+            hf_token: None,
+            default_embedding_model: None,
+            extra_models: Vec::new(),
+        };
+        let deployment = FlexServPodDeployment::from_configs(
+            tapis,
+            model,
+            Backend::Transformers { command: vec![] },
+            PodDeploymentOptions::default(),
+        );
+        assert_eq!(deployment.server.tapis_user, "u");
+        assert_eq!(deployment.server.default_model, "openai-community/gpt2");
+        assert_eq!(deployment.tapis_token, "jwt");
+    }
+
+    #[test]
+    fn test_create_deployment_ok() {
+        let deployment = FlexServPodDeployment::create_deployment(
+            "https://tacc.tapis.io".to_string(),
+            "myuser".to_string(),
+            "token".to_string(),
+            "openai-community/gpt2".to_string(),
+            None,
+            Backend::Transformers { command: vec![] },
+        )
+        .unwrap();
+        assert_eq!(deployment.server.tapis_user, "myuser");
+        assert_eq!(deployment.server.default_model, "openai-community/gpt2");
+    }
+
+    #[test]
+    fn test_create_deployment_validation_fails() {
+        let err = FlexServPodDeployment::create_deployment(
+            "not-a-url".to_string(),
+            "u".to_string(),
+            "token".to_string(),
+            "gpt2".to_string(),
+            None,
+            Backend::Transformers { command: vec![] },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidTenantUrl(_)));
+    }
+
+    #[test]
+    fn test_pod_id_volume_id_format() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "testuser".to_string(),
+            "no-model-yet".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let deployment = FlexServPodDeployment::new(server, "dummy-token".to_string());
+        assert!(
+            deployment.pod_id.starts_with('p'),
+            "pod_id should start with p"
+        );
+        assert!(
+            deployment.volume_id.starts_with('v'),
+            "volume_id should start with v"
+        );
+        assert!(
+            is_lowercase_alphanumeric(&deployment.pod_id),
+            "pod_id must be lowercase alphanumeric"
+        );
+        assert!(
+            is_lowercase_alphanumeric(&deployment.volume_id),
+            "volume_id must be lowercase alphanumeric"
+        );
+    }
+
+    #[test]
+    fn test_pod_id_volume_id_stable() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "user1".to_string(),
+            "model-a".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let d1 = FlexServPodDeployment::new(server, "token".to_string());
+        let server2 = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "user1".to_string(),
+            "model-a".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let d2 = FlexServPodDeployment::new(server2, "token".to_string());
+        assert_eq!(d1.pod_id, d2.pod_id);
+        assert_eq!(d1.volume_id, d2.volume_id);
+    }
+
+    #[test]
+    fn test_pod_id_volume_id_from_deployment_id() {
+        let make_server = || {
+            FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "user1".to_string(),
+                "openai-community/gpt2".to_string(),
+                None,
+                None,
+                None,
+                Backend::Transformers { command: vec![] },
+            )
+        };
+        let uuid1 = "550e8400-e29b-41d4-a716-446655440000";
+        let uuid2 = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+        let opts1 = PodDeploymentOptions {
+            deployment_id: Some(uuid1.to_string()),
+            ..Default::default()
+        };
+        let opts2 = PodDeploymentOptions {
+            deployment_id: Some(uuid2.to_string()),
+            ..Default::default()
+        };
+        let d1 = FlexServPodDeployment::with_options(make_server(), "token".to_string(), opts1);
+        let d2 = FlexServPodDeployment::with_options(make_server(), "token".to_string(), opts2);
+        assert_eq!(d1.pod_id, "p550e8400e29b41d4a716446655440000");
+        assert_eq!(d1.volume_id, "v550e8400e29b41d4a716446655440000");
+        assert_eq!(d2.pod_id, "p6ba7b8109dad11d180b400c04fd430c8");
+        assert_eq!(d2.volume_id, "v6ba7b8109dad11d180b400c04fd430c8");
+        assert_ne!(d1.pod_id, d2.pod_id);
+        assert!(is_lowercase_alphanumeric(
+            d1.pod_id.strip_prefix('p').unwrap()
+        ));
+        assert!(is_lowercase_alphanumeric(
+            d1.volume_id.strip_prefix('v').unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_pod_deployment_new_optional_fields_none() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "u".to_string(),
+            "m".to_string(),
             None,
             None,
             None,
             Backend::Transformers { command: vec![] },
         );
+        let d = FlexServPodDeployment::new(server, "token".to_string());
+        assert!(d.volume_info.is_none());
+        assert!(d.pod_info.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_terminate_dry_run_makes_no_delete_calls() {
+        let mock = MockServer::start(|req| {
+            if req.path.starts_with("/v3/pods/volumes/") {
+                (
+                    404,
+                    r#"{"message":"volume not found","status":"error","result":null}"#.to_string(),
+                )
+            } else {
+                (200, POD_RESPONSE_JSON.to_string())
+            }
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let result = d.terminate_dry_run().await.unwrap();
+        let methods = mock.methods();
+        assert!(methods.len() >= 2);
+        assert!(methods.iter().all(|m| m == "GET"), "{methods:?}");
+        let view = result.as_pod().unwrap();
+        assert_eq!(view.status, Some("AVAILABLE"));
+        assert!(view.pod_info.starts_with("DRY RUN"));
+        assert!(view.pod_info.contains("would delete pod pabc: exists"));
+        assert!(view
+            .pod_info
+            .contains("would delete volume vabc: not found"));
+    }
+
+    #[test]
+    fn test_from_existing_preserves_supplied_ids() {
+        let server = || test_instance("https://example.tapis.io", "m");
+        // Ids as created with a deployment_id, reattached with and without it.
+        let created = FlexServPodDeployment::with_options(
+            server(),
+            "token".to_string(),
+            PodDeploymentOptions {
+                deployment_id: Some("d-1".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(created.pod_id, "pd1");
+        for options in [
+            PodDeploymentOptions::default(),
+            PodDeploymentOptions {
+                deployment_id: Some("d-1".to_string()),
+                ..Default::default()
+            },
+            PodDeploymentOptions {
+                deployment_id: Some("other".to_string()),
+                ..Default::default()
+            },
+        ] {
+            let d = FlexServPodDeployment::from_existing_with_options(
+                server(),
+                "token".to_string(),
+                created.pod_id.clone(),
+                created.volume_id.clone(),
+                options,
+            );
+            assert_eq!(d.pod_id, "pd1");
+            assert_eq!(d.volume_id, "vd1");
+        }
+        let d = FlexServPodDeployment::from_existing(
+            server(),
+            "token".to_string(),
+            "pd1".to_string(),
+            "vd1".to_string(),
+        );
+        assert_eq!((d.pod_id.as_str(), d.volume_id.as_str()), ("pd1", "vd1"));
+    }
+
+    #[tokio::test]
+    async fn test_monitor_issues_only_get_requests() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let _ = d.monitor().await;
+        let methods = mock.methods();
+        assert!(!methods.is_empty(), "monitor() should hit the Pods API");
+        assert!(
+            methods.iter().all(|m| m == "GET"),
+            "monitor() must only issue GET requests: {methods:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pods_config_rejects_invalid_ca_cert() {
+        let server = test_instance("https://tapis.example.org", "m");
+        let options = PodDeploymentOptions {
+            ca_cert_pem: Some("not a certificate".to_string()),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(server, "token".to_string(), options);
+        match d.pods_config().await {
+            Err(DeploymentError::InvalidConfiguration(msg)) => assert!(msg.contains("ca_cert_pem")),
+            Err(e) => panic!("expected InvalidConfiguration, got {:?}", e),
+            Ok(_) => panic!("expected invalid ca_cert_pem to be rejected"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pods_config_default_tls_and_dev_override() {
+        let make = |danger: bool| {
+            let server = test_instance("https://tapis.example.org", "m");
+            let options = PodDeploymentOptions {
+                danger_accept_invalid_certs: danger,
+                ..Default::default()
+            };
+            FlexServPodDeployment::with_options(server, "token".to_string(), options)
+        };
+        assert!(!PodDeploymentOptions::default().danger_accept_invalid_certs);
+        assert!(make(false).pods_config().await.is_ok());
+        assert!(make(true).pods_config().await.is_ok());
+    }
+
+    #[test]
+    fn test_pod_ids_single_replica() {
+        let server = test_instance("https://tacc.tapis.io", "m");
+        let d = FlexServPodDeployment::new(server, "token".to_string());
+        assert_eq!(d.pod_ids(), vec![d.pod_id.clone()]);
+    }
+
+    #[test]
+    fn test_pod_ids_multiple_replicas() {
+        let server = test_instance("https://tacc.tapis.io", "m");
+        let options = PodDeploymentOptions {
+            deployment_id: Some("abc".to_string()),
+            replicas: Some(12),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(server, "token".to_string(), options);
+        let ids = d.pod_ids();
+        assert_eq!(ids.len(), 12);
+        assert_eq!(ids[0], "pabc0");
+        assert_eq!(ids[10], "pabca");
+        assert_eq!(ids[11], "pabcb");
+        assert_eq!(d.volume_id, "vabc");
+        assert!(ids.iter().all(|id| is_lowercase_alphanumeric(id)));
+    }
+
+    #[test]
+    fn test_resource_usage_from_json() {
+        let pod = serde_json::json!({
+            "pod_id": "pabc",
+            "status": "AVAILABLE",
+            "status_container": {
+                "phase": "Running",
+                "usage": {"cpu": "250m", "memory": "2Gi", "gpu_percent": 37.5}
+            }
+        });
+        let usage = FlexServPodDeployment::resource_usage_from_json(&pod).unwrap();
+        assert_eq!(usage.cpu_millicores, Some(250));
+        assert_eq!(usage.mem_mb, Some(2048));
+        assert_eq!(usage.gpu_percent, Some(37.5));
+
+        let top_level = serde_json::json!({"resource_usage": {"cpu_millicores": 1500, "mem_mb": 512}});
+        let usage = FlexServPodDeployment::resource_usage_from_json(&top_level).unwrap();
+        assert_eq!(usage.cpu_millicores, Some(1500));
+        assert_eq!(usage.mem_mb, Some(512));
+        assert_eq!(usage.gpu_percent, None);
+    }
+
+    #[test]
+    fn test_resource_usage_absent_is_none() {
+        let pod = serde_json::json!({"pod_id": "pabc", "status_container": {"phase": "Running"}});
+        assert!(FlexServPodDeployment::resource_usage_from_json(&pod).is_none());
+        let empty = serde_json::json!({"usage": {}});
+        assert!(FlexServPodDeployment::resource_usage_from_json(&empty).is_none());
+    }
+
+    #[test]
+    fn test_volume_info_from_json() {
+        let volume = serde_json::json!({
+            "volume_id": "vabc",
+            "description": "model volume",
+            "size_limit": 20480,
+            "size": "19Gi",
+            "status": "AVAILABLE"
+        });
+        assert_eq!(
+            FlexServPodDeployment::volume_info_from_json(&volume, "unused"),
+            VolumeInfo {
+                id: "vabc".to_string(),
+                size_limit_mb: Some(20480),
+                size_used_mb: Some(19456),
+                status: Some("AVAILABLE".to_string()),
+            }
+        );
+        let sparse = serde_json::json!({"description": "no sizes"});
+        assert_eq!(
+            FlexServPodDeployment::volume_info_from_json(&sparse, "vfallback"),
+            VolumeInfo {
+                id: "vfallback".to_string(),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_conditions_from_json() {
+        let pod = serde_json::json!({
+            "pod_id": "pabc",
+            "status": "SPAWNING",
+            "status_container": {
+                "phase": "Pending",
+                "conditions": [
+                    {"type": "PodScheduled", "status": "True", "lastTransitionTime": "2024-05-01T10:00:00Z"},
+                    {"type": "ContainersReady", "status": "False", "reason": "ContainersNotReady",
+                     "message": "containers with unready status: [pabc]", "lastTransitionTime": "2024-05-01T10:00:05Z"}
+                ],
+                "events": [
+                    {"reason": "BackOff", "message": "Back-off pulling image \"tapis/flexserv:9.9\"",
+                     "last_timestamp": "2024-05-01T10:02:00Z"},
+                    {"reason": "Failed", "message": "Error: ImagePullBackOff",
+                     "last_timestamp": "2024-05-01T10:01:00Z"},
+                    {"count": 3}
+                ]
+            }
+        });
+        let conditions = FlexServPodDeployment::conditions_from_json(&pod);
+        let reasons: Vec<&str> = conditions.iter().map(|c| c.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            ["PodScheduled", "ContainersNotReady", "Failed", "BackOff"]
+        );
+        assert_eq!(
+            conditions[2],
+            PodCondition {
+                reason: "Failed".to_string(),
+                message: "Error: ImagePullBackOff".to_string(),
+                timestamp: Some("2024-05-01T10:01:00Z".to_string()),
+            }
+        );
+        assert!(conditions[0].message.is_empty());
+
+        let plain = serde_json::json!({"pod_id": "pabc", "status": "AVAILABLE"});
+        assert!(FlexServPodDeployment::conditions_from_json(&plain).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_monitor_reports_pod_conditions() {
+        let mock = MockServer::start(|req| {
+            if req.path == "/v3/pods/pabc" {
+                let body = serde_json::json!({
+                    "message": "ok", "metadata": {}, "status": "success", "version": "dev",
+                    "result": {
+                        "pod_id": "pabc", "image": "tapis/flexserv:1.0", "status": "SPAWNING",
+                        "status_container": {"phase": "Pending", "events": [
+                            {"reason": "FailedScheduling", "message": "0/4 nodes are available: 4 Insufficient nvidia.com/gpu.",
+                             "timestamp": "2024-05-01T10:00:00Z"}
+                        ]}
+                    }
+                });
+                (200, body.to_string())
+            } else {
+                (404, "{}".to_string())
+            }
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let result = d.monitor_pod_only().await.unwrap();
+        let conditions = result.as_pod().unwrap().conditions;
+        assert_eq!(conditions.len(), 1);
+        assert_eq!(conditions[0].reason, "FailedScheduling");
+        assert!(conditions[0]
+            .message
+            .contains("Insufficient nvidia.com/gpu"));
+        assert_eq!(
+            result.to_api_json()["conditions"][0]["reason"],
+            "FailedScheduling"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_monitor_attaches_volume_details() {
+        let mock = MockServer::start(|req| {
+            if req.path.starts_with("/v3/pods/volumes/") {
+                (
+                    200,
+                    r#"{"message":"ok","metadata":{},"result":{"volume_id":"vabc","description":"","size_limit":1024,"size":0,"status":"AVAILABLE","creation_ts":"2020-01-01T00:00:00Z","update_ts":"2020-01-01T00:00:00Z"},"status":"success","version":"dev"}"#
+                        .to_string(),
+                )
+            } else {
+                (200, POD_RESPONSE_JSON.to_string())
+            }
+        });
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let result = d.monitor().await.unwrap();
+        let pod = result.as_pod().unwrap();
+        let volume = pod.volume.unwrap();
+        assert_eq!(volume.id, "vabc");
+        assert_eq!(volume.size_limit_mb, Some(1024));
+        assert_eq!(volume.status.as_deref(), Some("AVAILABLE"));
+        assert!(!pod.volume_info.is_empty());
+
+        d.options.skip_volume_info_dump = true;
+        let result = d.monitor().await.unwrap();
+        assert!(result.as_pod().unwrap().volume_info.is_empty());
+        assert!(result.as_pod().unwrap().volume.is_some());
+    }
+
+    fn make_override_deployment(overrides: serde_json::Value) -> FlexServPodDeployment {
+        let server = test_instance("https://tacc.tapis.io", "m");
+        let options = PodDeploymentOptions {
+            raw_pod_overrides: Some(overrides),
+            ..Default::default()
+        };
+        FlexServPodDeployment::with_options(server, "token".to_string(), options)
+    }
+
+    #[test]
+    fn test_raw_pod_overrides_must_be_object() {
+        let d = make_override_deployment(serde_json::json!(["not", "an", "object"]));
+        assert!(matches!(
+            d.validate_raw_pod_overrides(),
+            Err(DeploymentError::InvalidConfiguration(_))
+        ));
+        let d = make_override_deployment(serde_json::json!({}));
+        assert!(d.validate_raw_pod_overrides().is_ok());
+    }
+
+    #[test]
+    fn test_raw_pod_overrides_deep_merge_wins() {
+        let d = make_override_deployment(serde_json::json!({
+            "image": "custom/image:2",
+            "resources": {"gpus": 2},
+            "tolerations": [{"key": "gpu", "operator": "Exists"}]
+        }));
+        let mut new_pod = models::NewPod::new("pabc".to_string());
+        new_pod.image = Some("tapis/flexserv:1.0".to_string());
+        let mut resources = models::ModelsPodsResources::new();
+        resources.cpu_limit = Some(2000);
+        resources.gpus = Some(0);
+        new_pod.resources = Some(Box::new(resources));
+
+        let body = d.pod_request_body(&new_pod).unwrap();
+        assert_eq!(body["pod_id"], "pabc");
+        assert_eq!(body["image"], "custom/image:2");
+        assert_eq!(body["resources"]["gpus"], 2);
+        assert_eq!(body["resources"]["cpu_limit"], 2000);
+        assert_eq!(body["tolerations"][0]["key"], "gpu");
+    }
+
+    #[test]
+    fn test_restart_policy_validated_not_sent() {
+        let mut d = make_override_deployment(serde_json::json!({}));
+        let backend = d.server.backend.clone();
+        assert!(d.render_pod_spec().get("restart_policy").is_none());
+        for policy in RESTART_POLICIES {
+            d.options.restart_policy = Some(policy.to_string());
+            assert!(d.options.validate(&backend).is_ok());
+            assert!(d.render_pod_spec().get("restart_policy").is_none());
+        }
+        for bad in ["always", "Sometimes", ""] {
+            d.options.restart_policy = Some(bad.to_string());
+            assert!(matches!(
+                d.options.validate(&backend),
+                Err(DeploymentError::InvalidConfiguration(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_ready_surfaces_api_errors() {
+        let mock = MockServer::start(|_| (500, r#"{"message":"boom","status":"error"}"#.to_string()));
+        let d = existing_deployment(&mock.base_url, "pabc", "");
+        let err = d.wait_until_ready(&BackoffConfig::default()).await.unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisInternalServerError(ref m) if m == "boom"));
+        assert_eq!(mock.methods().len(), 1, "API errors should not be retried");
+    }
+
+    /// Mock whose `get_pod` answers with each of `statuses` in turn (the last one repeats).
+    fn status_sequence_mock(statuses: &'static [&'static str]) -> MockServer {
+        let gets = std::sync::atomic::AtomicUsize::new(0);
+        MockServer::start(move |req| {
+            if req.method != "GET" || !is_pod_path(&req.path) {
+                return (
+                    404,
+                    r#"{"message":"not found","status":"error"}"#.to_string(),
+                );
+            }
+            let i = gets.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let status = statuses[i.min(statuses.len() - 1)];
+            (200, POD_RESPONSE_JSON.replace("AVAILABLE", status))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_webhook_receives_lifecycle_events() {
+        let webhook = MockServer::start(|_| (200, String::new()));
+        let tapis = MockServer::start(|req| {
+            if req.path.starts_with("/v3/pods/") {
+                (
+                    403,
+                    r#"{"message":"no access","status":"error"}"#.to_string(),
+                )
+            } else {
+                (500, r#"{"message":"busy","status":"error"}"#.to_string())
+            }
+        });
+        let mut d = existing_deployment(&tapis.base_url, "pabc", "vabc");
+        d.options.webhook_url = Some(format!("{}/hooks/flexserv", webhook.base_url));
+        assert!(d.terminate().await.is_err());
+
+        let reqs = webhook.requests();
+        assert_eq!(reqs.len(), 1);
+        assert_eq!(reqs[0].method, "POST");
+        assert_eq!(reqs[0].path, "/hooks/flexserv");
+        let event: DeploymentEvent = serde_json::from_str(&reqs[0].body).unwrap();
+        assert_eq!(event.pod_id, "pabc");
+        assert_eq!(event.operation, "terminate");
+        assert_eq!(event.status, None);
+        assert!(event.timestamp > 0);
+        assert!(event.error.unwrap().contains("no access"));
+
+        // An unreachable webhook doesn't change the outcome.
+        let tapis = MockServer::start(|_| {
+            (
+                200,
+                r#"{"message":"ok","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            )
+        });
+        let mut d = existing_deployment(&tapis.base_url, "pabc", "vabc");
+        d.options.webhook_url = Some("http://127.0.0.1:1/hook".to_string());
+        assert!(d.terminate().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_token_provider_used_per_operation() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.volume_id = String::new();
+        d.options.token_provider = Some(TokenProvider::from_fn(move || {
+            let n = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move { Ok(format!("jwt-{}", n)) }
+        }));
+        d.monitor().await.unwrap();
+        d.monitor().await.unwrap();
+        let tokens: Vec<String> = mock
+            .requests()
+            .iter()
+            .filter_map(|r| r.header("x-tapis-token").map(str::to_string))
+            .collect();
+        assert_eq!(tokens, vec!["jwt-0", "jwt-1"]);
+
+        d.options.token_provider = Some(TokenProvider::from_fn(|| async {
+            Err(DeploymentError::TapisAuthFailed(
+                "refresh failed".to_string(),
+            ))
+        }));
+        assert!(matches!(
+            d.monitor().await,
+            Err(DeploymentError::TapisAuthFailed(ref m)) if m == "refresh failed"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_tapis_reachable() {
+        let volumes = r#"{"message":"Volumes retrieved.","metadata":{},"result":[],"status":"success","version":"dev"}"#;
+        let mock = MockServer::start(move |_| (200, volumes.to_string()));
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.check_tapis_reachable().await.unwrap();
+        let reqs = mock.requests();
+        assert_eq!(reqs.len(), 1);
+        assert_eq!(reqs[0].method, "GET");
+        assert_eq!(reqs[0].path, "/v3/pods/volumes");
+
+        // A 404 (e.g. a proxy in front of the wrong host) is not reachable.
+        let mock = MockServer::start(|_| {
+            (
+                404,
+                r#"{"message":"Not found.","status":"error"}"#.to_string(),
+            )
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        assert!(d.check_tapis_reachable().await.is_err());
+
+        // Neither is a 2xx that is not a TAPIS envelope.
+        let mock = MockServer::start(|_| (200, r#"{"ok":true}"#.to_string()));
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        assert!(d.check_tapis_reachable().await.is_err());
+
+        let mock = MockServer::start(|_| {
+            (
+                401,
+                r#"{"message":"Invalid JWT.","status":"error"}"#.to_string(),
+            )
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let err = d.check_tapis_reachable().await.unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisAuthFailed(ref m) if m == "Invalid JWT."));
+
+        let d = existing_deployment("http://127.0.0.1:1", "pabc", "vabc");
+        assert!(d.check_tapis_reachable().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_status_reaches_target() {
+        let mock = status_sequence_mock(&["RUNNING", "STOPPING", "STOPPED"]);
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let status = d
+            .wait_for_status(PodStatus::Stopped, Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(status, PodStatus::Stopped);
+        let pod_gets = mock
+            .requests()
+            .iter()
+            .filter(|r| r.path == "/v3/pods/pabc")
+            .count();
+        assert_eq!(pod_gets, 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_status_stops_on_terminal() {
+        let mock = status_sequence_mock(&["SPAWNING", "FAILED"]);
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let status = d
+            .wait_for_status(PodStatus::Available, Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(status, PodStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_status_times_out() {
+        let mock = status_sequence_mock(&["SPAWNING"]);
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let err = d
+            .wait_for_status(PodStatus::Available, Duration::from_millis(800))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisTimeout(ref m) if m.contains("AVAILABLE")));
+    }
+
+    #[test]
+    fn test_long_and_symbol_deployment_ids() {
+        let make = |deployment_id: &str, id_prefix: Option<&str>| {
+            let server = FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "user1".to_string(),
+                "openai-community/gpt2".to_string(),
+                None,
+                None,
+                None,
+                Backend::Transformers { command: vec![] },
+            );
+            let options = PodDeploymentOptions {
+                deployment_id: Some(deployment_id.to_string()),
+                id_prefix: id_prefix.map(str::to_string),
+                ..Default::default()
+            };
+            FlexServPodDeployment::with_options(server, "token".to_string(), options)
+        };
+
+        let long_a = "a".repeat(200);
+        let long_b = format!("{}b", "a".repeat(199));
+        let a = make(&long_a, None);
+        let b = make(&long_b, None);
+        assert_eq!(a.pod_id.len(), MAX_TAPIS_ID_LEN);
+        assert_eq!(a.volume_id.len(), MAX_TAPIS_ID_LEN);
+        assert!(a.pod_id.starts_with("paaaa"));
+        assert!(is_lowercase_alphanumeric(&a.pod_id));
+        assert_ne!(a.pod_id, b.pod_id);
+        assert_eq!(a.pod_id, make(&long_a, None).pod_id);
+        assert_eq!(a.pod_id[1..], a.volume_id[1..]);
+
+        let prefixed = make(&long_a, Some(&"t".repeat(100)));
+        assert_eq!(prefixed.pod_id.len(), MAX_TAPIS_ID_LEN);
+
+        // Replica ids leave room for their index.
+        let mut replicated = make(&long_a, None);
+        replicated.options.replicas = Some(40);
+        replicated.set_model("openai-community/gpt2");
+        let ids = replicated.pod_ids();
+        assert!(ids.iter().all(|id| id.len() <= MAX_TAPIS_ID_LEN), "{ids:?}");
+        assert_eq!(ids[39].len(), MAX_TAPIS_ID_LEN);
+        assert!(prefixed
+            .pod_id
+            .starts_with(&format!("p{}a", "t".repeat(MAX_TAPIS_ID_LEN / 2))));
+
+        // A UUID fits unchanged.
+        let uuid = make("550e8400-e29b-41d4-a716-446655440000", None);
+        assert_eq!(uuid.pod_id, "p550e8400e29b41d4a716446655440000");
+
+        // No alphanumerics: the config hash is used, as without a deployment_id.
+        let symbols = make("-_-!@#", None);
+        let plain = FlexServPodDeployment::new(
+            FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "user1".to_string(),
+                "openai-community/gpt2".to_string(),
+                None,
+                None,
+                None,
+                Backend::Transformers { command: vec![] },
+            ),
+            "token".to_string(),
+        );
+        assert_eq!(symbols.pod_id, plain.pod_id);
+    }
+
+    #[test]
+    fn test_id_prefix_applied() {
+        let make_server = || {
+            FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "user1".to_string(),
+                "openai-community/gpt2".to_string(),
+                None,
+                None,
+                None,
+                Backend::Transformers { command: vec![] },
+            )
+        };
+        let options = PodDeploymentOptions {
+            deployment_id: Some("550e8400-e29b-41d4-a716-446655440000".to_string()),
+            id_prefix: Some("Team-A".to_string()),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        assert_eq!(d.pod_id, "pteama550e8400e29b41d4a716446655440000");
+        assert_eq!(d.volume_id, "vteama550e8400e29b41d4a716446655440000");
+
+        let options = PodDeploymentOptions {
+            id_prefix: Some("team".to_string()),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        let plain = FlexServPodDeployment::new(make_server(), "token".to_string());
+        assert!(d.pod_id.starts_with("pteam"));
+        assert!(d.volume_id.starts_with("vteam"));
+        assert_eq!(&d.pod_id[5..], &plain.pod_id[1..]);
+        assert!(is_lowercase_alphanumeric(&d.pod_id));
+        assert!(is_lowercase_alphanumeric(&d.volume_id));
+    }
+
+    #[test]
+    fn test_id_strategy_default_and_custom() {
+        let make_server = || {
+            FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "User1".to_string(),
+                "openai-community/gpt2".to_string(),
+                None,
+                None,
+                None,
+                Backend::Transformers { command: vec![] },
+            )
+        };
+        let default = FlexServPodDeployment::new(make_server(), "token".to_string());
+        assert_eq!(
+            default.pod_id,
+            format!("p{}", make_server().deployment_hash().to_lowercase())
+        );
+        assert!(default.validate_ids().is_ok());
+
+        // user + model + timestamp, after the usual marker and prefix.
+        let options = PodDeploymentOptions {
+            id_prefix: Some("team".to_string()),
+            id_strategy: IdStrategy::custom(|server, _| {
+                let user = crate::utils::normalize_to_lowercase_alphanumeric(&server.tapis_user);
+                let model =
+                    crate::utils::normalize_to_lowercase_alphanumeric(&server.default_model);
+                format!("{}{}{}", user, model, 1700000000)
+            }),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        assert_eq!(d.pod_id, "pteamuser1openaicommunitygpt21700000000");
+        assert_eq!(d.volume_id, "vteamuser1openaicommunitygpt21700000000");
+        assert!(d.validate_ids().is_ok());
+        assert_eq!(format!("{:?}", d.options.id_strategy), "Custom(..)");
+
+        // Custom output is not normalized or shortened, so bad ids are rejected.
+        let options = PodDeploymentOptions {
+            id_strategy: IdStrategy::custom(|server, _| server.tapis_user.clone()),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        assert_eq!(d.pod_id, "pUser1");
+        assert!(matches!(
+            d.validate_ids(),
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.contains("pod id")
+        ));
+        let options = PodDeploymentOptions {
+            id_strategy: IdStrategy::custom(|_, _| "x".repeat(MAX_TAPIS_ID_LEN)),
+            ..Default::default()
+        };
+        let d = FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        assert!(d.validate_ids().is_err());
+
+        // A pod id that only overflows once a replica index is appended is rejected up front.
+        let options = PodDeploymentOptions {
+            id_strategy: IdStrategy::custom(|_, _| "x".repeat(MAX_TAPIS_ID_LEN - 1)),
+            ..Default::default()
+        };
+        let mut d =
+            FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        assert!(d.validate_ids().is_ok());
+        d.options.replicas = Some(2);
+        assert!(matches!(
+            d.validate_ids(),
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.contains(&format!("{}0", d.pod_id))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_gated_model_without_token() {
+        let hub = MockServer::start(|req| {
+            if req.path.starts_with("/api/models/org/model") {
+                (200, r#"{"id":"org/model","gated":"manual"}"#.to_string())
+            } else {
+                (200, r#"{"id":"org/open","gated":false}"#.to_string())
+            }
+        });
+        let mut d = make_pre_start_deployment(vec![]);
+        if std::env::var("HF_TOKEN").is_ok() {
+            // The env token counts as a token, so there is nothing to check.
+            return;
+        }
+
+        // Advisory by default: no Hub call, no error.
+        assert!(d.check_gated_model_token(&hub.base_url).await.is_ok());
+        assert!(hub.requests().is_empty());
+
+        d.options.require_hf_token_for_gated = true;
+        let err = d.check_gated_model_token(&hub.base_url).await.unwrap_err();
+        assert!(
+            matches!(err, DeploymentError::InvalidConfiguration(ref m) if m.contains("org/model is a gated")),
+            "{}",
+            err
+        );
+        assert_eq!(hub.requests()[0].header("authorization"), None);
+
+        d.server.default_model = "org/open".to_string();
+        assert!(d.check_gated_model_token(&hub.base_url).await.is_ok());
+
+        // With a token the Hub is not asked.
+        d.server.default_model = "org/model".to_string();
+        d.server.hf_token = Some("hf_x".to_string());
+        let before = hub.requests().len();
+        assert!(d.check_gated_model_token(&hub.base_url).await.is_ok());
+        assert_eq!(hub.requests().len(), before);
+
+        // Hub unreachable: fall back to the built-in list.
+        let down = MockServer::start(|_| (503, "{}".to_string()));
+        d.server.hf_token = None;
+        assert!(d.check_gated_model_token(&down.base_url).await.is_ok());
+        d.server.default_model = "meta-llama/Llama-3.1-8B".to_string();
+        assert!(d.check_gated_model_token(&down.base_url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_max_model_size_guard() {
+        // 3 GB of weights plus a config file: 3073 MB.
+        let hub = MockServer::start(|_| {
+            (
+                200,
+                r#"{"siblings":[{"rfilename":"model.safetensors","size":3221225472},{"rfilename":"config.json","size":1024}]}"#
+                    .to_string(),
+            )
+        });
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.volume_size_mb = Some(2048);
+        assert_eq!(d.model_size_for_create(&hub.base_url).await, None);
+        assert!(
+            hub.requests().is_empty(),
+            "no lookup without max_model_size_mb or an estimated volume"
+        );
+        assert!(d.check_model_size(Some(3073)).is_ok());
+
+        d.options.max_model_size_mb = Some(100_000);
+        let size = d.model_size_for_create(&hub.base_url).await;
+        assert_eq!(size, Some(3073));
+        assert!(hub.requests()[0].path.starts_with("/api/models/org/model"));
+        let err = d.check_model_size(size).unwrap_err();
+        assert!(
+            matches!(err, DeploymentError::PodCreationFailed(ref m)
+                if m == "model org/model is 3073 MB, larger than the 2048 MB volume"),
+            "{}",
+            err
+        );
+
+        // The lookup uses the resolved revision, not the requested one.
+        d.resolved_revision = Some("abc123".to_string());
+        d.model_size_for_create(&hub.base_url).await;
+        assert!(hub
+            .requests()
+            .last()
+            .unwrap()
+            .path
+            .starts_with("/api/models/org/model/revision/abc123"));
+
+        d.options.max_model_size_mb = Some(1024);
+        d.options.volume_size_mb = None;
+        let err = d.check_model_size(size).unwrap_err();
+        assert!(matches!(err, DeploymentError::PodCreationFailed(ref m)
+            if m == "model org/model is 3073 MB, over max_model_size_mb 1024 MB"));
+
+        d.options.max_model_size_mb = Some(100_000);
+        d.options.volume_size_mb = Some(10240);
+        assert!(d.check_model_size(size).is_ok());
+
+        // Unknown size: the check is skipped.
+        let down = MockServer::start(|_| (404, "{}".to_string()));
+        d.options.volume_size_mb = Some(1);
+        let size = d.model_size_for_create(&down.base_url).await;
+        assert_eq!(size, None);
+        assert!(d.check_model_size(size).is_ok());
+    }
+
+    #[test]
+    fn test_is_already_exists_keys_off_409() {
+        let conflict =
+            apis::Error::<volumes_api::CreateVolumeError>::ResponseError(apis::ResponseContent {
+                status: reqwest::StatusCode::CONFLICT,
+                content: r#"{"message":"duplicate key","status":"error"}"#.to_string(),
+                entity: None,
+            });
+        assert!(is_already_exists(&conflict));
+        let bad_request =
+            apis::Error::<pods_api::CreatePodError>::ResponseError(apis::ResponseContent {
+                status: reqwest::StatusCode::BAD_REQUEST,
+                content: r#"{"message":"Pod already exists","status":"error"}"#.to_string(),
+                entity: None,
+            });
+        assert!(!is_already_exists(&bad_request));
+    }
+
+    #[tokio::test]
+    async fn test_pod_conflict_is_returned() {
+        let mock = MockServer::start(|req| match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => {
+                (409, r#"{"message":"exists","status":"error"}"#.to_string())
+            }
+            ("POST", "/v3/pods") => (409, r#"{"message":"exists","status":"error"}"#.to_string()),
+            _ => (
+                404,
+                r#"{"message":"not found","status":"error"}"#.to_string(),
+            ),
+        });
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(1024),
+            reuse_volume_on_pod_failure: true,
+            ..Default::default()
+        };
+        let mut d = mock_deployment(&mock.base_url, options);
+        let err = d.create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::PodCreate));
+
+        let reqs = mock.requests();
+        let posts = reqs
+            .iter()
+            .filter(|r| r.method == "POST" && r.path == "/v3/pods")
+            .count();
+        assert_eq!(posts, 1);
+        assert!(!reqs.iter().any(|r| r.method == "DELETE"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_url_polls_get_pod() {
+        let gets = std::sync::atomic::AtomicUsize::new(0);
+        let mock = MockServer::start(move |req| match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => {
+                (409, r#"{"message":"exists","status":"error"}"#.to_string())
+            }
+            ("POST", "/v3/pods") => (200, POD_RESPONSE_JSON.to_string()),
+            ("GET", _) => {
+                if gets.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    (200, POD_RESPONSE_JSON.to_string())
+                } else {
+                    (
+                        200,
+                        r#"{"message":"ok","metadata":{},"result":{"pod_id":"pabc","image":"tapis/flexserv:1.0","status":"AVAILABLE","networking":{"default":{"protocol":"http","port":8000,"url":"pabc.pods.tacc.tapis.io"}}},"status":"success","version":"dev"}"#
+                            .to_string(),
+                    )
+                }
+            }
+            _ => (
+                404,
+                r#"{"message":"not found","status":"error"}"#.to_string(),
+            ),
+        });
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(1024),
+            reuse_volume_on_pod_failure: true,
+            wait_for_url_secs: Some(30),
+            ..Default::default()
+        };
+        let mut d = mock_deployment(&mock.base_url, options);
+        let result = d.create().await.unwrap();
+        assert_eq!(result.pod_url(), Some("pabc.pods.tacc.tapis.io"));
+        let gets = mock
+            .requests()
+            .iter()
+            .filter(|r| r.method == "GET" && r.path == format!("/v3/pods/{}", d.pod_id))
+            .count();
+        assert_eq!(gets, 2);
+
+        // Without the option, create() returns immediately with no URL.
+        d.options.wait_for_url_secs = None;
+        let result = d.create().await.unwrap();
+        assert_eq!(result.pod_url(), None);
+    }
+
+    /// Pods API mock for create(): volume exists, pod submitted as SUBMITTED, and `get_pod`
+    /// answering with each of `statuses` in turn (the last one repeats).
+    fn create_mode_mock(statuses: &'static [&'static str]) -> MockServer {
+        let gets = std::sync::atomic::AtomicUsize::new(0);
+        MockServer::start(move |req| match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => {
+                (409, r#"{"message":"exists","status":"error"}"#.to_string())
+            }
+            ("POST", "/v3/pods") => (200, POD_RESPONSE_JSON.replace("AVAILABLE", "SUBMITTED")),
+            ("GET", path) if is_pod_path(path) => {
+                let i = gets.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let status = statuses[i.min(statuses.len() - 1)];
+                let body = format!(
+                    r#"{{"message":"ok","metadata":{{}},"result":{{"pod_id":"pabc","image":"tapis/flexserv:1.0","status":"{}","networking":{{"default":{{"protocol":"http","port":8000,"url":"pabc.pods.tacc.tapis.io"}}}}}},"status":"success","version":"dev"}}"#,
+                    status
+                );
+                (200, body)
+            }
+            _ => (
+                404,
+                r#"{"message":"not found","status":"error"}"#.to_string(),
+            ),
+        })
+    }
+
+    fn make_create_mode_deployment(
+        base_url: &str,
+        create_mode: CreateMode,
+    ) -> FlexServPodDeployment {
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(1024),
+            reuse_volume_on_pod_failure: true,
+            create_mode,
+            ..Default::default()
+        };
+        mock_deployment(base_url, options)
+    }
+
+    fn fast_backoff() -> BackoffConfig {
+        BackoffConfig {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(10),
+            jitter: 0.0,
+            max_attempts: Some(5),
+            ..Default::default()
+        }
+    }
+
+    fn pod_gets(mock: &MockServer) -> usize {
+        mock.requests()
+            .iter()
+            .filter(|r| r.method == "GET" && is_pod_path(&r.path))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_project_id_threaded_into_create_calls() {
+        let mock = create_mode_mock(&["AVAILABLE"]);
+        let mut d = make_create_mode_deployment(&mock.base_url, CreateMode::Submit);
+        d.options.project_id = Some("proj-42".to_string());
+        d.create().await.unwrap();
+
+        let reqs = mock.requests();
+        let body_of = |path: &str| -> serde_json::Value {
+            let req = reqs
+                .iter()
+                .find(|r| r.method == "POST" && r.path == path)
+                .unwrap();
+            serde_json::from_str(&req.body).unwrap()
+        };
+        let pod = body_of("/v3/pods");
+        assert!(pod["description"]
+            .as_str()
+            .unwrap()
+            .ends_with("[project: proj-42]"));
+        assert_eq!(pod["environment_variables"]["TAPIS_PROJECT_ID"], "proj-42");
+        let volume = body_of("/v3/pods/volumes");
+        assert!(volume["description"]
+            .as_str()
+            .unwrap()
+            .ends_with("[project: proj-42]"));
+
+        d.options.project_id = Some("my project".to_string());
+        assert!(matches!(
+            d.create().await,
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.contains("project_id")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_mode_submit_returns_immediately() {
+        let mock = create_mode_mock(&["AVAILABLE"]);
+        let mut d = make_create_mode_deployment(&mock.base_url, CreateMode::Submit);
+        let result = d.create().await.unwrap();
+        assert_eq!(result.as_pod().unwrap().status, Some("SUBMITTED"));
+        assert_eq!(result.pod_url(), None);
+        assert_eq!(pod_gets(&mock), 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_mode_wait_url() {
+        let mock = create_mode_mock(&["SPAWNING"]);
+        let mut d = make_create_mode_deployment(&mock.base_url, CreateMode::WaitUrl { secs: 30 });
+        let result = d.create().await.unwrap();
+        assert_eq!(result.pod_url(), Some("pabc.pods.tacc.tapis.io"));
+        // The URL is enough; the pod need not be ready.
+        assert_eq!(result.as_pod().unwrap().status, Some("SUBMITTED"));
+        assert_eq!(pod_gets(&mock), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_mode_wait_ready() {
+        let mock = create_mode_mock(&["SPAWNING", "SPAWNING", "AVAILABLE"]);
+        let mode = CreateMode::WaitReady {
+            backoff: fast_backoff(),
+        };
+        let mut d = make_create_mode_deployment(&mock.base_url, mode);
+        let result = d.create().await.unwrap();
+        assert_eq!(result.as_pod().unwrap().status, Some("AVAILABLE"));
+        assert_eq!(result.pod_url(), Some("pabc.pods.tacc.tapis.io"));
+        assert_eq!(pod_gets(&mock), 3);
+
+        let mock = create_mode_mock(&["SPAWNING", "ERROR"]);
+        let mode = CreateMode::WaitReady {
+            backoff: fast_backoff(),
+        };
+        let mut d = make_create_mode_deployment(&mock.base_url, mode);
+        let err = d.create().await.unwrap_err();
+        assert!(matches!(err, DeploymentError::PodCreationFailed(ref m) if m.contains("ERROR")));
+    }
+
+    #[tokio::test]
+    async fn test_create_failure_reports_stage() {
+        let make = |base_url: &str| {
+            let options = PodDeploymentOptions {
+                volume_size_mb: Some(1024),
+                ..Default::default()
+            };
+            mock_deployment(base_url, options)
+        };
+
+        let cleanup = MockServer::start(|req| match req.method.as_str() {
+            "DELETE" => (
+                401,
+                r#"{"message":"bad token","status":"error"}"#.to_string(),
+            ),
+            _ => (
+                404,
+                r#"{"message":"not found","status":"error"}"#.to_string(),
+            ),
+        });
+        let mut d = make(&cleanup.base_url);
+        d.options.cleanup_before_create = true;
+        let err = d.create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::Cleanup));
+        assert!(matches!(
+            err.root_cause(),
+            DeploymentError::TapisAuthFailed(_)
+        ));
+
+        let volume = MockServer::start(|req| match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => (
+                400,
+                r#"{"message":"size too big","status":"error"}"#.to_string(),
+            ),
+            _ => (
+                404,
+                r#"{"message":"not found","status":"error"}"#.to_string(),
+            ),
+        });
+        let err = make(&volume.base_url).create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::VolumeCreate));
+        assert!(
+            matches!(err.root_cause(), DeploymentError::TapisBadRequest(ref m) if m == "size too big")
+        );
+        assert!(!volume.requests().iter().any(|r| r.path == "/v3/pods"));
+    }
+
+    #[tokio::test]
+    async fn test_reuse_volume_on_pod_failure_keeps_volume() {
+        let mock = MockServer::start(|req| match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => (
+                409,
+                r#"{"message":"Volume already exists","status":"error"}"#.to_string(),
+            ),
+            ("POST", "/v3/pods") => (
+                503,
+                r#"{"message":"try again","status":"error"}"#.to_string(),
+            ),
+            _ => (404, r#"{"message":"not found","status":"error"}"#.to_string()),
+        });
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(1024),
+            reuse_volume_on_pod_failure: true,
+            ..Default::default()
+        };
+        let mut d = mock_deployment(&mock.base_url, options);
+        let err = d.create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::PodCreate));
+        assert!(
+            matches!(err.root_cause(), DeploymentError::TapisInternalServerError(ref m) if m == "try again")
+        );
+
+        let reqs = mock.requests();
+        let pod_posts = reqs
+            .iter()
+            .filter(|r| r.method == "POST" && r.path == "/v3/pods")
+            .count();
+        assert_eq!(pod_posts, 3, "retryable pod failures should be retried");
+        assert!(
+            !reqs
+                .iter()
+                .any(|r| r.method == "DELETE" && r.path.starts_with("/v3/pods/volumes")),
+            "volume must be kept for the next create()"
+        );
+    }
+
+    /// Create mock where the volume is created, the first pod POST succeeds and later ones fail
+    /// with 400; volume DELETEs answer `volume_delete_status`.
+    fn rollback_mock(volume_delete_status: u16) -> MockServer {
+        let pod_posts = std::sync::atomic::AtomicUsize::new(0);
+        MockServer::start(move |req| {
+            match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => (
+                200,
+                r#"{"message":"ok","metadata":{},"result":{"volume_id":"vabc","description":"","size_limit":1024,"size":0,"status":"AVAILABLE","creation_ts":"2020-01-01T00:00:00Z","update_ts":"2020-01-01T00:00:00Z"},"status":"success","version":"dev"}"#
+                    .to_string(),
+            ),
+            ("POST", "/v3/pods") => {
+                if pod_posts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    (200, POD_RESPONSE_JSON.to_string())
+                } else {
+                    (
+                        400,
+                        r#"{"message":"bad spec","status":"error"}"#.to_string(),
+                    )
+                }
+            }
+            ("DELETE", path) if path.starts_with("/v3/pods/volumes/") => (
+                volume_delete_status,
+                r#"{"message":"deleted","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            ),
+            _ => (
+                404,
+                r#"{"message":"not found","status":"error"}"#.to_string(),
+            ),
+        }
+        })
+    }
+
+    fn make_rollback_deployment(base_url: &str) -> FlexServPodDeployment {
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(1024),
+            replicas: Some(2),
+            ..Default::default()
+        };
+        mock_deployment(base_url, options)
+    }
+
+    /// (method, path) of the requests sent after the last pod POST, i.e. the rollback.
+    fn requests_after_last_pod_post(mock: &MockServer) -> Vec<(String, String)> {
+        let reqs = mock.requests();
+        let last_post = reqs
+            .iter()
+            .rposition(|r| r.method == "POST" && r.path == "/v3/pods")
+            .expect("a pod POST");
+        reqs[last_post + 1..]
+            .iter()
+            .map(|r| (r.method.clone(), r.path.clone()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_create_rolls_back_pods_and_volume_on_pod_failure() {
+        let mock = rollback_mock(200);
+        let mut d = make_rollback_deployment(&mock.base_url);
+        let ids = d.pod_ids();
+        let err = d.create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::PodCreate));
+        assert!(
+            matches!(err.root_cause(), DeploymentError::TapisBadRequest(ref m) if m == "bad spec")
+        );
+        assert!(err.rollback_errors().is_empty());
+        assert!(!err.to_string().contains("rollback failed"));
+
+        // Only the replica that was created and the volume are deleted, never the failed replica.
+        assert_eq!(
+            requests_after_last_pod_post(&mock),
+            vec![
+                ("DELETE".to_string(), format!("/v3/pods/{}", ids[0])),
+                ("DELETE".to_string(), format!("/v3/pods/volumes/{}", d.volume_id)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_reports_failed_rollback() {
+        let mock = rollback_mock(500);
+        let mut d = make_rollback_deployment(&mock.base_url);
+        let err = d.create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::PodCreate));
+        assert!(
+            matches!(err.root_cause(), DeploymentError::TapisBadRequest(ref m) if m == "bad spec")
+        );
+        let rollback = err.rollback_errors();
+        assert_eq!(rollback.len(), 1, "{:?}", rollback);
+        assert!(rollback[0].starts_with(&format!("volume {}", d.volume_id)));
+        assert!(err.to_string().contains("rollback failed"));
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["CreateFailed"]["rollback_errors"][0], rollback[0]);
+    }
+
+    /// Pods API mock where every call succeeds; pod GETs and POSTs return [POD_RESPONSE_JSON].
+    fn create_ok_mock() -> MockServer {
+        MockServer::start(|req| {
+            match (req.method.as_str(), req.path.as_str()) {
+            ("POST", "/v3/pods/volumes") => (
+                200,
+                r#"{"message":"ok","metadata":{},"result":{"volume_id":"vabc","description":"","size_limit":1024,"size":0,"status":"AVAILABLE","creation_ts":"2020-01-01T00:00:00Z","update_ts":"2020-01-01T00:00:00Z"},"status":"success","version":"dev"}"#
+                    .to_string(),
+            ),
+            ("POST", "/v3/pods") | ("GET", _) => (200, POD_RESPONSE_JSON.to_string()),
+            _ => (
+                200,
+                r#"{"message":"deleted","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            ),
+        }
+        })
+    }
+
+    fn method_paths(mock: &MockServer) -> Vec<(String, String)> {
+        mock.requests()
+            .iter()
+            .map(|r| (r.method.clone(), r.path.clone()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_deletes_pods_then_volume() {
+        let mock = create_ok_mock();
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.cleanup().await.unwrap();
+        assert_eq!(
+            method_paths(&mock),
+            vec![
+                ("DELETE".to_string(), "/v3/pods/pabc".to_string()),
+                ("DELETE".to_string(), "/v3/pods/volumes/vabc".to_string()),
+            ]
+        );
+
+        // A missing pod is fine; other pod errors fail the Cleanup stage.
+        let missing = MockServer::start(|_| (404, r#"{"message":"not found"}"#.to_string()));
+        assert!(existing_deployment(&missing.base_url, "pabc", "vabc")
+            .cleanup()
+            .await
+            .is_ok());
+        let denied = MockServer::start(|_| (401, r#"{"message":"bad token"}"#.to_string()));
+        let err = existing_deployment(&denied.base_url, "pabc", "vabc")
+            .cleanup()
+            .await
+            .unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::Cleanup));
+    }
+
+    #[tokio::test]
+    async fn test_create_skips_cleanup_by_default() {
+        let mock = create_ok_mock();
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.volume_size_mb = Some(1024);
+        d.create().await.unwrap();
+        let requests = method_paths(&mock);
+        assert!(
+            !requests.iter().any(|(m, _)| m == "DELETE"),
+            "{:?}",
+            requests
+        );
+        assert_eq!(
+            requests[0],
+            ("POST".to_string(), "/v3/pods/volumes".to_string())
+        );
+        assert!(requests.contains(&("POST".to_string(), "/v3/pods".to_string())));
+
+        // cleanup_before_create deletes the leftovers first.
+        let mock = create_ok_mock();
+        d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.volume_size_mb = Some(1024);
+        d.options.cleanup_before_create = true;
+        d.create().await.unwrap();
+        assert_eq!(
+            method_paths(&mock)[..2],
+            [
+                ("DELETE".to_string(), "/v3/pods/pabc".to_string()),
+                ("DELETE".to_string(), "/v3/pods/volumes/vabc".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_over_live_deployment_fails_without_deleting() {
+        let mock = MockServer::start(|req| match req.method.as_str() {
+            "POST" => (409, r#"{"message":"exists","status":"error"}"#.to_string()),
+            _ => (200, POD_RESPONSE_JSON.to_string()),
+        });
+        let options = PodDeploymentOptions {
+            volume_size_mb: Some(1024),
+            ..Default::default()
+        };
+        let mut d = mock_deployment(&mock.base_url, options);
+        let err = d.create().await.unwrap_err();
+        assert_eq!(err.stage(), Some(CreateStage::VolumeCreate));
+        let requests = method_paths(&mock);
+        assert!(
+            !requests.iter().any(|(m, _)| m == "DELETE"),
+            "{:?}",
+            requests
+        );
+        assert!(!requests.contains(&("POST".to_string(), "/v3/pods".to_string())));
+    }
+
+    #[test]
+    fn test_plan_reflects_options_and_flags_pre_delete() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.image = Some("registry.example/flexserv:2.0".to_string());
+        d.options.volume_size_mb = Some(2048);
+        d.options.replicas = Some(2);
+        d.options.gpus = Some(1);
+        d.server.model_revision = Some("v1".to_string());
+        let plan = d.plan();
+        assert_eq!(plan.pod_ids, d.pod_ids());
+        assert_eq!(plan.pod_ids.len(), 2);
+        assert_eq!(plan.volume_id, d.volume_id);
+        assert_eq!(plan.image, "registry.example/flexserv:2.0");
+        assert_eq!(plan.backend, "transformers");
+        assert_eq!(plan.model_id, "org/model");
+        assert_eq!(plan.model_revision.as_deref(), Some("v1"));
+        assert_eq!(plan.model_source, ModelSource::HuggingFace);
+        assert_eq!(plan.volume_size_mb, Some(2048));
+        assert_eq!(plan.gpus, 1);
+        assert_eq!(plan.cpu_request, DEFAULT_CPU_REQUEST);
+        assert!(plan.estimated_ready_secs.is_some_and(|s| s > 0));
+        assert!(!plan.is_destructive());
+
+        // With cleanup_before_create, create() deletes every replica, then the volume.
+        d.options.cleanup_before_create = true;
+        let plan = d.plan();
+        assert!(plan.is_destructive());
+        let mut expected: Vec<String> =
+            d.pod_ids().iter().map(|id| format!("pod {}", id)).collect();
+        expected.push(format!("volume {}", d.volume_id));
+        assert_eq!(plan.deletes, expected);
+        assert_eq!(plan.creates[0], format!("volume {}", d.volume_id));
+        assert_eq!(plan.creates.len(), 3);
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["volume_size_mb"], 2048);
+        assert_eq!(json["deletes"].as_array().unwrap().len(), 3);
+
+        // A shared volume is never deleted; without cleanup_before_create nothing is.
+        d.options.volume_id_override = Some("vshared".to_string());
+        d.volume_id = "vshared".to_string();
+        let plan = d.plan();
+        assert!(plan.shared_volume);
+        assert!(!plan.deletes.iter().any(|r| r.starts_with("volume")));
+        d.options.cleanup_before_create = false;
+        assert!(!d.plan().is_destructive());
+
+        // Without an explicit size the Hugging Face estimate is left to create().
+        d.options.volume_size_mb = None;
+        let plan = d.plan();
+        assert_eq!(plan.volume_size_mb, None);
+        assert_eq!(plan.estimated_ready_secs, None);
+    }
+
+    #[tokio::test]
+    async fn test_with_replica_ids_terminates_recorded_pods() {
+        let mock = create_ok_mock();
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc")
+            .with_replica_ids(vec!["pabc".to_string(), "pold7".to_string()]);
+        assert_eq!(d.pod_ids(), ["pabc", "pold7"]);
+        d.terminate().await.unwrap();
+        let deletes: Vec<String> = mock
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "DELETE")
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(
+            deletes,
+            ["/v3/pods/pabc", "/v3/pods/pold7", "/v3/pods/volumes/vabc"]
+        );
+
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc").with_replica_ids(vec![]);
+        assert_eq!(d.pod_ids(), ["pabc"]);
+        d = d.with_replica_ids(vec!["pold7".to_string()]);
+        d.set_model("other/model");
+        assert_eq!(d.pod_ids(), [d.pod_id.clone()]);
+    }
+
+    #[tokio::test]
+    async fn test_create_uses_explicit_ids() {
+        let mock = create_ok_mock();
+        let server = test_instance(&mock.base_url, "m");
+        let options = PodDeploymentOptions {
+            deployment_id: Some("0f8e2c1a-derived-id".to_string()),
+            volume_size_mb: Some(1024),
+            cleanup_before_create: true,
+            ..Default::default()
+        };
+        let mut d = FlexServPodDeployment::with_explicit_ids(
+            server,
+            "token".to_string(),
+            "extpod1".to_string(),
+            "extvol1".to_string(),
+            options,
+        )
+        .unwrap();
+        d.create().await.unwrap();
+        let requests = mock.requests();
+        let posted = |path: &str| -> serde_json::Value {
+            let req = requests
+                .iter()
+                .find(|r| r.method == "POST" && r.path == path)
+                .unwrap();
+            serde_json::from_str(&req.body).unwrap()
+        };
+        assert_eq!(posted("/v3/pods/volumes")["volume_id"], "extvol1");
+        assert_eq!(posted("/v3/pods")["pod_id"], "extpod1");
+        assert_eq!(
+            posted("/v3/pods")["volume_mounts"]["/app/models"]["source_id"],
+            "extvol1"
+        );
+        assert!(requests
+            .iter()
+            .any(|r| r.method == "DELETE" && r.path == "/v3/pods/extpod1"));
+
+        d.set_model("other/model");
+        assert_eq!(
+            (d.pod_id.as_str(), d.volume_id.as_str()),
+            ("extpod1", "extvol1")
+        );
+    }
+
+    #[test]
+    fn test_explicit_ids_rejected_when_invalid() {
+        let make = |pod_id: &str, volume_id: &str, options: PodDeploymentOptions| {
+            FlexServPodDeployment::with_explicit_ids(
+                make_pre_start_deployment(vec![]).server,
+                "token".to_string(),
+                pod_id.to_string(),
+                volume_id.to_string(),
+                options,
+            )
+        };
+        let err = make("Pod-1", "vabc", PodDeploymentOptions::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("pod id must be 1-") && err.to_string().contains("\"Pod-1\""),
+            "{err}"
+        );
+        let long = "v".repeat(MAX_TAPIS_ID_LEN + 1);
+        assert!(matches!(
+            make("pabc", &long, PodDeploymentOptions::default()),
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.starts_with("volume id")
+        ));
+        assert!(make("pabc", "", PodDeploymentOptions::default()).is_err());
 
-        let deployment = FlexServPodDeployment::new(server, "dummy-token".to_string());
-        assert_eq!(deployment.server.tapis_user, "testuser");
+        let shared = PodDeploymentOptions {
+            volume_id_override: Some("vshared".to_string()),
+            ..Default::default()
+        };
+        assert!(make("pabc", "vother", shared.clone()).is_err());
+        assert!(make("pabc", "vshared", shared).is_ok());
     }
 
-    #[test]
-    fn test_pod_deployment_with_options() {
-        let server = FlexServInstance::new(
-            "https://tacc.tapis.io".to_string(),
-            "myuser".to_string(),
-            "openai-community/gpt2".to_string(),
-            None,
-            None,
-            None,
-            Backend::Transformers { command: vec![] },
+    #[tokio::test]
+    async fn test_recreate_cleans_up_once_then_creates() {
+        let mock = create_ok_mock();
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.volume_size_mb = Some(1024);
+        d.recreate().await.unwrap();
+        let requests = method_paths(&mock);
+        assert_eq!(
+            requests[..4],
+            [
+                ("DELETE".to_string(), "/v3/pods/pabc".to_string()),
+                ("DELETE".to_string(), "/v3/pods/volumes/vabc".to_string()),
+                ("POST".to_string(), "/v3/pods/volumes".to_string()),
+                ("POST".to_string(), "/v3/pods".to_string()),
+            ]
+        );
+        assert_eq!(
+            requests.iter().filter(|(m, _)| m == "DELETE").count(),
+            2,
+            "{:?}",
+            requests
         );
+        assert!(!d.options.cleanup_before_create);
+    }
+
+    #[test]
+    fn test_model_revision_prefers_pinned_sha() {
+        let mut server = test_instance("https://tacc.tapis.io", "org/model");
+        server.model_revision = Some("main".to_string());
+        let mut d = FlexServPodDeployment::new(server, "token".to_string());
+        assert_eq!(d.model_revision().as_deref(), Some("main"));
+        d.resolved_revision = Some("0123abcd".to_string());
+        assert_eq!(d.model_revision().as_deref(), Some("0123abcd"));
+    }
+
+    #[tokio::test]
+    async fn test_http_client_supplied_or_cached() {
+        let make_server = || test_instance("https://tapis.example.org", "m");
+        // A supplied client is used as-is, so TLS options are not re-applied.
         let options = PodDeploymentOptions {
-            volume_size_mb: Some(20 * 1024),
-            image: Some("myregistry/flexserv:2.0".to_string()),
-            cpu_request: Some(2000),
-            flexserv_secret: Some("mysecret".to_string()),
+            http_client: Some(crate::deployment::shared_tapis_client(
+                "token",
+                None,
+                false,
+                None,
+                &Default::default(),
+            )
+            .unwrap()),
+            ca_cert_pem: Some("not a certificate".to_string()),
             ..Default::default()
         };
-        let deployment = FlexServPodDeployment::with_options(server, "token".to_string(), options);
-        assert_eq!(deployment.server.tapis_user, "myuser");
-        assert_eq!(deployment.options.volume_size_mb, Some(20 * 1024));
+        let d = FlexServPodDeployment::with_options(make_server(), "token".to_string(), options);
+        assert!(d.pods_config().await.is_ok());
+        assert!(d.client.get().is_none());
+
+        // Otherwise the client is built once and reused.
+        let d = FlexServPodDeployment::new(make_server(), "token".to_string());
+        assert!(d.client.get().is_none());
+        d.pods_config().await.unwrap();
+        assert!(d.client.get().is_some());
+        d.pods_config().await.unwrap();
+    }
+
+    #[test]
+    fn test_diff_reports_changed_fields_and_redacts_secrets() {
+        let make = |options| mock_deployment("https://tacc.tapis.io", options);
+        let base = PodDeploymentOptions {
+            deployment_id: Some("d1".to_string()),
+            image: Some("tapis/flexserv:1.0".to_string()),
+            flexserv_secret: Some("s1-".to_string()),
+            ..Default::default()
+        };
+        let current = make(base.clone());
+        assert!(current.diff(&make(base.clone())).is_empty());
+
+        let desired = make(PodDeploymentOptions {
+            image: Some("tapis/flexserv:2.0".to_string()),
+            gpus: Some(1),
+            flexserv_secret: Some("s2-".to_string()),
+            ..base
+        });
+        let diff = current.diff(&desired);
+        let paths: Vec<&str> = diff.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["/image", "/resources/gpus"]);
+        assert_eq!(diff[0].old, Some(serde_json::json!("tapis/flexserv:1.0")));
+        assert_eq!(diff[0].new, Some(serde_json::json!("tapis/flexserv:2.0")));
+
+        let spec = redact_pod_spec(current.render_pod_spec());
         assert_eq!(
-            deployment.options.image.as_deref(),
-            Some("myregistry/flexserv:2.0")
+            spec["environment_variables"]["FLEXSERV_SECRET"],
+            "[REDACTED]"
         );
-        assert_eq!(deployment.options.cpu_request, Some(2000));
+        assert!(!spec.to_string().contains("s1-"));
+    }
+
+    fn make_pre_start_deployment(pre_start_commands: Vec<String>) -> FlexServPodDeployment {
+        let options = PodDeploymentOptions {
+            flexserv_secret: Some("s-".to_string()),
+            pre_start_commands,
+            ..Default::default()
+        };
+        mock_deployment("https://tacc.tapis.io", options)
+    }
+
+    #[test]
+    fn test_pre_start_commands_rendered_in_order() {
+        let d = make_pre_start_deployment(vec![
+            "ulimit -n 65536".to_string(),
+            "python /app/decrypt.py".to_string(),
+        ]);
+        assert!(d.validate_pre_start_commands().is_ok());
+        let new_pod = d.render_new_pod();
+        let command = new_pod.command.flatten().unwrap();
+        assert_eq!(&command[..2], ["/bin/sh", "-c"]);
+        let script = &command[2];
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(lines[0], "set -e");
+        assert_eq!(lines[1], "ulimit -n 65536");
+        assert_eq!(lines[2], "python /app/decrypt.py");
+        assert!(lines[3].starts_with(
+            "exec /app/venvs/transformers/bin/python /app/flexserv/python/backend/transformers/backend_server.py /app/models/org_model"
+        ));
+        assert!(lines[3].ends_with("--flexserv-token s-org_model"));
+        assert_eq!(lines.len(), 4);
+        assert_eq!(new_pod.arguments.flatten(), Some(vec![]));
+
+        let spec = redact_pod_spec(d.render_pod_spec());
+        assert!(!spec.to_string().contains("s-org_model"));
+    }
+
+    #[test]
+    fn test_rendered_startup_script_matches_pod_and_redacts() {
+        let d = make_pre_start_deployment(vec!["ulimit -n 65536".to_string()]);
+        let script = d.rendered_startup_script();
+        let command = d.render_new_pod().command.flatten().unwrap();
+        assert_eq!(script, command[2]);
+        assert!(script.contains("/app/models/org_model"));
+        assert!(script.contains("8000"));
+        assert!(script.contains("--flexserv-token s-org_model"));
+
+        let redacted = d.rendered_startup_script_redacted();
+        assert!(!redacted.contains("s-org_model"));
+        assert!(redacted.contains("--flexserv-token [REDACTED]"));
+        assert!(redacted.contains("/app/models/org_model"));
+
+        // Without pre-start steps the pod runs the command directly; the script is the
+        // equivalent exec line.
+        let direct = make_pre_start_deployment(vec![]);
+        let script = direct.rendered_startup_script();
+        assert_eq!(script.lines().count(), 2);
+        assert!(script.starts_with("set -e\nexec "));
+    }
+
+    #[test]
+    fn test_pre_start_commands_validation() {
+        for bad in ["", "echo a\necho b", "exec sleep 1"] {
+            let d = make_pre_start_deployment(vec![bad.to_string()]);
+            assert!(matches!(
+                d.validate_pre_start_commands(),
+                Err(DeploymentError::InvalidConfiguration(_))
+            ));
+        }
+        let d = make_pre_start_deployment(vec![]);
+        assert!(d.validate_pre_start_commands().is_ok());
+        let command = d.render_new_pod().command.flatten().unwrap();
+        assert_ne!(command[0], "/bin/sh");
+    }
+
+    #[test]
+    fn test_server_log_level_flows_into_args() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.server_log_level = Some("debug".to_string());
+        assert!(d.validate_server_log_level().is_ok());
+        let args = d.render_new_pod().arguments.flatten().unwrap();
+        let pos = args.iter().position(|a| a == "--log-level").unwrap();
+        assert_eq!(args[pos + 1], "debug");
+
+        d.options.server_log_level = Some("verbose".to_string());
+        assert!(matches!(
+            d.validate_server_log_level(),
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.contains("server_log_level")
+        ));
+
+        d.options.server_log_level = None;
+        let args = d.render_new_pod().arguments.flatten().unwrap();
+        assert!(!args.contains(&"--log-level".to_string()));
+
+        d.options.server_log_level = Some("info".to_string());
+        d.server.backend = Backend::VLlm { command: vec![] };
+        assert!(d.validate_server_log_level().is_err());
+    }
+
+    #[test]
+    fn test_transformers_tuning_flows_into_args() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.continuous_batching = Some(true);
+        d.options.attn_implementation = Some("flash_attention_2".to_string());
+        assert!(d.validate_transformers_tuning().is_ok());
+        let args = d.render_new_pod().arguments.flatten().unwrap();
+        assert!(args.contains(&"--continuous-batching".to_string()));
+        let pos = args
+            .iter()
+            .position(|a| a == "--attn-implementation")
+            .unwrap();
+        assert_eq!(args[pos + 1], "flash_attention_2");
+
+        d.options.continuous_batching = Some(false);
+        d.options.attn_implementation = None;
+        let args = d.render_new_pod().arguments.flatten().unwrap();
+        assert!(!args.contains(&"--continuous-batching".to_string()));
+        assert!(!args.contains(&"--attn-implementation".to_string()));
+
+        d.options.attn_implementation = Some("flash".to_string());
+        assert!(matches!(
+            d.validate_transformers_tuning(),
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.contains("attn_implementation")
+        ));
+
+        d.options.attn_implementation = Some("sdpa".to_string());
+        d.server.backend = Backend::VLlm { command: vec![] };
+        assert!(d.validate_transformers_tuning().is_err());
+    }
+
+    #[test]
+    fn test_custom_python_and_server_paths_in_script() {
+        let mut d = make_pre_start_deployment(vec!["nvidia-smi".to_string()]);
+        d.options.python_bin = Some("/opt/venvs/cuda12/bin/python".to_string());
+        d.options.backend_server_path = Some("/opt/flexserv/backend_server.py".to_string());
+        assert!(d.validate_transformers_tuning().is_ok());
+        let command = d.render_new_pod().command.flatten().unwrap();
+        let script = &command[2];
+        assert!(
+            script.contains("exec /opt/venvs/cuda12/bin/python /opt/flexserv/backend_server.py ")
+        );
+        assert!(!script.contains(crate::backend::TRANSFORMERS_PYTHON_BIN));
+
+        // Without a script the paths are the pod command itself.
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.python_bin = Some("/opt/venvs/cuda12/bin/python".to_string());
+        let command = d.render_new_pod().command.flatten().unwrap();
         assert_eq!(
-            deployment.options.flexserv_secret.as_deref(),
-            Some("mysecret")
+            command,
+            vec![
+                "/opt/venvs/cuda12/bin/python",
+                crate::backend::TRANSFORMERS_SERVER_PATH
+            ]
         );
+
+        d.server.backend = Backend::VLlm { command: vec![] };
+        assert!(d.validate_transformers_tuning().is_err());
     }
 
     #[test]
-    fn test_from_configs() {
-        let tapis = TapisConfig {
-            tenant_url: "https://tacc.tapis.io".to_string(),
-            tapis_user: "u".to_string(),
-            tapis_token: "jwt".to_string(),
+    fn test_token_via_file_keeps_token_out_of_spec() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.token_via_file = true;
+        assert!(d.validate_pre_start_commands().is_ok());
+        let new_pod = d.render_new_pod();
+        let env = new_pod.environment_variables.clone().unwrap();
+        assert!(!env.contains_key("FLEXSERV_TOKEN"));
+        assert!(!env.contains_key("FLEXSERV_SECRET"));
+        let command = new_pod.command.flatten().unwrap();
+        assert_eq!(&command[..2], ["/bin/sh", "-c"]);
+        assert!(command[2]
+            .trim_end()
+            .ends_with(r#"--flexserv-token "$(cat /app/models/.flexserv_token)""#));
+        assert!(!d.render_pod_spec().to_string().contains("s-org_model"));
+
+        d.server.backend = Backend::Custom {
+            name: "mine".to_string(),
+            command: vec![],
         };
-        let model = ModelConfig {
-            model_id: "openai-community/gpt2".to_string(),
-            model_revision: None,
-            hf_token: None,
-            default_embedding_model: None,
+        assert!(d.validate_pre_start_commands().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_token_file() {
+        let mock = MockServer::start(|_| {
+            (
+                200,
+                r#"{"message":"ok","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            )
+        });
+        let options = PodDeploymentOptions {
+            flexserv_secret: Some("s-".to_string()),
+            token_via_file: true,
+            ..Default::default()
         };
-        let deployment = FlexServPodDeployment::from_configs(
-            tapis,
-            model,
+        let d = mock_deployment(&mock.base_url, options);
+        let config = d.pods_config().await.unwrap();
+        d.upload_token_file(&config).await.unwrap();
+        let reqs = mock.requests();
+        assert_eq!(
+            reqs[0].path,
+            format!("/v3/pods/volumes/{}/upload/.flexserv_token", d.volume_id)
+        );
+        assert!(reqs[0].body.contains("\r\n\r\ns-org_model\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_tapis_files_uploads_tapis_token() {
+        let mock = MockServer::start(|_| {
+            (
+                200,
+                r#"{"message":"ok","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            )
+        });
+        let options = PodDeploymentOptions {
+            model_source: ModelSource::TapisFiles {
+                system_id: "frontera".to_string(),
+                path: "/work/models/llama".to_string(),
+            },
+            ..Default::default()
+        };
+        let d = mock_deployment(&mock.base_url, options);
+        let config = d.pods_config().await.unwrap();
+        d.upload_volume_secrets(&config, true).await.unwrap();
+        let reqs = mock.requests();
+        assert_eq!(reqs.len(), 1);
+        assert_eq!(
+            reqs[0].path,
+            format!("/v3/pods/volumes/{}/upload/.tapis_token", d.volume_id)
+        );
+        assert!(reqs[0].body.contains("\r\n\r\ntoken\r\n"));
+
+        // An existing volume that already holds the model gets no token.
+        let files = r#"{"message":"ok","metadata":{},"result":[{"path":"/org_model","name":"org_model","type":"dir","size":0,"lastModified":"","nativePermissions":""}],"status":"success","version":"dev"}"#;
+        let listing = MockServer::start(move |_| (200, files.to_string()));
+        let d = mock_deployment(&listing.base_url, d.options.clone());
+        let config = d.pods_config().await.unwrap();
+        d.upload_volume_secrets(&config, false).await.unwrap();
+        assert_eq!(listing.methods(), ["GET"]);
+        assert_eq!(
+            listing.requests()[0].path,
+            format!("/v3/pods/volumes/{}/list", d.volume_id)
+        );
+    }
+
+    #[test]
+    fn test_validate_gpu_hints() {
+        let backend = Backend::Transformers { command: vec![] };
+        let options = |gpus, gpu_memory_mb, mig: Option<&str>| PodDeploymentOptions {
+            gpus,
+            gpu_memory_mb,
+            mig_profile: mig.map(str::to_string),
+            ..Default::default()
+        };
+        assert!(options(Some(1), Some(40_000), Some("3g.40gb"))
+            .validate(&backend)
+            .is_ok());
+        assert!(options(None, None, None).validate(&backend).is_ok());
+        for bad in [
+            options(None, None, Some("1g.10gb")),
+            options(Some(0), Some(16_000), None),
+            options(Some(1), Some(0), None),
+            options(Some(1), None, Some("1g10gb")),
+            options(Some(1), None, Some("g.10gb")),
+            options(Some(1), None, Some("")),
+        ] {
+            assert!(
+                matches!(
+                    bad.validate(&backend),
+                    Err(DeploymentError::InvalidConfiguration(_))
+                ),
+                "{:?} {:?} {:?}",
+                bad.gpus,
+                bad.gpu_memory_mb,
+                bad.mig_profile
+            );
+        }
+
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.gpus = Some(1);
+        d.options.gpu_memory_mb = Some(40_000);
+        d.options.mig_profile = Some("3g.40gb".to_string());
+        let env = d.render_new_pod().environment_variables.unwrap();
+        assert_eq!(env["GPU_MEMORY_MB"], "40000");
+        assert_eq!(env["GPU_MIG_PROFILE"], "3g.40gb");
+    }
+
+    #[test]
+    fn test_validate_gpus_for_backend() {
+        let backends = [
             Backend::Transformers { command: vec![] },
-            PodDeploymentOptions::default(),
+            Backend::VLlm { command: vec![] },
+            Backend::SGLang { command: vec![] },
+            Backend::TrtLlm { command: vec![] },
+        ];
+        for backend in &backends {
+            for gpus in [None, Some(0), Some(4)] {
+                let mut options = PodDeploymentOptions {
+                    gpus,
+                    ..Default::default()
+                };
+                let expect_warning = backend.requires_gpu() && gpus.unwrap_or(0) == 0;
+                let warnings = options.validate(backend).unwrap();
+                assert_eq!(
+                    !warnings.is_empty(),
+                    expect_warning,
+                    "{} gpus={:?}",
+                    backend.as_str(),
+                    gpus
+                );
+                options.strict_validation = true;
+                assert_eq!(
+                    options.validate(backend).is_err(),
+                    expect_warning,
+                    "strict {} gpus={:?}",
+                    backend.as_str(),
+                    gpus
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_flexserv_token() {
+        assert_eq!(
+            flexserv_token("", "openai-community/gpt2"),
+            "openai-community_gpt2"
         );
-        assert_eq!(deployment.server.tapis_user, "u");
-        assert_eq!(deployment.server.default_model, "openai-community/gpt2");
-        assert_eq!(deployment.tapis_token, "jwt");
+        assert_eq!(flexserv_token("s3cr3t", "org/Model"), "s3cr3torg_Model");
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.flexserv_secret = Some("s3cr3t".to_string());
+        assert_eq!(d.flexserv_token(), flexserv_token("s3cr3t", "org/model"));
+    }
+
+    #[test]
+    fn test_model_path() {
+        assert_eq!(
+            model_path("openai-community/gpt2"),
+            "/app/models/openai-community_gpt2"
+        );
+        let d = make_pre_start_deployment(vec![]);
+        assert_eq!(d.model_path(), format!("{}/org_model", FLEXSERV_MODEL_REPO));
+        let args = d.render_new_pod().arguments.flatten().unwrap();
+        assert_eq!(args[0], d.model_path());
+    }
+
+    #[test]
+    fn test_tapis_files_model_source_script() {
+        let mut d = make_pre_start_deployment(vec!["ulimit -n 65536".to_string()]);
+        d.options.model_source = ModelSource::TapisFiles {
+            system_id: "frontera".to_string(),
+            path: "/work/models/llama".to_string(),
+        };
+        assert!(d.validate_pre_start_commands().is_ok());
+        let new_pod = d.render_new_pod();
+        let env = new_pod.environment_variables.clone().unwrap();
+        assert!(!env.contains_key("TAPIS_TOKEN"));
+        let script = &new_pod.command.flatten().unwrap()[2];
+        let lines: Vec<&str> = script.lines().collect();
+        assert!(lines[1].starts_with(
+            "trap 'rm -f /app/models/.tapis_token' EXIT; [ -d /app/models/org_model ] || {"
+        ));
+        assert!(lines[1].contains(
+            "'https://tacc.tapis.io/v3/files/content/frontera/work/models/llama?zip=true'"
+        ));
+        assert!(lines[1].contains(r#"-H "X-Tapis-Token: $(cat /app/models/.tapis_token)""#));
+        // A failed download leaves no partial model dir, and the token goes either way.
+        assert!(lines[1].contains("|| { rm -rf /app/models/org_model /tmp/model.zip; exit 1; }"));
+        assert!(lines[1].ends_with("; rm -f /app/models/.tapis_token; trap - EXIT"));
+        assert_eq!(lines[2], "ulimit -n 65536");
+        assert!(lines[3].starts_with("exec "));
+        assert!(!redact_pod_spec(d.render_pod_spec())
+            .to_string()
+            .contains("\"token\""));
+
+        // The path is percent-encoded rather than rejected.
+        d.options.model_source = ModelSource::TapisFiles {
+            system_id: "frontera".to_string(),
+            path: "/work/my models/llama?#1".to_string(),
+        };
+        assert!(d.validate_pre_start_commands().is_ok());
+        assert!(d
+            .model_fetch_command()
+            .unwrap()
+            .contains("/v3/files/content/frontera/work/my%20models/llama%3F%231?zip=true"));
+
+        d.options.model_source = ModelSource::TapisFiles {
+            system_id: "frontera".to_string(),
+            path: " ".to_string(),
+        };
+        assert!(d.validate_pre_start_commands().is_err());
+    }
+
+    #[test]
+    fn test_extra_models_downloaded_by_startup_script() {
+        let mut d = make_pre_start_deployment(vec!["ulimit -n 65536".to_string()]);
+        d.server.extra_models = vec![
+            ExtraModel {
+                repo: "org/adapter".to_string(),
+                subdir: Some("adapters/a1".to_string()),
+                revision: Some("v2".to_string()),
+            },
+            ExtraModel::new("org/second"),
+        ];
+        d.server.default_embedding_model = Some("BAAI/bge-small-en".to_string());
+        assert!(d.validate_extra_models().is_ok());
+
+        let script = &d.render_new_pod().command.flatten().unwrap()[2];
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(
+            lines[1],
+            "[ -d /app/models/adapters/a1 ] || huggingface-cli download org/adapter --revision v2 --local-dir /app/models/adapters/a1"
+        );
+        assert_eq!(
+            lines[2],
+            "[ -d /app/models/org_second ] || huggingface-cli download org/second --local-dir /app/models/org_second"
+        );
+        assert!(lines[3].contains("huggingface-cli download BAAI/bge-small-en"));
+        assert_eq!(lines[4], "ulimit -n 65536");
+        assert!(lines[5].starts_with("exec "));
+
+        d.server.extra_models[0].subdir = Some("../escape".to_string());
+        assert!(d.validate_extra_models().is_err());
+        d.server.extra_models[0].subdir = None;
+        d.server.extra_models[0].revision = Some("a b".to_string());
+        assert!(d.validate_extra_models().is_err());
+        d.server.extra_models[0].revision = None;
+        d.server.backend = Backend::VLlm { command: vec![] };
+        assert!(d.validate_extra_models().is_err());
+    }
+
+    #[test]
+    fn test_image_compatibility_advisory_unless_strict() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.image = Some("tapis/flexserv:1.0".to_string());
+        assert!(d.check_image_compatibility().is_ok());
+        d.server.backend = Backend::VLlm { command: vec![] };
+        assert!(d.check_image_compatibility().is_ok());
+        d.options.strict_image_check = true;
+        match d.check_image_compatibility() {
+            Err(DeploymentError::IncompatibleImage { image, backend }) => {
+                assert_eq!(image, "tapis/flexserv:1.0");
+                assert_eq!(backend, "vllm");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        d.options.image = Some("myorg/llm:latest".to_string());
+        assert!(d.check_image_compatibility().is_ok());
+    }
+
+    #[test]
+    fn test_download_tool_fetches_model_in_startup_script() {
+        let mut d = make_pre_start_deployment(vec![]);
+        let first_step = |d: &FlexServPodDeployment| {
+            d.render_new_pod().command.flatten().unwrap()[2]
+                .lines()
+                .nth(1)
+                .unwrap()
+                .to_string()
+        };
+        assert!(d.model_fetch_command().is_none());
+
+        d.options.download_tool = Some(DownloadTool::PythonSnapshot);
+        assert!(d.validate_download_tool().is_ok());
+        assert_eq!(
+            first_step(&d),
+            "[ -d /app/models/org_model ] || /app/venvs/transformers/bin/python -c 'import sys; \
+             from huggingface_hub import snapshot_download; snapshot_download(sys.argv[1], \
+             revision=sys.argv[2] or None, local_dir=sys.argv[3])' org/model '' /app/models/org_model"
+        );
+
+        d.options.download_tool = Some(DownloadTool::HfCli);
+        assert_eq!(
+            first_step(&d),
+            "[ -d /app/models/org_model ] || huggingface-cli download org/model --local-dir /app/models/org_model"
+        );
+
+        d.options.download_tool = Some(DownloadTool::Git);
+        d.server.model_revision = Some("abc123".to_string());
+        assert_eq!(
+            first_step(&d),
+            "[ -d /app/models/org_model ] || { git lfs install && git clone https://huggingface.co/org/model \
+             /app/models/org_model && git -C /app/models/org_model checkout abc123; } \
+             || { rm -rf /app/models/org_model; exit 1; }"
+        );
+
+        d.server.extra_models = vec![ExtraModel::new("org/second")];
+        let script = &d.render_new_pod().command.flatten().unwrap()[2];
+        assert!(
+            script.contains("git clone https://huggingface.co/org/second /app/models/org_second")
+        );
+
+        d.server.backend = Backend::VLlm { command: vec![] };
+        assert!(d.validate_download_tool().is_err());
+    }
+
+    #[test]
+    fn test_download_retries_wrap_fetch_in_retry_loop() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.download_tool = Some(DownloadTool::HfCli);
+        d.options.download_retries = Some(DownloadRetries {
+            attempts: 5,
+            initial_delay_secs: 7,
+        });
+        d.server.extra_models = vec![ExtraModel::new("org/second")];
+        let script = &d.render_new_pod().command.flatten().unwrap()[2];
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(lines[0], "set -e");
+        assert!(lines[1].starts_with(
+            "[ -d /app/models/org_model ] || { attempt=1; delay=7; \
+             until huggingface-cli download org/model --local-dir /app/models/org_model; do"
+        ));
+        assert!(lines[1].contains(r#"if [ "$attempt" -ge 5 ]; then echo"#));
+        assert!(lines[1].contains("exit 1"));
+        assert!(lines[1].ends_with("delay=$((delay * 2)); done; }"));
+        assert!(lines[2].contains("until huggingface-cli download org/second"));
+
+        // Git removes the partial clone before trying again.
+        d.options.download_tool = Some(DownloadTool::Git);
+        let script = &d.render_new_pod().command.flatten().unwrap()[2];
+        assert!(script.contains("rm -rf /app/models/org_model; sleep"));
+
+        // One attempt renders the plain step.
+        d.options.download_retries = Some(DownloadRetries {
+            attempts: 1,
+            ..Default::default()
+        });
+        d.options.download_tool = Some(DownloadTool::HfCli);
+        let script = &d.render_new_pod().command.flatten().unwrap()[2];
+        assert!(!script.contains("until"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_download_step_retries_until_success_or_exhaustion() {
+        let dir = std::env::temp_dir().join(format!("flexserv-retry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let counter = dir.join("count");
+        let target = dir.join("model");
+        // Fails until it has been run `n` times, then creates the model directory.
+        let fetch = |n: u32| {
+            format!(
+                "{{ echo x >> {c}; [ $(wc -l < {c}) -ge {n} ] && mkdir {t}; }}",
+                c = counter.display(),
+                t = target.display(),
+                n = n
+            )
+        };
+        let retries = Some(DownloadRetries {
+            attempts: 3,
+            initial_delay_secs: 0,
+        });
+        let run = |step: String| {
+            std::process::Command::new("/bin/sh")
+                .arg("-c")
+                .arg(format!("set -e\n{}\necho done\n", step))
+                .output()
+                .unwrap()
+        };
+        let target_q = crate::utils::shell_quote(&target.display().to_string());
+
+        let out = run(download_step(&target_q, &fetch(3), retries, None));
+        assert!(out.status.success(), "{:?}", out);
+        assert!(target.is_dir());
+        assert_eq!(
+            std::fs::read_to_string(&counter).unwrap().lines().count(),
+            3
+        );
+
+        std::fs::remove_dir_all(&target).unwrap();
+        std::fs::remove_file(&counter).unwrap();
+        let out = run(download_step(&target_q, &fetch(4), retries, None));
+        assert!(!out.status.success());
+        assert!(!String::from_utf8_lossy(&out.stdout).contains("done"));
+        assert!(String::from_utf8_lossy(&out.stderr).contains("failed after 3 attempts"));
+
+        // A single failed attempt that left a partial directory is cleaned up.
+        let partial = format!("{{ mkdir {}; false; }}", target_q);
+        let cleanup = format!("rm -rf {}", target_q);
+        let out = run(download_step(&target_q, &partial, None, Some(&cleanup)));
+        assert!(!out.status.success());
+        assert!(!target.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_raw_args_appended_after_builder_args() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.server.backend = Backend::VLlm { command: vec![] };
+        d.options.raw_args = Some("--dtype half --max-num-seqs 256".to_string());
+        assert_eq!(
+            d.raw_args().unwrap(),
+            vec!["--dtype", "half", "--max-num-seqs", "256"]
+        );
+        assert!(d.validate_raw_args().is_ok());
+        let args = d.render_new_pod().arguments.flatten().unwrap();
+        let builder_len = d.pod_params().arguments.unwrap_or_default().len();
+        assert_eq!(
+            &args[1 + builder_len..1 + builder_len + 4],
+            ["--dtype", "half", "--max-num-seqs", "256"]
+        );
+
+        for bad in ["--x 'open", "--x a\n--y b"] {
+            d.options.raw_args = Some(bad.to_string());
+            assert!(d.validate_raw_args().is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn test_env_file_merged_with_options_taking_precedence() {
+        let path = std::env::temp_dir().join(format!("flexserv-env-{}.env", std::process::id()));
+        std::fs::write(
+            &path,
+            "# settings\nEXTRA=\"from file\"\nMODEL_NAME=ignored\nDB_PASSWORD='hunter22'\n",
+        )
+        .unwrap();
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.env_file = Some(path.to_string_lossy().into_owned());
+        assert!(d.validate_env_file().is_ok());
+        let env = d.render_new_pod().environment_variables.unwrap();
+        assert_eq!(env["EXTRA"], "from file");
+        assert_eq!(env["DB_PASSWORD"], "hunter22");
+        assert_eq!(env["MODEL_NAME"], "org_model");
+        let spec = redact_pod_spec(d.render_pod_spec());
+        assert_eq!(spec["environment_variables"]["DB_PASSWORD"], "[REDACTED]");
+        assert_eq!(spec["environment_variables"]["EXTRA"], "from file");
+
+        std::fs::write(&path, "NOT A VAR\n").unwrap();
+        assert!(d.validate_env_file().is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!(d.validate_env_file().is_err());
+    }
+
+    #[test]
+    fn test_model_subdir_overrides_download_and_model_path() {
+        let mut d = make_pre_start_deployment(vec![]);
+        d.options.model_subdir = Some("org/model".to_string());
+        d.options.model_source = ModelSource::TapisFiles {
+            system_id: "frontera".to_string(),
+            path: "/work/models/llama".to_string(),
+        };
+        assert_eq!(d.model_path(), "/app/models/org/model");
+        assert_eq!(d.flexserv_token(), "s-org/model");
+        let new_pod = d.render_new_pod();
+        let env = new_pod.environment_variables.clone().unwrap();
+        assert_eq!(env["MODEL_NAME"], "org/model");
+        let script = &new_pod.command.flatten().unwrap()[2];
+        assert!(script.contains("[ -d /app/models/org/model ] || {"));
+        assert!(script.contains("exec ") && script.contains(" /app/models/org/model"));
+
+        assert!(d.validate_model_subdir().is_ok());
+        for bad in ["/abs", "../up", "a/../b", "", "a b"] {
+            d.options.model_subdir = Some(bad.to_string());
+            assert!(d.validate_model_subdir().is_err(), "{bad:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_monitor_all_polls_each_pod_in_order() {
+        let mock = MockServer::start(|req| match req.path.strip_prefix("/v3/pods/") {
+            Some(id) => (
+                200,
+                POD_RESPONSE_JSON
+                    .replace("pabc", id)
+                    .replace("AVAILABLE", if id == "p3" { "ERROR" } else { "AVAILABLE" }),
+            ),
+            None => (404, "{}".to_string()),
+        });
+        let ids: Vec<String> = (0..12).map(|i| format!("p{}", i)).collect();
+        let deployments: Vec<FlexServPodDeployment> = ids
+            .iter()
+            .map(|id| {
+                let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+                d.pod_id = id.clone();
+                d
+            })
+            .collect();
+
+        let results = monitor_all(&deployments).await;
+        assert_eq!(results.len(), ids.len());
+        for (id, result) in ids.iter().zip(&results) {
+            match result {
+                Ok(DeploymentResult::PodResult {
+                    pod_id,
+                    status,
+                    volume_info,
+                    ..
+                }) => {
+                    assert_eq!(pod_id, id);
+                    let expected = if id == "p3" { "ERROR" } else { "AVAILABLE" };
+                    assert_eq!(status.as_deref(), Some(expected));
+                    assert!(volume_info.is_empty());
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+        let mut polled: Vec<String> = mock.requests().iter().map(|r| r.path.clone()).collect();
+        polled.sort();
+        let mut expected: Vec<String> = ids.iter().map(|id| format!("/v3/pods/{}", id)).collect();
+        expected.sort();
+        assert_eq!(polled, expected);
+    }
+
+    #[tokio::test]
+    async fn test_terminate_outcome_reports_leaked_volume() {
+        let mock = MockServer::start(|req| {
+            if req.path.starts_with("/v3/pods/volumes/") {
+                (
+                    500,
+                    r#"{"message":"volume busy","status":"error"}"#.to_string(),
+                )
+            } else {
+                (
+                    200,
+                    r#"{"message":"Pod successfully deleted.","metadata":{},"result":"","status":"success","version":"dev"}"#
+                        .to_string(),
+                )
+            }
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let (result, outcome) = d.terminate_with_outcome().await.unwrap();
+        assert!(outcome.pod_deleted);
+        assert!(!outcome.volume_deleted);
+        assert!(!outcome.is_complete());
+        assert_eq!(outcome.errors.len(), 1);
+        assert!(
+            matches!(outcome.errors[0], DeploymentError::TapisInternalServerError(ref m) if m == "volume busy")
+        );
+        assert!(result
+            .as_pod()
+            .unwrap()
+            .pod_info
+            .contains("volume: not deleted"));
+
+        // The trait method still succeeds on partial cleanup.
+        assert!(d.terminate().await.is_ok());
+    }
+
+    #[test]
+    fn test_volume_id_override_keeps_derived_pod_id() {
+        let mut d = make_pre_start_deployment(vec![]);
+        assert!(!d.shares_volume());
+        let (pod_id, volume_id) = (d.pod_id.clone(), d.volume_id.clone());
+        d.options.volume_id_override = Some("vshared1".to_string());
+        let (with_override_pod, with_override_volume) =
+            FlexServPodDeployment::ids_from_options(&d.server, &d.options);
+        assert_eq!(with_override_pod, pod_id);
+        assert_eq!(with_override_volume, "vshared1");
+        assert_ne!(with_override_volume, volume_id);
+        assert!(d.validate_volume_id_override().is_ok());
+        assert!(d.shares_volume());
+
+        for bad in ["", "Shared", "v-shared", &"v".repeat(MAX_TAPIS_ID_LEN + 1)] {
+            d.options.volume_id_override = Some(bad.to_string());
+            assert!(d.validate_volume_id_override().is_err(), "{bad:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_terminate_keeps_shared_volume_unless_asked() {
+        let mock = MockServer::start(|_| {
+            (
+                200,
+                r#"{"message":"deleted","metadata":{},"result":"","status":"success","version":"dev"}"#
+                    .to_string(),
+            )
+        });
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.volume_id_override = Some("vshared".to_string());
+        d.volume_id = "vshared".to_string();
+        let (result, outcome) = d.terminate_with_outcome().await.unwrap();
+        assert!(outcome.pod_deleted);
+        assert!(!outcome.volume_deleted);
+        assert!(outcome.errors.is_empty());
+        assert!(result
+            .as_pod()
+            .unwrap()
+            .pod_info
+            .contains("volume: kept (shared)"));
+        let calls: Vec<(String, String)> = mock
+            .requests()
+            .into_iter()
+            .map(|r| (r.method, r.path))
+            .collect();
+        assert_eq!(calls, [("DELETE".to_string(), "/v3/pods/pabc".to_string())]);
+
+        d.options.delete_shared_volume = true;
+        let (_, outcome) = d.terminate_with_outcome().await.unwrap();
+        assert!(outcome.is_complete());
+        assert!(mock
+            .requests()
+            .iter()
+            .any(|r| r.path == "/v3/pods/volumes/vshared"));
+    }
+
+    #[tokio::test]
+    async fn test_terminate_outcome_both_failed() {
+        let mock = MockServer::start(|req| {
+            if req.path.starts_with("/v3/pods/volumes/") {
+                (
+                    500,
+                    r#"{"message":"volume busy","status":"error"}"#.to_string(),
+                )
+            } else {
+                (
+                    403,
+                    r#"{"message":"no access","status":"error"}"#.to_string(),
+                )
+            }
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let (_, outcome) = d.terminate_with_outcome().await.unwrap();
+        assert!(!outcome.pod_deleted);
+        assert!(!outcome.volume_deleted);
+        assert_eq!(outcome.errors.len(), 2);
+        assert!(matches!(
+            outcome.errors[0],
+            DeploymentError::TapisAuthFailed(_)
+        ));
+        assert!(matches!(
+            outcome.errors[1],
+            DeploymentError::TapisInternalServerError(_)
+        ));
+
+        let err = d.terminate().await.unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisAuthFailed(ref m) if m == "no access"));
     }
 
     #[test]
-    fn test_create_deployment_ok() {
-        let deployment = FlexServPodDeployment::create_deployment(
-            "https://tacc.tapis.io".to_string(),
-            "myuser".to_string(),
-            "token".to_string(),
-            "openai-community/gpt2".to_string(),
-            None,
-            Backend::Transformers { command: vec![] },
-        )
-        .unwrap();
-        assert_eq!(deployment.server.tapis_user, "myuser");
-        assert_eq!(deployment.server.default_model, "openai-community/gpt2");
+    fn test_quantity_setters() {
+        let options = PodDeploymentOptions::default()
+            .with_cpu_request("500m")
+            .and_then(|o| o.with_cpu_limit("2"))
+            .and_then(|o| o.with_mem_request("512Mi"))
+            .and_then(|o| o.with_mem_limit("8Gi"))
+            .unwrap();
+        assert_eq!(options.cpu_request, Some(500));
+        assert_eq!(options.cpu_limit, Some(2000));
+        assert_eq!(options.mem_request_mb, Some(512));
+        assert_eq!(options.mem_limit_mb, Some(8192));
+
+        let err = PodDeploymentOptions::default()
+            .with_mem_limit("8192")
+            .unwrap_err();
+        assert!(err.to_string().contains("memory unit"));
     }
 
     #[test]
-    fn test_create_deployment_validation_fails() {
-        let err = FlexServPodDeployment::create_deployment(
-            "not-a-url".to_string(),
-            "u".to_string(),
-            "token".to_string(),
-            "gpt2".to_string(),
-            None,
-            Backend::Transformers { command: vec![] },
-        )
-        .unwrap_err();
-        assert!(matches!(err, ValidationError::InvalidTenantUrl(_)));
+    fn test_estimate_fit() {
+        // Defaults: 1000/2000 millicpus, 4096/8192 MB, no GPUs.
+        let options = PodDeploymentOptions::default();
+        let fit = options.estimate_fit(4000, 16384, 0);
+        assert!(fit.fits);
+        assert_eq!(fit.cpu_headroom, 3000);
+        assert_eq!(fit.mem_headroom_mb, 12288);
+        assert_eq!(fit.gpu_headroom, 0);
+        assert!(fit.warnings.is_empty());
+
+        // Requests fit but limits don't.
+        let fit = options.estimate_fit(1500, 6000, 0);
+        assert!(fit.fits);
+        assert_eq!(fit.warnings.len(), 2);
+        assert!(fit.warnings[0].contains("cpu_limit"));
+        assert!(fit.warnings[1].contains("mem_limit_mb"));
+
+        let options = PodDeploymentOptions {
+            gpus: Some(2),
+            mem_request_mb: Some(32768),
+            ..Default::default()
+        };
+        let fit = options.estimate_fit(8000, 16384, 1);
+        assert!(!fit.fits);
+        assert_eq!(fit.mem_headroom_mb, -16384);
+        assert_eq!(fit.gpu_headroom, -1);
     }
 
     #[test]
-    fn test_pod_id_volume_id_format() {
+    fn test_set_model_updates_ids() {
         let server = FlexServInstance::new(
             "https://tacc.tapis.io".to_string(),
-            "testuser".to_string(),
-            "no-model-yet".to_string(),
-            None,
+            "alice".to_string(),
+            "openai-community/gpt2".to_string(),
+            Some("main".to_string()),
             None,
             None,
             Backend::Transformers { command: vec![] },
         );
-        let deployment = FlexServPodDeployment::new(server, "dummy-token".to_string());
-        assert!(
-            deployment.pod_id.starts_with('p'),
-            "pod_id should start with p"
+        let mut d = FlexServPodDeployment::new(server, "token".to_string());
+        d.pod_info = Some("old".to_string());
+        let (old_pod_id, old_volume_id) = (d.pod_id.clone(), d.volume_id.clone());
+
+        d.set_model("Qwen/Qwen2-0.5B");
+        assert_eq!(d.server.default_model, "Qwen/Qwen2-0.5B");
+        assert_eq!(d.server.model_revision, None);
+        assert_eq!(d.pod_info, None);
+        assert_ne!(d.pod_id, old_pod_id);
+        assert_ne!(d.volume_id, old_volume_id);
+        let fresh = FlexServPodDeployment::new(d.server.clone(), "token".to_string());
+        assert_eq!(d.pod_id, fresh.pod_id);
+        assert_eq!(d.volume_id, fresh.volume_id);
+
+        // With a deployment_id the ids do not depend on the model.
+        let options = PodDeploymentOptions {
+            deployment_id: Some("abc-123".to_string()),
+            ..Default::default()
+        };
+        let mut d = FlexServPodDeployment::with_options(d.server.clone(), "t".to_string(), options);
+        d.set_model("openai-community/gpt2");
+        assert_eq!(d.pod_id, "pabc123");
+    }
+
+    #[tokio::test]
+    async fn test_replace_model_keeps_state_when_terminate_fails() {
+        let mock = MockServer::start(|_| {
+            (
+                403,
+                r#"{"message":"no access","status":"error"}"#.to_string(),
+            )
+        });
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let err = d
+            .replace_model("other/model".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeploymentError::TapisAuthFailed(_)));
+        assert_eq!(d.server.default_model, "m");
+        assert_eq!(d.pod_id, "pabc");
+        assert_eq!(d.volume_id, "vabc");
+    }
+
+    #[test]
+    fn test_image_precedence() {
+        let make = |image: Option<&str>| {
+            let server = test_instance("https://tacc.tapis.io", "m");
+            let options = PodDeploymentOptions {
+                image: image.map(str::to_string),
+                ..Default::default()
+            };
+            FlexServPodDeployment::with_options(server, "token".to_string(), options)
+        };
+
+        std::env::set_var("FLEXSERV_DEFAULT_IMAGE", "tapis/flexserv:2.0");
+        let from_env = make(None).render_new_pod().image;
+        let explicit = make(Some("custom/image:1")).render_new_pod().image;
+        std::env::remove_var("FLEXSERV_DEFAULT_IMAGE");
+        let compiled_in = make(None).render_new_pod().image;
+
+        assert_eq!(explicit.as_deref(), Some("custom/image:1"));
+        assert_eq!(from_env.as_deref(), Some("tapis/flexserv:2.0"));
+        assert_eq!(compiled_in.as_deref(), Some(crate::server::DEFAULT_IMAGE));
+    }
+
+    fn make_warmup_deployment() -> FlexServPodDeployment {
+        let server = test_instance("https://tacc.tapis.io", "org/model");
+        let options = PodDeploymentOptions {
+            flexserv_secret: Some("s-".to_string()),
+            warmup_prompt: Some("Warm up".to_string()),
+            warmup_max_tokens: Some(2),
+            ..Default::default()
+        };
+        FlexServPodDeployment::with_options(server, "tapis-jwt".to_string(), options)
+    }
+
+    #[tokio::test]
+    async fn test_warmup_sends_completion() {
+        let mock = MockServer::start(|_| (200, r#"{"choices":[{"text":" hi"}]}"#.to_string()));
+        let d = make_warmup_deployment();
+        d.warmup(&format!("{}/", mock.base_url)).await.unwrap();
+
+        let reqs = mock.requests();
+        assert_eq!(reqs.len(), 1);
+        assert_eq!(reqs[0].method, "POST");
+        assert_eq!(reqs[0].path, "/v1/completions");
+        assert_eq!(reqs[0].header("authorization"), Some("Bearer s-org_model"));
+        assert_eq!(reqs[0].header("x-flexserv-secret"), Some("s-org_model"));
+        assert_eq!(reqs[0].header("x-tapis-token"), None);
+        let body: serde_json::Value = serde_json::from_str(&reqs[0].body).unwrap();
+        assert_eq!(body["model"], "/app/models/org_model");
+        assert_eq!(body["prompt"], "Warm up");
+        assert_eq!(body["max_tokens"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_warmup_prompts_use_completions_without_chat_template() {
+        let mock = MockServer::start(|req| match req.path.as_str() {
+            "/v1/models" => (
+                200,
+                r#"{"data":[{"id":"/app/models/org_model","object":"model"}]}"#.to_string(),
+            ),
+            _ => (200, r#"{"choices":[{"text":" hi"}]}"#.to_string()),
+        });
+        let mut d = make_warmup_deployment();
+        d.options.warmup_prompts = vec!["one".to_string(), "two".to_string()];
+        d.warmup(&mock.base_url).await.unwrap();
+
+        let reqs = mock.requests();
+        let paths: Vec<&str> = reqs.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["/v1/models", "/v1/completions", "/v1/completions"]);
+        for (req, prompt) in reqs[1..].iter().zip(["one", "two"]) {
+            let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+            assert_eq!(body["prompt"], prompt);
+            assert_eq!(body["max_tokens"], 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_prompts_use_chat_with_chat_template() {
+        let mock = MockServer::start(|req| {
+            match req.path.as_str() {
+            "/v1/models" => (
+                200,
+                r#"{"data":[{"id":"other","chat_template":""},{"id":"/app/models/org_model","chat_template":"{{ messages }}"}]}"#
+                    .to_string(),
+            ),
+            _ => (200, r#"{"choices":[{"message":{"content":"hi"}}]}"#.to_string()),
+        }
+        });
+        let mut d = make_warmup_deployment();
+        d.options.warmup_prompts = vec!["one".to_string(), "two".to_string()];
+        d.warmup(&mock.base_url).await.unwrap();
+
+        let reqs = mock.requests();
+        let paths: Vec<&str> = reqs.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["/v1/models", "/v1/chat/completions", "/v1/chat/completions"]
         );
+        for (req, prompt) in reqs[1..].iter().zip(["one", "two"]) {
+            let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+            assert_eq!(body["messages"][0]["role"], "user");
+            assert_eq!(body["messages"][0]["content"], prompt);
+            assert!(body.get("prompt").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_failure_is_inference_failed() {
+        let mock = MockServer::start(|_| (503, "loading".to_string()));
+        let d = make_warmup_deployment();
+        let err = d.warmup(&mock.base_url).await.unwrap_err();
+        assert!(matches!(err, DeploymentError::InferenceFailed(ref m) if m.contains("loading")));
+    }
+
+    #[tokio::test]
+    async fn test_flexserv_calls_use_tls_options() {
+        let mock = MockServer::start(|_| (200, "{}".to_string()));
+        let mut d = make_warmup_deployment();
+        d.options.ca_cert_pem = Some("not a certificate".to_string());
+        assert!(matches!(
+            d.inference_client(&mock.base_url),
+            Err(DeploymentError::InvalidConfiguration(ref m)) if m.contains("ca_cert_pem")
+        ));
+        assert!(matches!(
+            d.warmup(&mock.base_url).await,
+            Err(DeploymentError::InvalidConfiguration(_))
+        ));
+        assert!(mock.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_operation_timeout_maps_to_tapis_timeout() {
+        let mock = MockServer::start(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(1500));
+            (200, POD_RESPONSE_JSON.to_string())
+        });
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.timeouts.monitor = Duration::from_millis(200);
+        let err = d.monitor().await.unwrap_err();
         assert!(
-            deployment.volume_id.starts_with('v'),
-            "volume_id should start with v"
+            matches!(err, DeploymentError::TapisTimeout(ref m) if m.starts_with("monitor did not finish")),
+            "{err:?}"
         );
-        assert!(
-            is_lowercase_alphanumeric(&deployment.pod_id),
-            "pod_id must be lowercase alphanumeric"
+
+        // Other operations keep their own (longer) limits.
+        d.options.timeouts.stop = Duration::from_secs(10);
+        assert!(!matches!(
+            d.stop().await,
+            Err(DeploymentError::TapisTimeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_sent_to_tapis() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.extra_headers = std::collections::HashMap::from([
+            ("X-Api-Key".to_string(), "gw-key".to_string()),
+            ("X-Tapis-Tenant".to_string(), "tacc".to_string()),
+        ]);
+        d.stop().await.unwrap();
+        let reqs = mock.requests();
+        assert_eq!(reqs[0].header("x-api-key"), Some("gw-key"));
+        assert_eq!(reqs[0].header("x-tapis-tenant"), Some("tacc"));
+        assert_eq!(reqs[0].header("x-tapis-token"), Some("token"));
+
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.extra_headers =
+            std::collections::HashMap::from([("bad header".to_string(), "v".to_string())]);
+        assert!(matches!(
+            d.stop().await,
+            Err(DeploymentError::TapisAuthFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_url_path_prefix_applied_to_pod_url() {
+        let pod: models::PodResponseModel = serde_json::from_value(serde_json::json!({
+            "pod_id": "pabc",
+            "image": "tapis/flexserv:1.0",
+            "status": "AVAILABLE",
+            "networking": {"default": {"protocol": "http", "port": 8000, "url": "https://tacc.tapis.io"}}
+        }))
+        .unwrap();
+        let mut d = make_pre_start_deployment(vec![]);
+        assert_eq!(
+            d.pod_url_from_result(&pod).as_deref(),
+            Some("https://tacc.tapis.io")
         );
-        assert!(
-            is_lowercase_alphanumeric(&deployment.volume_id),
-            "volume_id must be lowercase alphanumeric"
+        d.options.url_path_prefix = Some("/pods/pabc/".to_string());
+        assert_eq!(
+            d.pod_url_from_result(&pod).as_deref(),
+            Some("https://tacc.tapis.io/pods/pabc")
+        );
+
+        // A networking URL that already carries the subpath is kept as is.
+        let pod: models::PodResponseModel = serde_json::from_value(serde_json::json!({
+            "pod_id": "pabc",
+            "image": "tapis/flexserv:1.0",
+            "status": "AVAILABLE",
+            "networking": {"default": {"protocol": "http", "port": 8000, "url": "https://tacc.tapis.io/pods/pabc/"}}
+        }))
+        .unwrap();
+        assert_eq!(
+            d.pod_url_from_result(&pod).as_deref(),
+            Some("https://tacc.tapis.io/pods/pabc/")
         );
     }
 
-    #[test]
-    fn test_pod_id_volume_id_stable() {
-        let server = FlexServInstance::new(
-            "https://tacc.tapis.io".to_string(),
-            "user1".to_string(),
-            "model-a".to_string(),
-            None,
-            None,
-            None,
-            Backend::Transformers { command: vec![] },
+    #[tokio::test]
+    async fn test_monitor_raw_returns_sdk_model() {
+        let mock = MockServer::start(|req| {
+            if req.path == "/v3/pods/pabc" {
+                let body = serde_json::json!({
+                    "message": "ok", "metadata": {}, "status": "success", "version": "dev",
+                    "result": {
+                        "pod_id": "pabc",
+                        "image": "tapis/flexserv:1.0",
+                        "status": "AVAILABLE",
+                        "networking": {"default": {"protocol": "http", "port": 8000, "url": "https://pabc.pods.tacc.tapis.io"}}
+                    }
+                });
+                (200, body.to_string())
+            } else {
+                (404, "{}".to_string())
+            }
+        });
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+
+        let pod = d.monitor_raw().await.unwrap();
+        assert_eq!(pod.status.as_deref(), Some("AVAILABLE"));
+        let url = pod
+            .networking
+            .as_ref()
+            .and_then(|n| n.get("default"))
+            .and_then(|net| net.url.clone());
+        assert_eq!(url.as_deref(), Some("https://pabc.pods.tacc.tapis.io"));
+        let json = serde_json::to_value(&pod).unwrap();
+        assert_eq!(json["pod_id"], "pabc");
+        assert_eq!(json["image"], "tapis/flexserv:1.0");
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_monitor_raw_maps_not_found() {
+        let mock = MockServer::start(|_| (404, r#"{"message":"pod not found"}"#.to_string()));
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        assert!(d.monitor_raw().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_served_models() {
+        let mock = MockServer::start(|_| {
+            (
+                200,
+                r#"{"object":"list","data":[{"id":"/app/models/org_model","object":"model"},{"id":"embed","object":"model"}]}"#
+                    .to_string(),
+            )
+        });
+        let models = served_models(&mock.base_url, "s-org_model").await.unwrap();
+        assert_eq!(models, vec!["/app/models/org_model", "embed"]);
+        let reqs = mock.requests();
+        assert_eq!(reqs[0].method, "GET");
+        assert_eq!(reqs[0].path, "/v1/models");
+        assert_eq!(reqs[0].header("authorization"), Some("Bearer s-org_model"));
+
+        // A pod served under an ingress subpath keeps it.
+        let subpath_url = format!("{}/pods/pabc/", mock.base_url);
+        served_models(&subpath_url, "s-org_model").await.unwrap();
+        assert_eq!(mock.requests()[1].path, "/pods/pabc/v1/models");
+
+        let mock = MockServer::start(|_| (401, "bad token".to_string()));
+        let err = served_models(&mock.base_url, "x").await.unwrap_err();
+        assert!(matches!(err, DeploymentError::InferenceFailed(ref m) if m.contains("401")));
+    }
+
+    #[tokio::test]
+    async fn test_capture_trace_redacts_token() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.tapis_token = "sekrit-tapis-jwt".to_string();
+        d.options.capture_trace = true;
+        d.monitor().await.unwrap();
+        // The token did go out on the wire.
+        assert_eq!(
+            mock.requests()[0].header("x-tapis-token"),
+            Some("sekrit-tapis-jwt")
         );
-        let d1 = FlexServPodDeployment::new(server, "token".to_string());
-        let server2 = FlexServInstance::new(
-            "https://tacc.tapis.io".to_string(),
-            "user1".to_string(),
-            "model-a".to_string(),
-            None,
-            None,
-            None,
-            Backend::Transformers { command: vec![] },
+
+        let trace = d.take_trace();
+        assert!(!trace.entries.is_empty());
+        let first = &trace.entries[0];
+        assert_eq!(first.method, "GET");
+        assert_eq!(first.url, format!("{}/v3/pods/pabc", mock.base_url));
+        assert_eq!(first.status, Some(200));
+        assert!(first
+            .response_body
+            .as_deref()
+            .unwrap()
+            .contains("AVAILABLE"));
+        let dump = serde_json::to_string(&trace).unwrap();
+        assert!(!dump.contains("sekrit"), "{dump}");
+        assert!(d.take_trace().entries.is_empty());
+
+        // Off by default.
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.monitor().await.unwrap();
+        assert!(d.take_trace().entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_token_updates_in_place() {
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        let token = d.rotate_token("n3w").await.unwrap();
+        assert_eq!(token, flexserv_token("n3w", "m"));
+
+        let reqs = mock.requests();
+        assert_eq!(reqs.len(), 2);
+        assert_eq!(
+            (reqs[0].method.as_str(), reqs[0].path.as_str()),
+            ("PUT", "/v3/pods/pabc")
         );
-        let d2 = FlexServPodDeployment::new(server2, "token".to_string());
-        assert_eq!(d1.pod_id, d2.pod_id);
-        assert_eq!(d1.volume_id, d2.volume_id);
+        let body: serde_json::Value = serde_json::from_str(&reqs[0].body).unwrap();
+        assert_eq!(body["environment_variables"]["FLEXSERV_SECRET"], "n3w");
+        assert_eq!(
+            body["environment_variables"]["FLEXSERV_TOKEN"],
+            token.as_str()
+        );
+        assert!(body.to_string().contains(&format!("\"{}\"", token)));
+        assert_eq!(
+            (reqs[1].method.as_str(), reqs[1].path.as_str()),
+            ("GET", "/v3/pods/pabc/restart")
+        );
+        assert!(d.rotate_token(" ").await.is_err());
+
+        // A failed update keeps the old secret.
+        let mock =
+            MockServer::start(|_| (500, r#"{"message":"boom","status":"error"}"#.to_string()));
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.flexserv_secret = Some("old".to_string());
+        assert!(d.rotate_token("n3w").await.is_err());
+        assert_eq!(d.options.flexserv_secret.as_deref(), Some("old"));
+        assert_eq!(mock.methods(), ["PUT"]);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_token_falls_back_to_recreate_keeping_volume() {
+        let mock = create_mode_mock(&["AVAILABLE"]);
+        let mut d = make_create_mode_deployment(&mock.base_url, CreateMode::Submit);
+        d.options.reuse_volume_on_pod_failure = false;
+        let token = d.rotate_token("n3w").await.unwrap();
+        assert_eq!(token, flexserv_token("n3w", "org/model"));
+        assert!(!d.options.reuse_volume_on_pod_failure);
+
+        let reqs = mock.requests();
+        assert!(!reqs
+            .iter()
+            .any(|r| r.method == "DELETE" && r.path.starts_with("/v3/pods/volumes")));
+        let delete = reqs
+            .iter()
+            .position(|r| r.method == "DELETE" && r.path == format!("/v3/pods/{}", d.pod_id))
+            .expect("the fallback deletes the pod first");
+        assert!(reqs[delete..]
+            .iter()
+            .any(|r| r.method == "POST" && r.path == "/v3/pods"));
+        let pod_post = reqs
+            .iter()
+            .find(|r| r.method == "POST" && r.path == "/v3/pods")
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_str(&pod_post.body).unwrap();
+        assert_eq!(body["environment_variables"]["FLEXSERV_SECRET"], "n3w");
+    }
+
+    #[tokio::test]
+    async fn test_check_gpu_availability() {
+        // Capacity is not queryable through the Pods API: a no-op that sends nothing.
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        assert!(check_gpu_availability(&mock.base_url, "token", 8)
+            .await
+            .unwrap());
+        assert!(mock.requests().is_empty());
+
+        // create() with GPUs makes no capacity request either.
+        let mock = create_mode_mock(&["AVAILABLE"]);
+        let mut d = make_create_mode_deployment(&mock.base_url, CreateMode::Submit);
+        d.options.gpus = Some(4);
+        d.create().await.unwrap();
+        assert!(!mock.requests().iter().any(|r| r.path.contains("capacity")));
     }
 
     #[test]
-    fn test_pod_id_volume_id_from_deployment_id() {
-        let make_server = || {
-            FlexServInstance::new(
-                "https://tacc.tapis.io".to_string(),
-                "user1".to_string(),
-                "openai-community/gpt2".to_string(),
-                None,
-                None,
-                None,
-                Backend::Transformers { command: vec![] },
+    fn test_estimate_cost() {
+        let inputs = CostInputs {
+            cpu_millicores: 2000,
+            mem_mb: 8192,
+            gpus: 1,
+            uptime_seconds: 7200,
+        };
+        let rates = CostRates {
+            per_cpu_hour: 0.05,
+            per_gb_mem_hour: 0.01,
+            per_gpu_hour: 2.0,
+        };
+        // 2h x (2 x 0.05 + 8 x 0.01 + 1 x 2.0) = 4.36
+        assert!((estimate_cost(&inputs, &rates) - 4.36).abs() < 1e-9);
+        let idle = CostInputs {
+            uptime_seconds: 0,
+            ..inputs.clone()
+        };
+        assert_eq!(estimate_cost(&idle, &rates), 0.0);
+        let half_cpu = CostInputs {
+            cpu_millicores: 500,
+            mem_mb: 0,
+            gpus: 0,
+            uptime_seconds: 1800,
+        };
+        assert!((estimate_cost(&half_cpu, &rates) - 0.0125).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_cost_inputs_uses_options_and_start_time() {
+        let mock = MockServer::start(|_| {
+            (
+                200,
+                r#"{"message":"ok","metadata":{},"result":{"pod_id":"pabc","image":"tapis/flexserv:1.0","status":"AVAILABLE","start_instance_ts":"2020-01-01T00:00:00Z"},"status":"success","version":"dev"}"#
+                    .to_string(),
             )
+        });
+        let mut d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        d.options.gpus = Some(2);
+        let inputs = d.cost_inputs().await.unwrap();
+        assert_eq!(inputs.cpu_millicores, DEFAULT_CPU_REQUEST as i64);
+        assert_eq!(inputs.mem_mb, DEFAULT_MEM_REQUEST_MB as i64);
+        assert_eq!(inputs.gpus, 2);
+        assert!(inputs.uptime_seconds > 100_000_000);
+
+        let mock = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let d = existing_deployment(&mock.base_url, "pabc", "vabc");
+        assert_eq!(d.cost_inputs().await.unwrap().uptime_seconds, 0);
+    }
+
+    fn watchdog_tapis_mock(pod_url: String) -> MockServer {
+        MockServer::start(move |req| {
+            if req.method == "GET" && req.path == "/v3/pods/pabc" {
+                let body = serde_json::json!({
+                    "message": "ok", "metadata": {}, "status": "success", "version": "dev",
+                    "result": {
+                        "pod_id": "pabc", "image": "tapis/flexserv:1.0", "status": "AVAILABLE",
+                        "networking": {"default": {"protocol": "http", "port": 8000, "url": pod_url}}
+                    }
+                });
+                (200, body.to_string())
+            } else {
+                (
+                    200,
+                    r#"{"message":"deleted","metadata":{},"result":"","status":"success","version":"dev"}"#
+                        .to_string(),
+                )
+            }
+        })
+    }
+
+    const FAST_WATCHDOG: AutoTerminatePolicy = AutoTerminatePolicy {
+        check_interval: Duration::from_millis(50),
+        inactivity_timeout: Duration::from_millis(1000),
+    };
+
+    #[tokio::test]
+    async fn test_watchdog_terminates_after_inactivity() {
+        let server_down = MockServer::start(|_| (503, "gone".to_string()));
+        let tapis = watchdog_tapis_mock(server_down.base_url.clone());
+        let d = existing_deployment(&tapis.base_url, "pabc", "vabc");
+
+        let handle = d.watchdog(FAST_WATCHDOG);
+        let result = tokio::time::timeout(Duration::from_secs(10), handle)
+            .await
+            .expect("watchdog should terminate")
+            .unwrap();
+        assert!(result.is_ok());
+        assert!(server_down.requests().len() >= 3);
+        let deletes: Vec<String> = tapis
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "DELETE")
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(deletes, ["/v3/pods/pabc", "/v3/pods/volumes/vabc"]);
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_keeps_healthy_pod_and_aborts_cleanly() {
+        let server_ok = MockServer::start(|_| (200, r#"{"status":"ok"}"#.to_string()));
+        let tapis = watchdog_tapis_mock(server_ok.base_url.clone());
+        let d = existing_deployment(&tapis.base_url, "pabc", "vabc");
+
+        let handle = d.watchdog(FAST_WATCHDOG);
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!handle.is_finished());
+        handle.abort();
+        assert!(handle.await.unwrap_err().is_cancelled());
+        assert!(server_ok.requests().len() >= 3);
+        assert!(tapis.requests().iter().all(|r| r.method == "GET"));
+    }
+
+    #[tokio::test]
+    async fn test_health_summary_combinations() {
+        let server_ok = MockServer::start(|_| (200, r#"{"status":"ok"}"#.to_string()));
+        let server_down = MockServer::start(|_| (503, "loading".to_string()));
+        let tapis_for = |status: &'static str, url: Option<String>| {
+            MockServer::start(move |_| {
+                let mut result = serde_json::json!({
+                    "pod_id": "pabc",
+                    "image": "tapis/flexserv:1.0",
+                    "status": status,
+                });
+                if let Some(ref url) = url {
+                    result["networking"] = serde_json::json!({
+                        "default": {"protocol": "http", "port": 8000, "url": url}
+                    });
+                }
+                let body = serde_json::json!({
+                    "message": "ok", "metadata": {}, "result": result,
+                    "status": "success", "version": "dev"
+                });
+                (200, body.to_string())
+            })
         };
-        let uuid1 = "550e8400-e29b-41d4-a716-446655440000";
-        let uuid2 = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
-        let opts1 = PodDeploymentOptions {
-            deployment_id: Some(uuid1.to_string()),
+
+        let tapis = tapis_for("AVAILABLE", Some(server_ok.base_url.clone()));
+        let summary = existing_deployment(&tapis.base_url, "pabc", "vabc")
+            .health_summary()
+            .await;
+        assert!(summary.healthy && summary.server_healthy);
+        assert_eq!(summary.status.as_deref(), Some("AVAILABLE"));
+        assert_eq!(summary.error, None);
+        assert_eq!(server_ok.requests()[0].path, "/v1/flexserv/health");
+
+        let tapis = tapis_for("AVAILABLE", Some(server_down.base_url.clone()));
+        let summary = existing_deployment(&tapis.base_url, "pabc", "vabc")
+            .health_summary()
+            .await;
+        assert!(!summary.healthy && !summary.server_healthy);
+        assert!(summary.error.unwrap().contains("503"));
+
+        let tapis = tapis_for("AVAILABLE", None);
+        let summary = existing_deployment(&tapis.base_url, "pabc", "vabc")
+            .health_summary()
+            .await;
+        assert!(!summary.healthy && summary.pod_url.is_none());
+        assert_eq!(summary.error, None);
+
+        let tapis = tapis_for("STOPPED", Some(server_ok.base_url.clone()));
+        let summary = existing_deployment(&tapis.base_url, "pabc", "vabc")
+            .health_summary()
+            .await;
+        assert!(!summary.healthy && summary.server_healthy);
+
+        let tapis = MockServer::start(|_| (500, "boom".to_string()));
+        let summary = existing_deployment(&tapis.base_url, "pabc", "vabc")
+            .health_summary()
+            .await;
+        assert!(!summary.healthy && summary.status.is_none());
+        assert!(summary.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_custom_path_and_status() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/healthz" => (204, String::new()),
+            _ => (404, "no such path".to_string()),
+        });
+        let url = server.base_url.clone();
+        assert!(health_check(&url, "tok", &HealthCheckConfig::default())
+            .await
+            .is_err());
+
+        let custom = HealthCheckConfig {
+            path: "/healthz".to_string(),
             ..Default::default()
         };
-        let opts2 = PodDeploymentOptions {
-            deployment_id: Some(uuid2.to_string()),
-            ..Default::default()
+        assert!(health_check(&url, "tok", &custom).await.is_ok());
+        let expect_204 = HealthCheckConfig {
+            expected_status: Some(204),
+            ..custom.clone()
         };
-        let d1 = FlexServPodDeployment::with_options(make_server(), "token".to_string(), opts1);
-        let d2 = FlexServPodDeployment::with_options(make_server(), "token".to_string(), opts2);
-        assert_eq!(d1.pod_id, "p550e8400e29b41d4a716446655440000");
-        assert_eq!(d1.volume_id, "v550e8400e29b41d4a716446655440000");
-        assert_eq!(d2.pod_id, "p6ba7b8109dad11d180b400c04fd430c8");
-        assert_eq!(d2.volume_id, "v6ba7b8109dad11d180b400c04fd430c8");
-        assert_ne!(d1.pod_id, d2.pod_id);
-        assert!(is_lowercase_alphanumeric(
-            d1.pod_id.strip_prefix('p').unwrap()
-        ));
-        assert!(is_lowercase_alphanumeric(
-            d1.volume_id.strip_prefix('v').unwrap()
-        ));
+        assert!(health_check(&url, "tok", &expect_204).await.is_ok());
+        let expect_200 = HealthCheckConfig {
+            expected_status: Some(200),
+            ..custom
+        };
+        let err = health_check(&url, "tok", &expect_200).await.unwrap_err();
+        assert!(err.to_string().contains("204"), "{err}");
+
+        let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(
+            paths,
+            ["/v1/flexserv/health", "/healthz", "/healthz", "/healthz"]
+        );
     }
 
-    #[test]
-    fn test_pod_deployment_new_optional_fields_none() {
-        let server = FlexServInstance::new(
-            "https://tacc.tapis.io".to_string(),
-            "u".to_string(),
-            "m".to_string(),
-            None,
-            None,
-            None,
-            Backend::Transformers { command: vec![] },
+    #[tokio::test]
+    async fn test_wait_until_ready_uses_health_check_config() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/ready" => (204, String::new()),
+            _ => (404, "no such path".to_string()),
+        });
+        let url = server.base_url.clone();
+        let tapis = MockServer::start(move |_| {
+            let body = serde_json::json!({
+                "message": "ok", "metadata": {}, "status": "success", "version": "dev",
+                "result": {
+                    "pod_id": "pabc", "image": "tapis/flexserv:1.0", "status": "AVAILABLE",
+                    "networking": {"default": {"protocol": "http", "port": 8000, "url": url}}
+                }
+            });
+            (200, body.to_string())
+        });
+        let backoff = BackoffConfig {
+            initial_delay: Duration::from_millis(10),
+            max_attempts: Some(2),
+            ..Default::default()
+        };
+        let mut d = existing_deployment(&tapis.base_url, "pabc", "vabc");
+        d.options.health_check = Some(HealthCheckConfig {
+            path: "/ready".to_string(),
+            expected_status: Some(204),
+            ..Default::default()
+        });
+        assert!(d.wait_until_ready(&backoff).await.is_ok());
+        assert_eq!(server.requests().last().unwrap().path, "/ready");
+
+        // A server that never passes the check times out.
+        d.options.health_check = Some(HealthCheckConfig {
+            path: "/missing".to_string(),
+            ..Default::default()
+        });
+        let err = d.wait_until_ready(&backoff).await.unwrap_err();
+        assert!(
+            matches!(err, DeploymentError::TapisTimeout(ref m) if m.contains("not healthy")),
+            "{err:?}"
         );
-        let d = FlexServPodDeployment::new(server, "token".to_string());
-        assert!(d.volume_info.is_none());
-        assert!(d.pod_info.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_proxy_routes_tapis_calls() {
+        let proxy = MockServer::start(|_| (200, POD_RESPONSE_JSON.to_string()));
+        let server = test_instance("http://tapis.invalid", "m");
+        let options = PodDeploymentOptions {
+            http_proxy: Some(proxy.base_url.clone()),
+            ..Default::default()
+        };
+        let mut d = FlexServPodDeployment::with_options(server, "token".to_string(), options);
+        d.pod_id = "pabc".to_string();
+        d.monitor().await.unwrap();
+        let reqs = proxy.requests();
+        assert!(!reqs.is_empty());
+        // Requests through an HTTP proxy carry the absolute target URL.
+        assert_eq!(reqs[0].path, "http://tapis.invalid/v3/pods/pabc");
     }
 }
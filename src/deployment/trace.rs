@@ -0,0 +1,128 @@
+//! Opt-in request/response capture for a single operation (`PodDeploymentOptions::capture_trace`),
+//! so a failing call can be attached to a bug report. Secrets are redacted before anything is
+//! stored: sensitive header values, and every known secret string wherever it appears.
+
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// Header names (lowercase substrings) whose values are never recorded.
+const SENSITIVE_HEADERS: &[&str] = &["token", "authorization", "secret", "cookie", "key"];
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Secrets shorter than this are not scrubbed from bodies.
+const MIN_SECRET_LEN: usize = 4;
+
+/// One HTTP exchange. `status` and `response_body` are None when the request failed in transit.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TraceEntry {
+    pub method: String,
+    pub url: String,
+    /// Request headers in send order, sensitive values replaced by `[REDACTED]`.
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: Option<String>,
+    pub status: Option<u16>,
+    pub response_body: Option<String>,
+    /// Transport error, when there was no response.
+    pub error: Option<String>,
+}
+
+/// Exchanges captured since the last `take_trace()`, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct OperationTrace {
+    pub entries: Vec<TraceEntry>,
+}
+
+/// Shared buffer the middleware appends to and the deployment drains.
+pub(crate) type TraceBuffer = Arc<Mutex<Vec<TraceEntry>>>;
+
+/// Records each request and response into a [TraceBuffer], scrubbing `secrets`.
+pub(crate) struct TraceMiddleware {
+    buffer: TraceBuffer,
+    secrets: Vec<String>,
+}
+
+impl TraceMiddleware {
+    pub(crate) fn new(buffer: TraceBuffer, secrets: Vec<String>) -> Self {
+        // Very short values would blank out unrelated text and are not real secrets anyway.
+        let mut secrets: Vec<String> = secrets
+            .into_iter()
+            .filter(|s| s.len() >= MIN_SECRET_LEN)
+            .collect();
+        // Longest first, so a token that embeds the secret is replaced whole.
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        Self { buffer, secrets }
+    }
+
+    fn scrub(&self, text: &str) -> String {
+        self.secrets
+            .iter()
+            .fold(text.to_string(), |t, secret| t.replace(secret, REDACTED))
+    }
+
+    fn record(&self, entry: TraceEntry) {
+        self.buffer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(entry);
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for TraceMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut http::Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut entry = TraceEntry {
+            method: req.method().to_string(),
+            url: self.scrub(req.url().as_str()),
+            request_headers: req
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    let name = name.as_str().to_string();
+                    let value = if SENSITIVE_HEADERS.iter().any(|s| name.contains(s)) {
+                        REDACTED.to_string()
+                    } else {
+                        self.scrub(&String::from_utf8_lossy(value.as_bytes()))
+                    };
+                    (name, value)
+                })
+                .collect(),
+            request_body: req
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| self.scrub(&String::from_utf8_lossy(b))),
+            ..Default::default()
+        };
+
+        let resp = match next.run(req, extensions).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                entry.error = Some(self.scrub(&e.to_string()));
+                self.record(entry);
+                return Err(e);
+            }
+        };
+
+        // Read the body for the trace, then hand the caller an equivalent response.
+        let status = resp.status();
+        let version = resp.version();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?;
+        entry.status = Some(status.as_u16());
+        entry.response_body = Some(self.scrub(&String::from_utf8_lossy(&body)));
+        self.record(entry);
+
+        let mut rebuilt = http::Response::new(body);
+        *rebuilt.status_mut() = status;
+        *rebuilt.version_mut() = version;
+        *rebuilt.headers_mut() = headers;
+        Ok(Response::from(rebuilt))
+    }
+}
@@ -1,20 +1,43 @@
+pub mod api;
 pub mod backend;
 pub mod base62;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod deployment;
+#[cfg(feature = "server")]
+pub mod middleware;
+pub mod polling;
+pub mod registry;
 pub mod server;
 mod utils;
 
+#[cfg(test)]
+mod test_support;
+
 // Re-export commonly used types for convenience
 pub use backend::{
-    Backend, BackendParameterSetBuilder, HPCParameterSet, PodParameterSet,
+    estimate_create_duration, Backend, BackendMismatch, BackendParameterSetBuilder, CreateEstimate,
+    CustomParameterSetBuilder, HPCParameterSet, PodParameterSet, ResourceHints,
     SGLangParameterSetBuilder, TransformersParameterSetBuilder, TrtLlmParameterSetBuilder,
-    VLlmParameterSetBuilder,
+    VLlmParameterSetBuilder, TRANSFORMERS_PYTHON_BIN, TRANSFORMERS_SERVER_PATH,
 };
 pub use deployment::{
-    DeploymentError, DeploymentResult, FlexServDeployment, FlexServHPCDeployment,
-    FlexServPodDeployment, HpcDeploymentOptions, PodDeploymentOptions,
+    check_gpu_availability, estimate_cost, flexserv_token, health_check, model_path, monitor_all,
+    served_models, shared_tapis_client, tapis_client, AutoTerminatePolicy, ChatChunk,
+    ChatChunkChoice, ChatDelta, CostInputs, CostRates, CreateMode, CreateStage, DeploymentError,
+    DeploymentEvent, DeploymentPlan, DeploymentResult, DownloadRetries, DownloadTool, FitResult,
+    FlexServDeployment, FlexServHPCDeployment, FlexServPodDeployment, HealthCheckConfig,
+    HealthSummary, HpcDeploymentOptions, HpcResultView, IdStrategy, ModelSource, OperationTimeouts,
+    OperationTrace, PodCondition, PodDeploymentOptions, PodInferenceClient, PodResponseModel,
+    PodResultView, PodStatus, ResourceUsage, SpecDiff, TapisErrorBody, TerminateOutcome,
+    TokenProvider, TraceEntry, VolumeInfo, FLEXSERV_MODEL_REPO, MAX_TAPIS_ID_LEN,
+    MONITOR_ALL_CONCURRENCY, RESTART_POLICIES,
 };
+pub use polling::{poll_with_backoff, BackoffConfig, PollError};
+pub use registry::{DeploymentRecord, DeploymentRegistry};
 pub use server::{
-    normalize_tenant_url, FlexServInstance, FlexServInstanceBuilder, ModelConfig, TapisConfig,
-    ValidationError,
+    default_image, image_supports_backend, normalize_hf_model_id, normalize_tenant_url,
+    parse_cpu_millis, parse_memory_mb, parse_quantity, tapis_api_base, ExtraModel,
+    FlexServInstance, FlexServInstanceBuilder, ModelConfig, TapisConfig, ValidationError,
+    DEFAULT_IMAGE,
 };
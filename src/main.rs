@@ -1,4 +1,8 @@
 use actix_web::{middleware, web, App, HttpResponse, HttpServer, Responder};
+use flexserv_deployer::api;
+use flexserv_deployer::backend::register_custom_backend;
+use flexserv_deployer::middleware::log_requests;
+use flexserv_deployer::registry::DeploymentRegistry;
 
 async fn health() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({
@@ -7,23 +11,32 @@ async fn health() -> impl Responder {
     }))
 }
 
-async fn get_models() -> impl Responder {
-    HttpResponse::Ok().json(serde_json::json!({
-        "models": ["transformers", "vllm", "sglang", "trtllm"]
-    }))
-}
-
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
     log::info!("Starting FlexServ Deployer Server...");
 
-    HttpServer::new(|| {
+    // Comma-separated custom backend names to advertise on /models.
+    if let Ok(names) = std::env::var("FLEXSERV_CUSTOM_BACKENDS") {
+        for name in names.split(',') {
+            register_custom_backend(name);
+        }
+    }
+
+    // Active pods, shared by every worker; not persisted across restarts.
+    let registry = web::Data::new(DeploymentRegistry::new());
+
+    HttpServer::new(move || {
         App::new()
-            .wrap(middleware::Logger::default())
+            .wrap(middleware::from_fn(log_requests))
+            .app_data(registry.clone())
             .route("/health", web::get().to(health))
-            .route("/models", web::get().to(get_models))
+            .route("/models", web::get().to(api::list_models))
+            .app_data(api::json_config())
+            .route("/pods", web::post().to(api::create_pod))
+            .route("/pods", web::get().to(api::list_pods))
+            .route("/pods/{pod_id}", web::delete().to(api::terminate_pod))
     })
     .bind(("127.0.0.1", 8080))?
     .run()
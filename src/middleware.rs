@@ -0,0 +1,120 @@
+//! HTTP middleware for the deployer server (`server` feature).
+//!
+//! [log_requests] logs one line per request with method, path, status, and latency as
+//! `key=value` fields. Credential headers are always redacted before logging.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::HeaderMap;
+use actix_web::middleware::Next;
+use actix_web::Error;
+use std::time::Instant;
+
+/// Headers whose values are never written to logs (compared case-insensitively).
+pub const REDACTED_HEADERS: &[&str] = &[
+    "x-tapis-token",
+    "authorization",
+    "x-flexserv-secret",
+    "x-flexserv-token",
+    "cookie",
+];
+
+/// Render headers as `name=value` pairs, replacing credential values with `[REDACTED]`.
+pub fn redacted_headers(headers: &HeaderMap) -> String {
+    let mut pairs: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str();
+            let value = if REDACTED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                "[REDACTED]"
+            } else {
+                value.to_str().unwrap_or("<non-ascii>")
+            };
+            format!("{}={}", name, value)
+        })
+        .collect();
+    pairs.sort();
+    pairs.join(",")
+}
+
+/// Request logging middleware; use with `actix_web::middleware::from_fn(log_requests)`.
+pub async fn log_requests(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let path = req.path().to_string();
+    let headers = redacted_headers(req.headers());
+    let res = next.call(req).await?;
+    log::info!(
+        target: "flexserv_deployer::http",
+        "method={} path={} status={} latency_ms={} headers=[{}]",
+        method,
+        path,
+        res.status().as_u16(),
+        started.elapsed().as_millis(),
+        headers
+    );
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{middleware::from_fn, test, web, App, HttpResponse};
+    use std::sync::Mutex;
+
+    static CAPTURED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+        fn log(&self, record: &log::Record) {
+            if record.target() == "flexserv_deployer::http" {
+                CAPTURED.lock().unwrap().push(record.args().to_string());
+            }
+        }
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger;
+
+    #[actix_web::test]
+    async fn test_log_requests_redacts_tokens() {
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Info);
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(log_requests))
+                .route("/health", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+        let req = test::TestRequest::get()
+            .uri("/health")
+            .insert_header(("X-Tapis-Token", "super-secret-jwt"))
+            .insert_header(("Authorization", "Bearer super-secret-bearer"))
+            .insert_header(("X-FlexServ-Token", "super-secret-pod-token"))
+            .insert_header(("User-Agent", "unit-test"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let lines = CAPTURED.lock().unwrap().clone();
+        let line = lines
+            .iter()
+            .find(|l| l.contains("path=/health"))
+            .expect("request should be logged");
+        assert!(line.contains("method=GET"));
+        assert!(line.contains("status=200"));
+        assert!(line.contains("latency_ms="));
+        assert!(line.contains("user-agent=unit-test"));
+        assert!(line.contains("x-tapis-token=[REDACTED]"));
+        assert!(line.contains("x-flexserv-token=[REDACTED]"));
+        assert!(!lines.iter().any(|l| l.contains("super-secret")));
+    }
+}
@@ -0,0 +1,203 @@
+//! Polling with exponential backoff and jitter, shared by pod and HPC wait helpers.
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Backoff schedule for [poll_with_backoff].
+///
+/// Attempt `n` (0-based) waits `min(initial_delay * multiplier^n, max_delay)`, randomized by
+/// ±`jitter` (fraction, 0.0-1.0) so many callers don't poll TAPIS in lockstep.
+#[derive(Debug, Clone)]
+pub struct BackoffConfig {
+    /// Delay before the second attempt. Default 1 s.
+    pub initial_delay: Duration,
+    /// Upper bound for any single delay. Default 30 s.
+    pub max_delay: Duration,
+    /// Growth factor between attempts. Default 2.0.
+    pub multiplier: f64,
+    /// Random spread applied to each delay, as a fraction. Default 0.2 (±20%).
+    pub jitter: f64,
+    /// Stop after this many attempts. Default None (bounded by `timeout` only).
+    pub max_attempts: Option<u32>,
+    /// Stop once this much time has elapsed. Default 10 minutes.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: None,
+            timeout: Some(Duration::from_secs(600)),
+        }
+    }
+}
+
+impl BackoffConfig {
+    /// Delay after attempt `attempt` (0-based) for a jitter sample in `[0, 1)`.
+    pub fn delay_for_attempt(&self, attempt: u32, jitter_sample: f64) -> Duration {
+        let base = self.initial_delay.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let capped = base.min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = 1.0 + jitter * (2.0 * jitter_sample.clamp(0.0, 1.0) - 1.0);
+        Duration::from_secs_f64((capped * factor).max(0.0))
+    }
+}
+
+/// Why [poll_with_backoff] stopped without reaching the done condition.
+#[derive(Debug)]
+pub enum PollError<E> {
+    /// The operation itself failed; polling stops on the first error.
+    Operation(E),
+    /// `max_attempts` or `timeout` was reached first.
+    Exhausted { attempts: u32, elapsed: Duration },
+}
+
+impl<E: fmt::Display> fmt::Display for PollError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PollError::Operation(e) => write!(f, "{}", e),
+            PollError::Exhausted { attempts, elapsed } => write!(
+                f,
+                "condition not met after {} attempt(s) in {:.1}s",
+                attempts,
+                elapsed.as_secs_f64()
+            ),
+        }
+    }
+}
+
+/// Call `op(attempt)` (1-based attempt number) until `is_done` accepts its output, sleeping
+/// per `config` between attempts. Returns the accepted value, the first operation error, or
+/// [PollError::Exhausted].
+pub async fn poll_with_backoff<T, E, F, Fut, D>(
+    mut op: F,
+    is_done: D,
+    config: &BackoffConfig,
+) -> Result<T, PollError<E>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    D: Fn(&T) -> bool,
+{
+    let started = Instant::now();
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        let value = op(attempt).await.map_err(PollError::Operation)?;
+        if is_done(&value) {
+            return Ok(value);
+        }
+        let delay = config.delay_for_attempt(attempt - 1, jitter_sample());
+        let out_of_attempts = config.max_attempts.is_some_and(|max| attempt >= max);
+        let out_of_time = config
+            .timeout
+            .is_some_and(|t| started.elapsed() + delay > t);
+        if out_of_attempts || out_of_time {
+            return Err(PollError::Exhausted {
+                attempts: attempt,
+                elapsed: started.elapsed(),
+            });
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Uniform-ish sample in `[0, 1)` from std's randomly keyed hasher (no rand dependency).
+fn jitter_sample() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    hasher.write_u128(nanos);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_for_attempt_grows_and_caps() {
+        let config = BackoffConfig {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(config.delay_for_attempt(0, 0.5), Duration::from_secs(1));
+        assert_eq!(config.delay_for_attempt(1, 0.5), Duration::from_secs(2));
+        assert_eq!(config.delay_for_attempt(2, 0.5), Duration::from_secs(4));
+        assert_eq!(config.delay_for_attempt(3, 0.5), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_delay_for_attempt_jitter_bounds() {
+        let config = BackoffConfig {
+            initial_delay: Duration::from_secs(10),
+            jitter: 0.2,
+            ..Default::default()
+        };
+        let low = config.delay_for_attempt(0, 0.0).as_secs_f64();
+        let high = config.delay_for_attempt(0, 1.0).as_secs_f64();
+        assert!((low - 8.0).abs() < 1e-6, "low = {low}");
+        assert!((high - 12.0).abs() < 1e-6, "high = {high}");
+        let sample = jitter_sample();
+        assert!((0.0..1.0).contains(&sample));
+    }
+
+    fn fast_config(max_attempts: Option<u32>) -> BackoffConfig {
+        BackoffConfig {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_with_backoff_until_done() {
+        let result: Result<u32, PollError<()>> = poll_with_backoff(
+            |attempt| async move { Ok(attempt) },
+            |n| *n == 3,
+            &fast_config(None),
+        )
+        .await;
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_with_backoff_exhausted() {
+        let result: Result<u32, PollError<()>> = poll_with_backoff(
+            |attempt| async move { Ok(attempt) },
+            |_| false,
+            &fast_config(Some(4)),
+        )
+        .await;
+        assert!(matches!(result, Err(PollError::Exhausted { attempts: 4, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_poll_with_backoff_stops_on_error() {
+        let mut calls = 0;
+        let result: Result<u32, PollError<&str>> = poll_with_backoff(
+            |_| {
+                calls += 1;
+                async { Err("boom") }
+            },
+            |_| true,
+            &fast_config(None),
+        )
+        .await;
+        assert!(matches!(result, Err(PollError::Operation("boom"))));
+        assert_eq!(calls, 1);
+    }
+}
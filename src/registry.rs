@@ -0,0 +1,170 @@
+//! In-memory registry of active deployments, shared across HTTP requests as actix app data.
+//! `POST /pods` records each created pod and `DELETE /pods/{pod_id}` removes it. Nothing is
+//! persisted: the registry starts empty whenever the process starts.
+
+use crate::deployment::{DeploymentResult, FlexServPodDeployment};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// What the registry keeps about one pod deployment: enough to find, monitor, or terminate it
+/// later with [FlexServPodDeployment::from_existing].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    /// Primary pod id (the registry key).
+    pub pod_id: String,
+    /// Every replica's pod id (see [FlexServPodDeployment::pod_ids]).
+    pub pod_ids: Vec<String>,
+    pub volume_id: String,
+    /// The volume is shared (`volume_id_override`), so terminating the pods keeps it.
+    pub shared_volume: bool,
+    pub tenant_url: String,
+    pub tapis_user: String,
+    pub model_id: String,
+    /// Pod URL at create time, if the pod had one yet.
+    pub pod_url: Option<String>,
+    /// Unix seconds when the record was made.
+    pub created_at: i64,
+}
+
+impl DeploymentRecord {
+    /// Record for `deployment` after create() returned `result`.
+    pub fn new(deployment: &FlexServPodDeployment, result: &DeploymentResult) -> Self {
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        Self {
+            pod_id: deployment.pod_id.clone(),
+            pod_ids: deployment.pod_ids(),
+            volume_id: deployment.volume_id.clone(),
+            shared_volume: deployment.options.volume_id_override.is_some(),
+            tenant_url: deployment.server.tenant_url.clone(),
+            tapis_user: deployment.server.tapis_user.clone(),
+            model_id: deployment.server.default_model.clone(),
+            pod_url: result.pod_url().map(str::to_string),
+            created_at,
+        }
+    }
+}
+
+/// Thread-safe map of pod id to [DeploymentRecord]. Cloning is cheap and clones share the same
+/// map, so one registry can be handed to every actix worker.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentRegistry {
+    records: Arc<RwLock<HashMap<String, DeploymentRecord>>>,
+}
+
+impl DeploymentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A panic while holding the lock cannot leave a half-written record, so poisoning is ignored.
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, DeploymentRecord>> {
+        self.records.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, DeploymentRecord>> {
+        self.records.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Add `record` under its pod id, returning the record it replaced, if any.
+    pub fn insert(&self, record: DeploymentRecord) -> Option<DeploymentRecord> {
+        self.write().insert(record.pod_id.clone(), record)
+    }
+
+    pub fn get(&self, pod_id: &str) -> Option<DeploymentRecord> {
+        self.read().get(pod_id).cloned()
+    }
+
+    pub fn remove(&self, pod_id: &str) -> Option<DeploymentRecord> {
+        self.write().remove(pod_id)
+    }
+
+    /// Every record, sorted by pod id.
+    pub fn list(&self) -> Vec<DeploymentRecord> {
+        let mut records: Vec<DeploymentRecord> = self.read().values().cloned().collect();
+        records.sort_by(|a, b| a.pod_id.cmp(&b.pod_id));
+        records
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(pod_id: &str) -> DeploymentRecord {
+        DeploymentRecord {
+            pod_id: pod_id.to_string(),
+            pod_ids: vec![pod_id.to_string()],
+            volume_id: format!("{}v", pod_id),
+            shared_volume: false,
+            tenant_url: "https://tacc.tapis.io".to_string(),
+            tapis_user: "u".to_string(),
+            model_id: "m".to_string(),
+            pod_url: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_insert_get_remove_list() {
+        let registry = DeploymentRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.insert(record("pb")).is_none());
+        assert!(registry.insert(record("pa")).is_none());
+        assert_eq!(registry.insert(record("pa")), Some(record("pa")));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get("pa"), Some(record("pa")));
+        assert!(registry.get("pc").is_none());
+
+        let ids: Vec<String> = registry.list().into_iter().map(|r| r.pod_id).collect();
+        assert_eq!(ids, ["pa", "pb"]);
+
+        // Clones share the map.
+        let other = registry.clone();
+        assert_eq!(other.remove("pa"), Some(record("pa")));
+        assert!(registry.get("pa").is_none());
+        assert!(registry.remove("pa").is_none());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_insert_and_remove() {
+        let registry = DeploymentRegistry::new();
+        let tasks: Vec<_> = (0..16)
+            .map(|t| {
+                let registry = registry.clone();
+                tokio::spawn(async move {
+                    for i in 0..50 {
+                        let id = format!("p{}x{}", t, i);
+                        registry.insert(record(&id));
+                        assert!(registry.get(&id).is_some());
+                        // Remove every other record again.
+                        if i % 2 == 0 {
+                            assert!(registry.remove(&id).is_some());
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(registry.len(), 16 * 25);
+        for r in registry.list() {
+            let i: u32 = r.pod_id.rsplit('x').next().unwrap().parse().unwrap();
+            assert_eq!(i % 2, 1, "{} should have been removed", r.pod_id);
+        }
+    }
+}
@@ -1,10 +1,15 @@
 use crate::backend::Backend;
 use crate::base62;
-use crate::utils::is_absolute_http_url;
+use crate::utils::{canonical_tenant_url, is_absolute_http_url};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fmt;
+use std::time::Duration;
 
-pub use crate::utils::normalize_tenant_url;
+pub use crate::utils::{
+    image_supports_backend, normalize_hf_model_id, normalize_tenant_url, parse_cpu_millis,
+    parse_memory_mb, parse_quantity, tapis_api_base,
+};
 
 /// Server-side TAPIS connection config (tenant, user, token).
 #[derive(Clone, Debug)]
@@ -15,6 +20,9 @@ pub struct TapisConfig {
     pub tapis_user: String,
     /// JWT used to authenticate against TAPIS Pods API
     pub tapis_token: String,
+    /// Fresh token per operation instead of `tapis_token`, for long-running services; see
+    /// [crate::TokenProvider]. None = always use `tapis_token`.
+    pub token_provider: Option<crate::TokenProvider>,
 }
 
 /// Model-related config (what to deploy, how to fetch it).
@@ -28,6 +36,202 @@ pub struct ModelConfig {
     pub hf_token: Option<String>,
     /// Optional default embedding model
     pub default_embedding_model: Option<String>,
+    /// Models downloaded into the volume next to the main one (adapters, a second model).
+    pub extra_models: Vec<ExtraModel>,
+}
+
+/// A Hugging Face repo the pod downloads into the model volume before starting, alongside the
+/// main model (e.g. a LoRA adapter or an embedding model).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct ExtraModel {
+    /// Hugging Face repo id (e.g. "org/adapter").
+    pub repo: String,
+    /// Directory under the model volume; None = the repo id with `/` replaced by `_`. Must be
+    /// relative, without `..`.
+    pub subdir: Option<String>,
+    /// Revision (branch/tag/commit); None = repo default.
+    pub revision: Option<String>,
+}
+
+impl ExtraModel {
+    pub fn new(repo: impl Into<String>) -> Self {
+        Self {
+            repo: repo.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Volume size used when the model size cannot be determined (10 GB).
+pub const DEFAULT_VOLUME_SIZE_MB: i32 = 10 * 1024;
+
+/// Compiled-in pod image, used when neither `PodDeploymentOptions::image` nor the
+/// `FLEXSERV_DEFAULT_IMAGE` env var is set.
+pub const DEFAULT_IMAGE: &str = "tapis/flexserv:1.0";
+
+/// Pod image when none is given explicitly: `FLEXSERV_DEFAULT_IMAGE` env var (if non-empty),
+/// else [DEFAULT_IMAGE]. Lets operators roll out a new image without changing callers.
+pub fn default_image() -> String {
+    std::env::var("FLEXSERV_DEFAULT_IMAGE")
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_IMAGE.to_string())
+}
+
+/// Default Hugging Face Hub endpoint; overridden by the `HF_ENDPOINT` env var.
+const DEFAULT_HF_ENDPOINT: &str = "https://huggingface.co";
+
+#[derive(Deserialize)]
+struct HfModelInfo {
+    /// Commit sha the requested revision points at.
+    #[serde(default)]
+    sha: Option<String>,
+    #[serde(default)]
+    siblings: Vec<HfSibling>,
+    /// `false`, or the gating mode (`"auto"`, `"manual"`) for models that need an accepted license.
+    #[serde(default)]
+    gated: serde_json::Value,
+}
+
+/// Model id prefixes known to be gated on the Hugging Face Hub, checked without a Hub call.
+const KNOWN_GATED_PREFIXES: &[&str] = &[
+    "meta-llama/",
+    "mistralai/",
+    "google/gemma",
+    "bigcode/starcoder",
+    "tiiuae/falcon-180b",
+];
+
+/// Whether `model_id` is on the built-in list of gated models (case-insensitive prefix match).
+/// A false answer means unknown, not ungated.
+pub(crate) fn is_known_gated_model(model_id: &str) -> bool {
+    let id = model_id.trim().to_ascii_lowercase();
+    KNOWN_GATED_PREFIXES.iter().any(|p| id.starts_with(p))
+}
+
+/// Whether the Hub marks the model as gated; None when the lookup fails.
+pub(crate) async fn model_is_gated(
+    endpoint: &str,
+    model_id: &str,
+    revision: Option<&str>,
+) -> Option<bool> {
+    let info = fetch_hf_model_info(endpoint, model_id, revision, None, false).await?;
+    Some(match info.gated {
+        serde_json::Value::Bool(b) => b,
+        serde_json::Value::String(ref mode) => !mode.is_empty() && mode != "false",
+        _ => false,
+    })
+}
+
+#[derive(Deserialize)]
+struct HfSibling {
+    size: Option<u64>,
+}
+
+impl ModelConfig {
+    /// Suggested volume size in MB for this model, from the total file size reported by the
+    /// Hugging Face API plus headroom. Returns None when the API call fails or reports no sizes.
+    pub async fn estimate_volume_mb(&self) -> Option<i32> {
+        self.estimate_volume_mb_with_endpoint(&hf_endpoint()).await
+    }
+
+    /// Same as [ModelConfig::estimate_volume_mb] against an explicit Hub endpoint (mirrors, tests).
+    pub async fn estimate_volume_mb_with_endpoint(&self, endpoint: &str) -> Option<i32> {
+        estimate_model_volume_mb(
+            endpoint,
+            &self.model_id,
+            self.model_revision.as_deref(),
+            self.hf_token.as_deref(),
+        )
+        .await
+    }
+}
+
+/// Hugging Face Hub endpoint: `HF_ENDPOINT` env var, or `https://huggingface.co`.
+pub(crate) fn hf_endpoint() -> String {
+    std::env::var("HF_ENDPOINT").unwrap_or_else(|_| DEFAULT_HF_ENDPOINT.to_string())
+}
+
+/// `GET {endpoint}/api/models/{id}[/revision/{rev}][?blobs=true]`; None on any failure.
+async fn fetch_hf_model_info(
+    endpoint: &str,
+    model_id: &str,
+    revision: Option<&str>,
+    hf_token: Option<&str>,
+    blobs: bool,
+) -> Option<HfModelInfo> {
+    let base = endpoint.trim_end_matches('/');
+    let mut url = match revision {
+        Some(rev) => format!("{}/api/models/{}/revision/{}", base, model_id, rev),
+        None => format!("{}/api/models/{}", base, model_id),
+    };
+    if blobs {
+        url.push_str("?blobs=true");
+    }
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .ok()?;
+    let mut req = client.get(&url);
+    if let Some(token) = hf_token {
+        req = req.bearer_auth(token);
+    }
+    let resp = req.send().await.ok()?;
+    if !resp.status().is_success() {
+        log::debug!("HF model info {} returned {}", url, resp.status());
+        return None;
+    }
+    resp.json().await.ok()
+}
+
+/// Resolve a branch or tag (None = repo default branch) to the commit sha it currently points at,
+/// via the Hugging Face model info API. Returns None when the lookup fails.
+pub(crate) async fn resolve_model_revision(
+    endpoint: &str,
+    model_id: &str,
+    revision: Option<&str>,
+    hf_token: Option<&str>,
+) -> Option<String> {
+    fetch_hf_model_info(endpoint, model_id, revision, hf_token, false)
+        .await?
+        .sha
+        .filter(|sha| !sha.is_empty())
+}
+
+/// Total size of the model's files in MB (rounded up), from the Hugging Face model info.
+/// None when the API call fails or reports no sizes.
+pub(crate) async fn model_size_mb(
+    endpoint: &str,
+    model_id: &str,
+    revision: Option<&str>,
+    hf_token: Option<&str>,
+) -> Option<u64> {
+    let info = fetch_hf_model_info(endpoint, model_id, revision, hf_token, true).await?;
+    let total_bytes: u64 = info.siblings.iter().filter_map(|f| f.size).sum();
+    if total_bytes == 0 {
+        return None;
+    }
+    Some(total_bytes.div_ceil(1024 * 1024))
+}
+
+/// Query the Hugging Face model info (with file sizes) and size a volume for it:
+/// total size + 25%, plus 1 GB for runtime files, rounded up to a whole GB.
+pub(crate) async fn estimate_model_volume_mb(
+    endpoint: &str,
+    model_id: &str,
+    revision: Option<&str>,
+    hf_token: Option<&str>,
+) -> Option<i32> {
+    let model_mb = model_size_mb(endpoint, model_id, revision, hf_token).await?;
+    volume_mb_for_model_size(model_mb)
+}
+
+/// Size a volume for a model of `model_mb`: + 25%, plus 1 GB, rounded up to a whole GB.
+pub(crate) fn volume_mb_for_model_size(model_mb: u64) -> Option<i32> {
+    let with_headroom = model_mb + model_mb / 4 + 1024;
+    let rounded = with_headroom.div_ceil(1024) * 1024;
+    i32::try_from(rounded).ok()
 }
 
 /// Input validation error (URL format, non-empty fields, etc.).
@@ -36,7 +240,9 @@ pub enum ValidationError {
     InvalidTenantUrl(String),
     EmptyTapisUser,
     EmptyModelId,
+    InvalidModelId(String),
     InvalidModelRevision(String),
+    InvalidQuantity(String),
     MissingBackend,
 }
 
@@ -46,9 +252,11 @@ impl fmt::Display for ValidationError {
             ValidationError::InvalidTenantUrl(msg) => write!(f, "invalid tenant URL: {}", msg),
             ValidationError::EmptyTapisUser => write!(f, "tapis_user must be non-empty"),
             ValidationError::EmptyModelId => write!(f, "model_id must be non-empty"),
+            ValidationError::InvalidModelId(msg) => write!(f, "invalid model_id: {}", msg),
             ValidationError::InvalidModelRevision(msg) => {
                 write!(f, "invalid model_revision: {}", msg)
             }
+            ValidationError::InvalidQuantity(msg) => write!(f, "invalid quantity: {}", msg),
             ValidationError::MissingBackend => write!(f, "backend is required"),
         }
     }
@@ -56,8 +264,49 @@ impl fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+impl ValidationError {
+    /// Stable snake_case identifier of the variant (e.g. `invalid_tenant_url`), for clients that
+    /// react to specific errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationError::InvalidTenantUrl(_) => "invalid_tenant_url",
+            ValidationError::EmptyTapisUser => "empty_tapis_user",
+            ValidationError::EmptyModelId => "empty_model_id",
+            ValidationError::InvalidModelId(_) => "invalid_model_id",
+            ValidationError::InvalidModelRevision(_) => "invalid_model_revision",
+            ValidationError::InvalidQuantity(_) => "invalid_quantity",
+            ValidationError::MissingBackend => "missing_backend",
+        }
+    }
+
+    /// The input field at fault (e.g. `tenant_url`). None for [ValidationError::InvalidQuantity],
+    /// which the quantity parsers raise without knowing the field.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            ValidationError::InvalidTenantUrl(_) => Some("tenant_url"),
+            ValidationError::EmptyTapisUser => Some("tapis_user"),
+            ValidationError::EmptyModelId | ValidationError::InvalidModelId(_) => Some("model_id"),
+            ValidationError::InvalidModelRevision(_) => Some("model_revision"),
+            ValidationError::InvalidQuantity(_) => None,
+            ValidationError::MissingBackend => Some("backend"),
+        }
+    }
+}
+
+/// Serializes as `{"field": ..., "message": ..., "code": ...}`, with the Display text as `message`.
+impl Serialize for ValidationError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ValidationError", 3)?;
+        state.serialize_field("field", &self.field())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("code", self.code())?;
+        state.end()
+    }
+}
+
 /// FlexServ server configuration
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct FlexServInstance {
     /// tenant url
     pub tenant_url: String,
@@ -77,8 +326,15 @@ pub struct FlexServInstance {
     /// default embedding model
     pub default_embedding_model: Option<String>,
 
+    /// Models downloaded next to `default_model` (see [ModelConfig::extra_models]).
+    pub extra_models: Vec<ExtraModel>,
+
     /// backend to use
     pub backend: Backend,
+
+    /// Optional tag folded into [FlexServInstance::deployment_hash], so otherwise identical
+    /// configs (e.g. an A/B pair) get distinct pod/volume ids without a `deployment_id`.
+    pub deployment_tag: Option<String>,
 }
 
 /// Builder for [FlexServInstance] with optional validation.
@@ -90,7 +346,9 @@ pub struct FlexServInstanceBuilder {
     model_revision: Option<String>,
     hf_token: Option<String>,
     default_embedding_model: Option<String>,
+    extra_models: Vec<ExtraModel>,
     backend: Option<Backend>,
+    deployment_tag: Option<String>,
 }
 
 impl FlexServInstanceBuilder {
@@ -102,7 +360,9 @@ impl FlexServInstanceBuilder {
             model_revision: None,
             hf_token: None,
             default_embedding_model: None,
+            extra_models: Vec::new(),
             backend: None,
+            deployment_tag: None,
         }
     }
 
@@ -136,12 +396,25 @@ impl FlexServInstanceBuilder {
         self
     }
 
+    /// Download `model` into the volume next to the main model.
+    pub fn extra_model(mut self, model: ExtraModel) -> Self {
+        self.extra_models.push(model);
+        self
+    }
+
     pub fn backend(mut self, backend: Backend) -> Self {
         self.backend = Some(backend);
         self
     }
 
-    /// Build and validate (non-empty user/model, tenant URL; URL normalized if no scheme, e.g. `tacc.tapis.io` → `https://tacc.tapis.io`).
+    /// Set [FlexServInstance::deployment_tag].
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.deployment_tag = Some(tag.into());
+        self
+    }
+
+    /// Build and validate (non-empty user, tenant URL, model id; URL normalized if no scheme, e.g. `tacc.tapis.io` → `https://tacc.tapis.io`;
+    /// model id normalized with [normalize_hf_model_id]).
     pub fn build(self) -> Result<FlexServInstance, ValidationError> {
         let tenant_url = normalize_tenant_url(&self.tenant_url.unwrap_or_default());
         let tenant_url = tenant_url.trim();
@@ -154,11 +427,18 @@ impl FlexServInstanceBuilder {
         if tapis_user.is_empty() {
             return Err(ValidationError::EmptyTapisUser);
         }
-        let default_model = self.default_model.unwrap_or_default().trim().to_string();
-        if default_model.is_empty() {
-            return Err(ValidationError::EmptyModelId);
-        }
+        let default_model = normalize_hf_model_id(&self.default_model.unwrap_or_default())?;
         let backend = self.backend.ok_or(ValidationError::MissingBackend)?;
+        let extra_models = self
+            .extra_models
+            .into_iter()
+            .map(|m| {
+                Ok(ExtraModel {
+                    repo: normalize_hf_model_id(&m.repo)?,
+                    ..m
+                })
+            })
+            .collect::<Result<Vec<_>, ValidationError>>()?;
         Ok(FlexServInstance {
             tenant_url: tenant_url.to_string(),
             tapis_user,
@@ -166,7 +446,9 @@ impl FlexServInstanceBuilder {
             model_revision: self.model_revision,
             hf_token: self.hf_token,
             default_embedding_model: self.default_embedding_model,
+            extra_models,
             backend,
+            deployment_tag: self.deployment_tag,
         })
     }
 }
@@ -192,7 +474,9 @@ impl FlexServInstance {
             model_revision: model.model_revision.clone(),
             hf_token: model.hf_token.clone(),
             default_embedding_model: model.default_embedding_model.clone(),
+            extra_models: model.extra_models.clone(),
             backend,
+            deployment_tag: None,
         }
     }
 
@@ -212,16 +496,61 @@ impl FlexServInstance {
             model_revision,
             hf_token,
             default_embedding_model,
+            extra_models: Vec::new(),
             backend,
+            deployment_tag: None,
+        }
+    }
+
+    /// Same instance with a different backend (e.g. to compare vllm vs transformers for one model).
+    /// The backend is part of [FlexServInstance::deployment_hash], so derived pod/volume ids change too.
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Tenant host without scheme or API root, e.g. `https://tacc.tapis.io/v3` -> `tacc.tapis.io`.
+    pub fn tenant_name(&self) -> String {
+        let url = canonical_tenant_url(&self.tenant_url);
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(&url);
+        host.split('/').next().unwrap_or(host).to_string()
+    }
+
+    /// One-line description for logs and CLI output, e.g.
+    /// `testuser@tacc.tapis.io model=gpt2 backend=transformers hash=abc123def456`
+    /// (with ` tag=...` appended when [FlexServInstance::deployment_tag] is set).
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "{}@{} model={} backend={} hash={}",
+            self.tapis_user,
+            self.tenant_name(),
+            self.default_model,
+            self.backend.as_str(),
+            self.deployment_hash()
+        );
+        if let Some(ref tag) = self.deployment_tag {
+            out.push_str(&format!(" tag={}", tag));
         }
+        out
     }
 
     pub fn deployment_hash(&self) -> String {
         // Create a unique hash for the deployment configuration
-        let config_string = format!(
+        let mut config_string = format!(
             "{}@{}-{}-{:?}",
-            self.tapis_user, self.tenant_url, self.default_model, self.backend
+            self.tapis_user,
+            canonical_tenant_url(&self.tenant_url),
+            self.default_model,
+            self.backend
         );
+        // Only tagged instances change the input, so untagged ids stay as before.
+        if let Some(ref tag) = self.deployment_tag {
+            config_string.push('#');
+            config_string.push_str(tag);
+        }
         let digest = Sha256::digest(config_string.as_bytes());
 
         // Encode full 256-bit SHA256 to base62 and take first 12 characters
@@ -305,6 +634,23 @@ mod tests {
         assert!(matches!(err, ValidationError::EmptyModelId));
     }
 
+    #[test]
+    fn test_builder_normalizes_model_id() {
+        let builder = FlexServInstance::builder()
+            .tenant_url("https://tacc.tapis.io")
+            .tapis_user("u")
+            .backend(Backend::Transformers { command: vec![] });
+        let server = builder
+            .clone()
+            .model(" openai-community/gpt2/ ")
+            .build()
+            .unwrap();
+        assert_eq!(server.default_model, "openai-community/gpt2");
+        let err = builder.model("openai community/gpt2").build().unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidModelId(_)));
+        assert!(err.to_string().starts_with("invalid model_id:"));
+    }
+
     #[test]
     fn test_builder_validation_bad_url() {
         let err = FlexServInstance::builder()
@@ -317,18 +663,44 @@ mod tests {
         assert!(matches!(err, ValidationError::InvalidTenantUrl(_)));
     }
 
+    #[test]
+    fn test_validation_error_serializes_field_message_code() {
+        let err = FlexServInstance::builder()
+            .tenant_url("not-a-url")
+            .tapis_user("u")
+            .model("m")
+            .backend(Backend::Transformers { command: vec![] })
+            .build()
+            .unwrap_err();
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["code"], "invalid_tenant_url");
+        assert_eq!(json["field"], "tenant_url");
+        assert_eq!(json["message"], err.to_string());
+        assert!(err.to_string().starts_with("invalid tenant URL:"));
+
+        let json = serde_json::to_value(ValidationError::InvalidQuantity("\"2x\"".into())).unwrap();
+        assert_eq!(json["code"], "invalid_quantity");
+        assert!(json["field"].is_null());
+        assert_eq!(
+            serde_json::to_value(ValidationError::MissingBackend).unwrap(),
+            serde_json::json!({"field": "backend", "message": "backend is required", "code": "missing_backend"})
+        );
+    }
+
     #[test]
     fn test_from_configs() {
         let tapis = TapisConfig {
             tenant_url: "https://tacc.tapis.io".to_string(),
             tapis_user: "u".to_string(),
             tapis_token: "token".to_string(),
+            token_provider: None,
         };
         let model = ModelConfig {
             model_id: "openai-community/gpt2".to_string(),
             model_revision: Some("main".to_string()),
             hf_token: None,
             default_embedding_model: None,
+            extra_models: Vec::new(),
         };
         let server = FlexServInstance::from_configs(
             &tapis,
@@ -350,4 +722,164 @@ mod tests {
             .unwrap();
         assert_eq!(server.tenant_url, "https://tacc.tapis.io");
     }
+
+    #[tokio::test]
+    async fn test_estimate_volume_mb_from_hf_metadata() {
+        let mock = crate::test_support::MockServer::start(|_| {
+            // 3 GB of weights + small config files
+            (
+                200,
+                r#"{"siblings":[{"rfilename":"model.safetensors","size":3221225472},{"rfilename":"config.json","size":1024},{"rfilename":"README.md"}]}"#
+                    .to_string(),
+            )
+        });
+        let model = ModelConfig {
+            model_id: "org/model".to_string(),
+            model_revision: Some("main".to_string()),
+            hf_token: Some("hf_x".to_string()),
+            default_embedding_model: None,
+            extra_models: Vec::new(),
+        };
+        let mb = model
+            .estimate_volume_mb_with_endpoint(&mock.base_url)
+            .await
+            .unwrap();
+        // 3073 MB + 25% + 1 GB, rounded up to whole GB
+        assert_eq!(mb, 5 * 1024);
+        let reqs = mock.requests();
+        assert_eq!(reqs[0].path, "/api/models/org/model/revision/main?blobs=true");
+        assert_eq!(reqs[0].header("authorization"), Some("Bearer hf_x"));
+    }
+
+    #[test]
+    fn test_is_known_gated_model() {
+        assert!(is_known_gated_model("meta-llama/Llama-2-7b-hf"));
+        assert!(is_known_gated_model("Meta-Llama/Meta-Llama-3-8B"));
+        assert!(is_known_gated_model("google/gemma-2b"));
+        assert!(!is_known_gated_model("google/flan-t5-base"));
+        assert!(!is_known_gated_model("openai-community/gpt2"));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_volume_mb_api_failure_is_none() {
+        let mock = crate::test_support::MockServer::start(|_| (404, "{}".to_string()));
+        let model = ModelConfig {
+            model_id: "org/missing".to_string(),
+            model_revision: None,
+            hf_token: None,
+            default_embedding_model: None,
+            extra_models: Vec::new(),
+        };
+        assert_eq!(model.estimate_volume_mb_with_endpoint(&mock.base_url).await, None);
+    }
+
+    #[test]
+    fn test_with_backend_changes_hash() {
+        let server = FlexServInstance::new(
+            "https://tacc.tapis.io".to_string(),
+            "testuser".to_string(),
+            "Qwen/Qwen3-0.6B".to_string(),
+            Some("main".to_string()),
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        let vllm = server.clone().with_backend(Backend::VLlm { command: vec![] });
+        assert_eq!(vllm.backend, Backend::VLlm { command: vec![] });
+        assert_eq!(vllm.default_model, server.default_model);
+        assert_eq!(vllm.model_revision, server.model_revision);
+        assert_ne!(vllm.deployment_hash(), server.deployment_hash());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut server = FlexServInstance::new(
+            "https://tacc.tapis.io/v3/".to_string(),
+            "testuser".to_string(),
+            "gpt2".to_string(),
+            None,
+            None,
+            None,
+            Backend::Transformers { command: vec![] },
+        );
+        assert_eq!(server.tenant_name(), "tacc.tapis.io");
+        assert_eq!(
+            server.summary(),
+            format!(
+                "testuser@tacc.tapis.io model=gpt2 backend=transformers hash={}",
+                server.deployment_hash()
+            )
+        );
+        server.deployment_tag = Some("b".to_string());
+        assert!(server.summary().ends_with(" tag=b"));
+    }
+
+    #[test]
+    fn test_deployment_hash_includes_tag() {
+        let make = |tag: Option<&str>| {
+            let mut builder = FlexServInstance::builder()
+                .tenant_url("https://tacc.tapis.io")
+                .tapis_user("testuser")
+                .model("Qwen/Qwen3-0.6B")
+                .backend(Backend::Transformers { command: vec![] });
+            if let Some(tag) = tag {
+                builder = builder.tag(tag);
+            }
+            builder.build().unwrap()
+        };
+        let untagged = make(None);
+        assert_eq!(untagged.deployment_hash(), make(None).deployment_hash());
+        assert_eq!(
+            untagged.deployment_hash(),
+            FlexServInstance::new(
+                "https://tacc.tapis.io".to_string(),
+                "testuser".to_string(),
+                "Qwen/Qwen3-0.6B".to_string(),
+                None,
+                None,
+                None,
+                Backend::Transformers { command: vec![] },
+            )
+            .deployment_hash()
+        );
+        let a = make(Some("a")).deployment_hash();
+        let b = make(Some("b")).deployment_hash();
+        assert_ne!(a, b);
+        assert_ne!(a, untagged.deployment_hash());
+        assert_eq!(a, make(Some("a")).deployment_hash());
+    }
+
+    #[test]
+    fn test_deployment_hash_ignores_equivalent_tenant_url_forms() {
+        let make = |tenant_url: &str| FlexServInstance {
+            tenant_url: tenant_url.to_string(),
+            tapis_user: "testuser".to_string(),
+            default_model: "Qwen/Qwen3-0.6B".to_string(),
+            model_revision: None,
+            hf_token: None,
+            default_embedding_model: None,
+            extra_models: Vec::new(),
+            backend: Backend::Transformers { command: vec![] },
+            deployment_tag: None,
+        };
+        let base = make("https://tacc.tapis.io").deployment_hash();
+        assert_eq!(make("https://tacc.tapis.io/").deployment_hash(), base);
+        assert_eq!(make("https://tacc.tapis.io/v3").deployment_hash(), base);
+        assert_eq!(make("https://tacc.tapis.io/v3/").deployment_hash(), base);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_model_revision() {
+        let mock = crate::test_support::MockServer::start(|req| {
+            if req.path == "/api/models/org/model/revision/main" {
+                (200, r#"{"id":"org/model","sha":"0123abcd"}"#.to_string())
+            } else {
+                (404, "{}".to_string())
+            }
+        });
+        let sha = resolve_model_revision(&mock.base_url, "org/model", Some("main"), None).await;
+        assert_eq!(sha.as_deref(), Some("0123abcd"));
+        let missing = resolve_model_revision(&mock.base_url, "org/model", Some("nope"), None).await;
+        assert_eq!(missing, None);
+    }
 }
@@ -0,0 +1,151 @@
+//! Test-only helpers: a tiny in-process HTTP server that records every request and replies
+//! with canned responses, so deployment logic can be exercised without a real TAPIS tenant.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use crate::backend::Backend;
+use crate::deployment::{FlexServPodDeployment, PodDeploymentOptions};
+use crate::server::FlexServInstance;
+
+/// Minimal Pods API envelope for pod `pabc` in AVAILABLE state.
+pub const POD_RESPONSE_JSON: &str = r#"{"message":"ok","metadata":{},"result":{"pod_id":"pabc","image":"tapis/flexserv:1.0","status":"AVAILABLE"},"status":"success","version":"dev"}"#;
+
+/// Transformers server for user `u` serving `model` from the tenant at `tenant_url`.
+pub fn test_instance(tenant_url: &str, model: &str) -> FlexServInstance {
+    FlexServInstance::new(
+        tenant_url.to_string(),
+        "u".to_string(),
+        model.to_string(),
+        None,
+        None,
+        None,
+        Backend::Transformers { command: vec![] },
+    )
+}
+
+/// Deployment of `org/model` for user `u` against the mock Pods API at `base_url`.
+pub fn mock_deployment(base_url: &str, options: PodDeploymentOptions) -> FlexServPodDeployment {
+    let server = test_instance(base_url, "org/model");
+    FlexServPodDeployment::with_options(server, "token".to_string(), options)
+}
+
+/// Handle to an already-created pod `pod_id` and volume `volume_id` at `base_url`.
+pub fn existing_deployment(base_url: &str, pod_id: &str, volume_id: &str) -> FlexServPodDeployment {
+    FlexServPodDeployment::from_existing(
+        test_instance(base_url, "m"),
+        "token".to_string(),
+        pod_id.to_string(),
+        volume_id.to_string(),
+    )
+}
+
+/// One request received by [MockServer].
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Case-insensitive header lookup.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Recording HTTP/1.1 server bound to `127.0.0.1` on a random port.
+/// The handler returns `(status, json_body)` for each request.
+pub struct MockServer {
+    /// Base URL (e.g. `http://127.0.0.1:45678`); use as tenant URL.
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&RecordedRequest) -> (u16, String) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server addr");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let Some(req) = read_request(&mut stream) else {
+                    continue;
+                };
+                let (status, body) = handler(&req);
+                recorded.lock().unwrap().push(req);
+                let resp = format!(
+                    "HTTP/1.1 {} MOCK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(resp.as_bytes());
+                let _ = stream.flush();
+            }
+        });
+        Self {
+            base_url: format!("http://{}", addr),
+            requests,
+        }
+    }
+
+    /// Snapshot of all requests received so far, in arrival order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// HTTP methods of all requests received so far, in arrival order.
+    pub fn methods(&self) -> Vec<String> {
+        self.requests().into_iter().map(|r| r.method).collect()
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = Vec::new();
+    let mut content_length = 0usize;
+    loop {
+        let mut h = String::new();
+        if reader.read_line(&mut h).ok()? == 0 {
+            break;
+        }
+        let h = h.trim_end();
+        if h.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = h.split_once(':') {
+            let (k, v) = (k.trim().to_string(), v.trim().to_string());
+            if k.eq_ignore_ascii_case("content-length") {
+                content_length = v.parse().unwrap_or(0);
+            }
+            headers.push((k, v));
+        }
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).ok()?;
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
@@ -1,28 +1,50 @@
 //! Crate-wide utility functions (URL normalization, string predicates, ID normalization).
 
+use crate::backend::Backend;
+use crate::server::ValidationError;
+
 /// Returns true if `s` is a non-empty absolute HTTP or HTTPS URL (after trimming).
 pub fn is_absolute_http_url(s: &str) -> bool {
     let s = s.trim();
     (s.starts_with("https://") || s.starts_with("http://")) && s.len() > 8
 }
 
+/// Normalize a tenant URL: add `https://` when the scheme is missing (host must look like a
+/// domain or `localhost`), then canonicalize with [canonical_tenant_url].
 pub fn normalize_tenant_url(url: &str) -> String {
     let s = url.trim();
     if s.is_empty() {
         return s.to_string();
     }
     if s.starts_with("https://") || s.starts_with("http://") {
-        return s.to_string();
+        return canonical_tenant_url(s);
     }
 
     let host = s.split('/').next().unwrap_or(s);
     if host.contains('.') || host == "localhost" {
-        format!("https://{}", s)
+        canonical_tenant_url(&format!("https://{}", s))
     } else {
         s.to_string()
     }
 }
 
+/// Canonical form of a tenant URL so equivalent spellings compare (and hash) equal:
+/// trims whitespace and strips trailing slashes and a trailing `/v3` API root.
+/// e.g. `https://tacc.tapis.io/`, `https://tacc.tapis.io/v3/` -> `https://tacc.tapis.io`.
+pub fn canonical_tenant_url(url: &str) -> String {
+    let s = url.trim().trim_end_matches('/');
+    s.strip_suffix("/v3")
+        .unwrap_or(s)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// TAPIS v3 API root for a tenant URL given with or without `/v3` and trailing slashes,
+/// e.g. `https://tacc.tapis.io/v3/` -> `https://tacc.tapis.io/v3`.
+pub fn tapis_api_base(tenant_url: &str) -> String {
+    format!("{}/v3", canonical_tenant_url(tenant_url))
+}
+
 /// Normalize a string to lowercase ASCII alphanumeric only (e.g. strip dashes from a UUID).
 /// Useful for deriving stable IDs from user input.
 pub fn normalize_to_lowercase_alphanumeric(s: &str) -> String {
@@ -32,10 +54,586 @@ pub fn normalize_to_lowercase_alphanumeric(s: &str) -> String {
         .collect()
 }
 
+/// Normalize a Hugging Face model id: trim whitespace and trailing slashes, then check the
+/// `org/name` shape (a single-segment id like `gpt2` is also accepted). Segments may contain
+/// ASCII letters, digits, `-`, `_`, and `.`, but not `..`. Case is preserved.
+pub fn normalize_hf_model_id(model_id: &str) -> Result<String, ValidationError> {
+    let id = model_id.trim().trim_end_matches('/');
+    if id.is_empty() {
+        return Err(ValidationError::EmptyModelId);
+    }
+    let invalid = |reason: &str| ValidationError::InvalidModelId(format!("{:?} {}", id, reason));
+    let segments: Vec<&str> = id.split('/').collect();
+    if segments.len() > 2 {
+        return Err(invalid("must be `name` or `org/name`"));
+    }
+    for segment in segments {
+        if segment.is_empty() {
+            return Err(invalid("has an empty path segment"));
+        }
+        if segment.contains("..") {
+            return Err(invalid("must not contain `..`"));
+        }
+        if !segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        {
+            return Err(invalid(
+                "may only contain letters, digits, `-`, `_`, and `.` (no spaces)",
+            ));
+        }
+    }
+    Ok(id.to_string())
+}
+
+/// Parse a Kubernetes-style quantity into its base value: a plain number (`2`, `0.5`), milli
+/// (`500m` = 0.5), decimal (`k`, `M`, `G`, `T`), or binary (`Ki`, `Mi`, `Gi`, `Ti`) suffixes,
+/// e.g. `512Mi` = 536870912. Negative, empty, or unknown-suffix quantities are rejected.
+pub fn parse_quantity(quantity: &str) -> Result<f64, ValidationError> {
+    let q = quantity.trim();
+    let invalid = |reason: &str| ValidationError::InvalidQuantity(format!("{:?} {}", q, reason));
+    let split = q
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(q.len());
+    let (number, suffix) = q.split_at(split);
+    if number.is_empty() {
+        return Err(invalid("must start with a non-negative number"));
+    }
+    let value: f64 = number
+        .parse()
+        .map_err(|_| invalid("has an invalid number"))?;
+    let multiplier = match suffix {
+        "" => 1.0,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "Ki" => 1024.0,
+        "Mi" => 1024.0 * 1024.0,
+        "Gi" => 1024.0 * 1024.0 * 1024.0,
+        "Ti" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => {
+            return Err(invalid(
+                "has an unknown unit (use m, k, M, G, T, Ki, Mi, Gi, or Ti)",
+            ))
+        }
+    };
+    Ok(value * multiplier)
+}
+
+/// Memory quantity (e.g. `512Mi`, `8Gi`, `1G`) in whole MB (MiB), rounded up. A unit is
+/// required, since a bare number would be bytes and is almost always a sizing mistake.
+pub fn parse_memory_mb(quantity: &str) -> Result<i32, ValidationError> {
+    let q = quantity.trim();
+    if q.ends_with(|c: char| c.is_ascii_digit() || c == '.') || q.ends_with('m') {
+        return Err(ValidationError::InvalidQuantity(format!(
+            "{:?} needs a memory unit (e.g. 512Mi, 8Gi)",
+            q
+        )));
+    }
+    let mb = (parse_quantity(q)? / (1024.0 * 1024.0)).ceil();
+    if mb < 1.0 || mb > i32::MAX as f64 {
+        return Err(ValidationError::InvalidQuantity(format!(
+            "{:?} is out of range",
+            q
+        )));
+    }
+    Ok(mb as i32)
+}
+
+/// CPU quantity (e.g. `2`, `0.5`, `500m`) in millicpus, rounded up.
+pub fn parse_cpu_millis(quantity: &str) -> Result<i32, ValidationError> {
+    let q = quantity.trim();
+    let millis = (parse_quantity(q)? * 1000.0).ceil();
+    if millis < 1.0 || millis > i32::MAX as f64 {
+        return Err(ValidationError::InvalidQuantity(format!(
+            "{:?} is out of range",
+            q
+        )));
+    }
+    Ok(millis as i32)
+}
+
+/// Join `path` onto `base` with exactly one `/` between them, keeping any path `base` already
+/// has: `("https://host/pods/p1/", "/v1/models")` -> `https://host/pods/p1/v1/models`.
+pub fn join_url_path(base: &str, path: &str) -> String {
+    format!(
+        "{}/{}",
+        base.trim().trim_end_matches('/'),
+        path.trim().trim_start_matches('/')
+    )
+}
+
+/// Percent-encode `path` for use in a URL path: every byte except unreserved characters
+/// (`A-Za-z0-9-._~`) and `/` becomes `%XX`, so `?`, `#`, spaces, etc. stay part of the path.
+pub fn percent_encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+
+/// Recursively merge `overrides` into `base`: objects are merged key by key, any other value
+/// (including arrays) in `overrides` replaces the one in `base`.
+pub fn deep_merge_json(base: &mut serde_json::Value, overrides: &serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(b), serde_json::Value::Object(o)) => {
+            for (k, v) in o {
+                deep_merge_json(b.entry(k.clone()).or_insert(serde_json::Value::Null), v);
+            }
+        }
+        (b, o) => *b = o.clone(),
+    }
+}
+
+/// One entry of [json_diff]: `(pointer, old, new)`.
+pub type JsonDiffEntry = (String, Option<serde_json::Value>, Option<serde_json::Value>);
+
+/// Leaf-level differences between two JSON values as `(pointer, old, new)`, where `pointer` is a
+/// JSON pointer (e.g. `/resources/gpus`) and `old`/`new` are None when the key is missing on that
+/// side. Objects are compared key by key; arrays and scalars are compared as whole values.
+pub fn json_diff(old: &serde_json::Value, new: &serde_json::Value) -> Vec<JsonDiffEntry> {
+    let mut out = Vec::new();
+    json_diff_into(String::new(), Some(old), Some(new), &mut out);
+    out
+}
+
+fn json_diff_into(
+    path: String,
+    old: Option<&serde_json::Value>,
+    new: Option<&serde_json::Value>,
+    out: &mut Vec<JsonDiffEntry>,
+) {
+    match (old, new) {
+        (Some(serde_json::Value::Object(o)), Some(serde_json::Value::Object(n))) => {
+            let mut keys: Vec<&String> = o.keys().chain(n.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for k in keys {
+                let escaped = k.replace('~', "~0").replace('/', "~1");
+                json_diff_into(format!("{}/{}", path, escaped), o.get(k), n.get(k), out);
+            }
+        }
+        (o, n) if o != n => out.push((path, o.cloned(), n.cloned())),
+        _ => {}
+    }
+}
+
+/// Quote `s` for POSIX sh as a single word (`'...'`, with embedded quotes escaped).
+pub fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+    {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Split `s` into words like POSIX sh: whitespace separates words, `'...'` is literal,
+/// `"..."` allows `\"` and `\\` escapes, and a backslash outside quotes escapes the next
+/// character. No expansion is done. Returns None on an unterminated quote or trailing backslash.
+pub fn shell_split(s: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                word.push(chars.next()?);
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+/// Parse a dotenv-style file: one `KEY=VALUE` per line, optionally prefixed by `export `.
+/// Blank lines and `#` comments are skipped. Values may be `'...'` (literal) or `"..."` (with
+/// `\n`, `\t`, `\"` and `\\` escapes); unquoted values are trimmed and end at ` #`.
+/// Later duplicates replace earlier ones. Errors name the offending line.
+pub fn parse_dotenv(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let err = |msg: &str| Err(format!("line {}: {}", i + 1, msg));
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let Some((key, rest)) = line.split_once('=') else {
+            return err("expected KEY=VALUE");
+        };
+        let key = key.trim();
+        if key.is_empty()
+            || key.starts_with(|c: char| c.is_ascii_digit())
+            || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return err(&format!("invalid key {:?}", key));
+        }
+        let rest = rest.trim_start();
+        let (value, tail) = if let Some(quoted) = rest.strip_prefix('\'') {
+            match quoted.find('\'') {
+                Some(end) => (quoted[..end].to_string(), &quoted[end + 1..]),
+                None => return err("unterminated single quote"),
+            }
+        } else if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = None;
+            while let Some((j, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        end = Some(j + 1);
+                        break;
+                    }
+                    '\\' => match chars.next().map(|(_, c)| c) {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(c @ ('"' | '\\')) => value.push(c),
+                        Some(c) => {
+                            value.push('\\');
+                            value.push(c);
+                        }
+                        None => break,
+                    },
+                    c => value.push(c),
+                }
+            }
+            match end {
+                Some(end) => (value, &quoted[end..]),
+                None => return err("unterminated double quote"),
+            }
+        } else {
+            let value = match rest.find(" #") {
+                Some(idx) => &rest[..idx],
+                None => rest,
+            };
+            (value.trim_end().to_string(), "")
+        };
+        let tail = tail.trim_start();
+        if !tail.is_empty() && !tail.starts_with('#') {
+            return err("unexpected text after closing quote");
+        }
+        vars.retain(|(k, _)| k != key);
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+/// Unix seconds of an ISO 8601 timestamp as TAPIS returns them: `YYYY-MM-DD[T ]HH:MM:SS`,
+/// optional fractional seconds, then `Z`, `+HH:MM`/`-HH:MM`, or nothing (UTC). None otherwise.
+pub fn parse_timestamp_secs(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let num = |r: std::ops::Range<usize>| -> Option<i64> {
+        let part = s.get(r)?;
+        part.bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| part.parse().ok())?
+    };
+    let bytes = s.as_bytes();
+    if bytes.len() < 19
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+    let mut rest = &s[19..];
+    if let Some(frac) = rest.strip_prefix('.') {
+        let digits = frac.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        rest = &frac[digits..];
+    }
+    let offset = match rest {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let (h, m) = rest[1..].split_once(':')?;
+            if h.len() != 2 || m.len() != 2 {
+                return None;
+            }
+            sign * (h.parse::<i64>().ok()? * 3600 + m.parse::<i64>().ok()? * 60)
+        }
+    };
+    // Days since 1970-01-01 (proleptic Gregorian), from Howard Hinnant's days_from_civil.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+/// Images whose supported backends are known, as `(image, backends)`. An entry with a tag
+/// matches only that tag; one without matches every tag and digest of the repository.
+const KNOWN_IMAGE_BACKENDS: &[(&str, &[&str])] = &[
+    ("tapis/flexserv:1.0", &["transformers"]),
+    ("vllm/vllm-openai", &["vllm"]),
+    ("lmsysorg/sglang", &["sglang"]),
+    ("nvcr.io/nvidia/tritonserver", &["trtllm"]),
+];
+
+/// Whether `image` can run `backend`, from [KNOWN_IMAGE_BACKENDS]. None when the image is not
+/// listed or the backend is [Backend::Custom]. A `docker.io/` or `docker.io/library/` prefix
+/// is ignored.
+pub fn image_supports_backend(image: &str, backend: &Backend) -> Option<bool> {
+    if matches!(backend, Backend::Custom { .. }) {
+        return None;
+    }
+    let image = image.trim();
+    let image = image
+        .strip_prefix("docker.io/library/")
+        .or_else(|| image.strip_prefix("docker.io/"))
+        .unwrap_or(image);
+    let image = image.split('@').next().unwrap_or(image);
+    // The tag follows the last ':' after the last '/' (a registry may have a port).
+    let repo = match image.rfind(':') {
+        Some(idx) if !image[idx..].contains('/') => &image[..idx],
+        _ => image,
+    };
+    KNOWN_IMAGE_BACKENDS
+        .iter()
+        .find(|(known, _)| *known == image || (!known.contains(':') && *known == repo))
+        .map(|(_, backends)| backends.contains(&backend.as_str()))
+}
+
+/// Encode `n` in lowercase base36 (`0-9a-z`), e.g. 35 -> "z", 36 -> "10".
+pub fn to_base36(mut n: u32) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut out = Vec::new();
+    loop {
+        out.push(DIGITS[(n % 36) as usize]);
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    out.reverse();
+    String::from_utf8(out).expect("ascii digits")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_timestamp_secs() {
+        assert_eq!(parse_timestamp_secs("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_timestamp_secs("2024-03-01T12:30:15Z"),
+            Some(1_709_296_215)
+        );
+        assert_eq!(
+            parse_timestamp_secs("2024-03-01 12:30:15.123456"),
+            Some(1_709_296_215)
+        );
+        assert_eq!(
+            parse_timestamp_secs("2024-03-01T14:30:15+02:00"),
+            Some(1_709_296_215)
+        );
+        assert_eq!(parse_timestamp_secs("2024-13-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp_secs("2024-03-01"), None);
+        assert_eq!(parse_timestamp_secs("2024-03-01T12:30:15 UTC"), None);
+    }
+
+    #[test]
+    fn test_percent_encode_path() {
+        assert_eq!(
+            percent_encode_path("work/models/llama-3.1_x~"),
+            "work/models/llama-3.1_x~"
+        );
+        assert_eq!(
+            percent_encode_path("a b/c?d#e%f'g"),
+            "a%20b/c%3Fd%23e%25f%27g"
+        );
+        assert_eq!(percent_encode_path("modèle"), "mod%C3%A8le");
+    }
+
+    #[test]
+    fn test_shell_split() {
+        assert_eq!(
+            shell_split("--dtype half --max-num-seqs 256").unwrap(),
+            vec!["--dtype", "half", "--max-num-seqs", "256"]
+        );
+        assert_eq!(
+            shell_split(r#"  --chat-template '{{ a b }}' --x "say \"hi\"" a\ b ''"#).unwrap(),
+            vec![
+                "--chat-template",
+                "{{ a b }}",
+                "--x",
+                "say \"hi\"",
+                "a b",
+                ""
+            ]
+        );
+        assert_eq!(shell_split("").unwrap(), Vec::<String>::new());
+        assert!(shell_split("--x 'open").is_none());
+        assert!(shell_split("--x \"open").is_none());
+        assert!(shell_split("trailing\\").is_none());
+    }
+
+    #[test]
+    fn test_image_supports_backend() {
+        let transformers = Backend::Transformers { command: vec![] };
+        let vllm = Backend::VLlm { command: vec![] };
+        assert_eq!(
+            image_supports_backend("tapis/flexserv:1.0", &transformers),
+            Some(true)
+        );
+        assert_eq!(
+            image_supports_backend("tapis/flexserv:1.0", &vllm),
+            Some(false)
+        );
+        assert_eq!(
+            image_supports_backend("vllm/vllm-openai:v0.6.3", &vllm),
+            Some(true)
+        );
+        assert_eq!(
+            image_supports_backend("docker.io/vllm/vllm-openai@sha256:abc", &vllm),
+            Some(true)
+        );
+        assert_eq!(
+            image_supports_backend("vllm/vllm-openai", &transformers),
+            Some(false)
+        );
+        assert_eq!(
+            image_supports_backend("nvcr.io/nvidia/tritonserver:24.08-trtllm-python-py3", &vllm),
+            Some(false)
+        );
+        // Unknown images, other tags of a tag-specific entry, and custom backends.
+        assert_eq!(image_supports_backend("tapis/flexserv:2.0", &vllm), None);
+        assert_eq!(
+            image_supports_backend("myorg/llm:latest", &transformers),
+            None
+        );
+        assert_eq!(
+            image_supports_backend("registry:5000/vllm-openai", &vllm),
+            None
+        );
+        let custom = Backend::Custom {
+            name: "mine".to_string(),
+            command: vec![],
+        };
+        assert_eq!(image_supports_backend("tapis/flexserv:1.0", &custom), None);
+    }
+
+    #[test]
+    fn test_tapis_api_base_strips_existing_v3() {
+        for url in [
+            "https://tacc.tapis.io",
+            "https://tacc.tapis.io/",
+            "https://tacc.tapis.io/v3",
+            "https://tacc.tapis.io/v3/",
+            " https://tacc.tapis.io//v3// ",
+        ] {
+            assert_eq!(tapis_api_base(url), "https://tacc.tapis.io/v3", "{url:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let text = r#"
+# comment line
+PLAIN=value
+export EXPORTED=1
+SPACED = padded value   # trailing comment
+HASH=a#b
+SINGLE='literal $HOME \n # kept'
+DOUBLE="line1\nline2 \"q\" # kept" # comment
+EMPTY=
+PLAIN=override
+"#;
+        assert_eq!(
+            parse_dotenv(text).unwrap(),
+            vec![
+                ("EXPORTED".to_string(), "1".to_string()),
+                ("SPACED".to_string(), "padded value".to_string()),
+                ("HASH".to_string(), "a#b".to_string()),
+                ("SINGLE".to_string(), "literal $HOME \\n # kept".to_string()),
+                (
+                    "DOUBLE".to_string(),
+                    "line1\nline2 \"q\" # kept".to_string()
+                ),
+                ("EMPTY".to_string(), String::new()),
+                ("PLAIN".to_string(), "override".to_string()),
+            ]
+        );
+        assert_eq!(parse_dotenv("").unwrap(), Vec::new());
+        assert!(parse_dotenv("NO_EQUALS")
+            .unwrap_err()
+            .starts_with("line 1:"));
+        assert!(parse_dotenv("\n1BAD=x").unwrap_err().starts_with("line 2:"));
+        assert!(parse_dotenv("BAD-KEY=x").is_err());
+        assert!(parse_dotenv("OPEN='x").is_err());
+        assert!(parse_dotenv("OPEN=\"x").is_err());
+        assert!(parse_dotenv("TAIL='x' y").is_err());
+    }
+
     #[test]
     fn test_is_absolute_http_url() {
         assert!(is_absolute_http_url("https://tacc.tapis.io"));
@@ -75,4 +673,178 @@ mod tests {
         );
         assert_eq!(normalize_to_lowercase_alphanumeric("ABC-123"), "abc123");
     }
+
+    #[test]
+    fn test_normalize_hf_model_id() {
+        assert_eq!(
+            normalize_hf_model_id("openai-community/gpt2").unwrap(),
+            "openai-community/gpt2"
+        );
+        assert_eq!(
+            normalize_hf_model_id("  openai-community/gpt2//  ").unwrap(),
+            "openai-community/gpt2"
+        );
+        assert_eq!(
+            normalize_hf_model_id("Qwen/Qwen2.5-0.5B_Instruct").unwrap(),
+            "Qwen/Qwen2.5-0.5B_Instruct"
+        );
+        // Single-segment ids are fine.
+        assert_eq!(normalize_hf_model_id("gpt2/").unwrap(), "gpt2");
+
+        assert!(matches!(
+            normalize_hf_model_id(" / "),
+            Err(ValidationError::EmptyModelId)
+        ));
+        for bad in [
+            "org/my model",
+            "org/../etc",
+            "..",
+            "a/b/c",
+            "/gpt2",
+            "org//gpt2",
+            "org/gpt2?rev=main",
+        ] {
+            assert!(
+                matches!(
+                    normalize_hf_model_id(bad),
+                    Err(ValidationError::InvalidModelId(_))
+                ),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("2").unwrap(), 2.0);
+        assert_eq!(parse_quantity(" 0.5 ").unwrap(), 0.5);
+        assert_eq!(parse_quantity("500m").unwrap(), 0.5);
+        assert_eq!(parse_quantity("1k").unwrap(), 1000.0);
+        assert_eq!(parse_quantity("1G").unwrap(), 1e9);
+        assert_eq!(parse_quantity("512Mi").unwrap(), 536870912.0);
+        assert_eq!(parse_quantity("1Ti").unwrap(), 1099511627776.0);
+        for bad in ["", "Gi", "-1", "1.2.3", "8GB", "8 Gi", "8gi", "1e3"] {
+            assert!(
+                matches!(
+                    parse_quantity(bad),
+                    Err(ValidationError::InvalidQuantity(_))
+                ),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_memory_mb() {
+        assert_eq!(parse_memory_mb("512Mi").unwrap(), 512);
+        assert_eq!(parse_memory_mb("8Gi").unwrap(), 8192);
+        assert_eq!(parse_memory_mb("1.5Gi").unwrap(), 1536);
+        assert_eq!(parse_memory_mb("1G").unwrap(), 954); // 1e9 bytes, rounded up
+        assert_eq!(parse_memory_mb("1Ki").unwrap(), 1);
+        // Bare numbers and milli are rejected rather than read as bytes.
+        assert!(parse_memory_mb("8192").is_err());
+        assert!(parse_memory_mb("500m").is_err());
+        assert!(parse_memory_mb("0Mi").is_err());
+        assert!(parse_memory_mb("4096Ti").is_err());
+    }
+
+    #[test]
+    fn test_parse_cpu_millis() {
+        assert_eq!(parse_cpu_millis("2").unwrap(), 2000);
+        assert_eq!(parse_cpu_millis("0.5").unwrap(), 500);
+        assert_eq!(parse_cpu_millis("250m").unwrap(), 250);
+        assert_eq!(parse_cpu_millis("0.0001").unwrap(), 1);
+        assert!(parse_cpu_millis("0").is_err());
+        assert!(parse_cpu_millis("two").is_err());
+    }
+
+    #[test]
+    fn test_join_url_path() {
+        assert_eq!(
+            join_url_path("https://p1.pods.tacc.tapis.io", "/v1/models"),
+            "https://p1.pods.tacc.tapis.io/v1/models"
+        );
+        assert_eq!(
+            join_url_path("https://p1.pods.tacc.tapis.io/", "v1/models"),
+            "https://p1.pods.tacc.tapis.io/v1/models"
+        );
+        assert_eq!(
+            join_url_path("https://tacc.tapis.io/pods/p123", "/v1/completions"),
+            "https://tacc.tapis.io/pods/p123/v1/completions"
+        );
+        assert_eq!(
+            join_url_path("https://tacc.tapis.io/pods/p123//", "//v1/completions"),
+            "https://tacc.tapis.io/pods/p123/v1/completions"
+        );
+    }
+
+    #[test]
+    fn test_to_base36() {
+        assert_eq!(to_base36(0), "0");
+        assert_eq!(to_base36(9), "9");
+        assert_eq!(to_base36(10), "a");
+        assert_eq!(to_base36(35), "z");
+        assert_eq!(to_base36(36), "10");
+    }
+
+    #[test]
+    fn test_deep_merge_json() {
+        let mut base = serde_json::json!({"a": 1, "nested": {"x": 1, "y": 2}, "list": [1, 2]});
+        deep_merge_json(
+            &mut base,
+            &serde_json::json!({"nested": {"y": 3, "z": 4}, "list": [9], "new": true}),
+        );
+        let expected = serde_json::json!({
+            "a": 1,
+            "nested": {"x": 1, "y": 3, "z": 4},
+            "list": [9],
+            "new": true
+        });
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn test_canonical_tenant_url() {
+        assert_eq!(canonical_tenant_url("https://tacc.tapis.io"), "https://tacc.tapis.io");
+        assert_eq!(canonical_tenant_url("https://tacc.tapis.io/"), "https://tacc.tapis.io");
+        assert_eq!(canonical_tenant_url("https://tacc.tapis.io/v3"), "https://tacc.tapis.io");
+        assert_eq!(canonical_tenant_url("https://tacc.tapis.io/v3/"), "https://tacc.tapis.io");
+        assert_eq!(normalize_tenant_url("tacc.tapis.io/v3"), "https://tacc.tapis.io");
+    }
+
+    #[test]
+    fn test_json_diff() {
+        let old = serde_json::json!({"image": "a:1", "resources": {"gpus": 0, "cpu": 1}, "args": [1], "x/y": 1});
+        let new = serde_json::json!({"image": "a:2", "resources": {"gpus": 1, "cpu": 1}, "args": [1], "extra": true});
+        let diff = json_diff(&old, &new);
+        assert_eq!(
+            diff,
+            vec![
+                ("/extra".to_string(), None, Some(serde_json::json!(true))),
+                (
+                    "/image".to_string(),
+                    Some(serde_json::json!("a:1")),
+                    Some(serde_json::json!("a:2"))
+                ),
+                (
+                    "/resources/gpus".to_string(),
+                    Some(serde_json::json!(0)),
+                    Some(serde_json::json!(1))
+                ),
+                ("/x~1y".to_string(), Some(serde_json::json!(1)), None),
+            ]
+        );
+        assert!(json_diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/app/models/gpt2"), "/app/models/gpt2");
+        assert_eq!(shell_quote("--flexserv-token"), "--flexserv-token");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
 }
@@ -57,8 +57,8 @@ async fn test_create_functionality() {
     let server = make_server(&tenant_url, &expected_model_id);
     let mut deployment = FlexServPodDeployment::new(server, tapis_token);
 
-    // Test create() - this also tests cleanup of existing pods/volumes (handled inside create_impl)
-    let result = deployment.create().await;
+    // Test recreate() - deletes leftover pods/volumes under the same ids, then creates
+    let result = deployment.recreate().await;
     let create_result = result
         .as_ref()
         .map_err(|e| panic!("create failed: {:?}", e))
@@ -74,6 +74,7 @@ async fn test_create_functionality() {
             tapis_user,
             tapis_tenant: _,
             model_id,
+            ..
         } => {
             assert!(
                 !pod_id.is_empty(),